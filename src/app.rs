@@ -1,9 +1,9 @@
-//! Application specific traits and functions. For example `code` is a
-//! ted-application.
+//! Application specific traits and functions. For example `code` and
+//! `less` are ted-applications.
 
-use std::sync::mpsc;
+use std::{sync::mpsc, time};
 
-use crate::{code, event::Event, pubsub::Notify, state, window::Cursor, Result};
+use crate::{code, event::Event, less, pubsub::Notify, state, window::Cursor, Result};
 #[allow(unused_imports)]
 use crate::{pubsub::PubSub, window::Coord};
 
@@ -27,12 +27,18 @@ pub trait Application {
     /// Return the cursor within application's view-port.
     fn to_cursor(&self) -> Option<Cursor>;
 
+    /// Return the duration to wait for the next key, while application is
+    /// resolving an ambiguous key sequence. If no key is received within
+    /// the duration, application is handled with [Event::Timeout].
+    fn to_timeout(&self) -> Option<time::Duration>;
+
     /// Return a string less than the specified width.
     fn to_tab_title(&self, wth: usize) -> state::TabTitle;
 }
 
 pub enum App {
     Code(code::Code),
+    Less(less::Less),
     None,
 }
 
@@ -46,6 +52,7 @@ impl App {
     pub fn subscribe(&mut self, topic: &str, tx: mpsc::Sender<Notify>) {
         match self {
             App::Code(app) => app.subscribe(topic, tx),
+            App::Less(app) => app.subscribe(topic, tx),
             App::None => (),
         }
     }
@@ -53,6 +60,7 @@ impl App {
     pub fn notify(&self, topic: &str, msg: Notify) -> Result<()> {
         match self {
             App::Code(app) => app.notify(topic, msg),
+            App::Less(app) => app.notify(topic, msg),
             App::None => Ok(()),
         }
     }
@@ -60,6 +68,15 @@ impl App {
     pub fn to_cursor(&self) -> Option<Cursor> {
         match self {
             App::Code(app) => app.to_cursor(),
+            App::Less(app) => app.to_cursor(),
+            App::None => None,
+        }
+    }
+
+    pub fn to_timeout(&self) -> Option<time::Duration> {
+        match self {
+            App::Code(app) => app.to_timeout(),
+            App::Less(app) => app.to_timeout(),
             App::None => None,
         }
     }
@@ -67,6 +84,7 @@ impl App {
     pub fn on_event(&mut self, evnt: Event) -> Result<Event> {
         match self {
             App::Code(app) => app.on_event(evnt),
+            App::Less(app) => app.on_event(evnt),
             App::None => Ok(evnt),
         }
     }
@@ -74,6 +92,7 @@ impl App {
     pub fn on_refresh(&mut self) -> Result<()> {
         match self {
             App::Code(app) => app.on_refresh(),
+            App::Less(app) => app.on_refresh(),
             App::None => Ok(()),
         }
     }
@@ -81,6 +100,7 @@ impl App {
     pub fn to_tab_title(&self, wth: usize) -> state::TabTitle {
         match self {
             App::Code(app) => app.to_tab_title(wth),
+            App::Less(app) => app.to_tab_title(wth),
             App::None => unreachable!(),
         }
     }
@@ -23,6 +23,7 @@ use crate::{
     event::{Event, Mod, Mto, DP},
     location::Location,
     mark,
+    search::{self, Offset, Search, Substitute},
     term::{Span, Spanline},
     text,
     window::WinBuffer,
@@ -129,10 +130,17 @@ pub struct Buffer {
     marks: mark::Marks,
     // sticky state for cursor column.
     sticky_col: StickyCol,
-    // Last search command applied on this buffer.
-    mto_pattern: Mto,
+    // Last search applied on this buffer, the last one in a `;` chain,
+    // used to repeat the search and for empty patterns.
+    last_search: Option<Search>,
+    // Search patterns are case insensitive.
+    ignore_case: bool,
+    // Override ignore_case, if search pattern contains upper-case.
+    smart_case: bool,
     // Last find character command (within the line) applied on this buffer.
     mto_find_char: Mto,
+    // Change, and its events, when the buffer was last saved.
+    saved: Option<(rc::Weak<RefCell<Change>>, Event)>,
 }
 
 #[derive(Clone)]
@@ -246,16 +254,15 @@ impl Inner {
 impl Buffer {
     /// Create a new instance of buffer pre-populating it with
     /// content from `loc`. Refer [Location] for details.
-    pub fn from_reader(loc: Location) -> Result<Buffer> {
-        let buf = {
-            let bytes = loc.to_bytes()?;
-            err_at!(FailBuffer, Rope::from_reader(bytes.as_slice()))?
-        };
+    pub fn from_reader(mut loc: Location) -> Result<Buffer> {
+        loc.refresh_stat();
+        let buf = loc.to_rope()?;
+        let (format, _) = text::Format::detect_chunks(buf.chunks());
         let mut num = BUFFER_NUM.lock().unwrap();
         *num = *num + 1;
         let b = Buffer {
             location: loc,
-            format: text::Format::default(),
+            format,
             shift_width: SHIFT_WIDTH,
             num: *num,
 
@@ -264,8 +271,11 @@ impl Buffer {
             tab_state: TabState::default(),
             marks: mark::new_marks(),
             sticky_col: StickyCol::default(),
-            mto_pattern: Mto::default(),
+            last_search: None,
+            ignore_case: false,
+            smart_case: false,
             mto_find_char: Mto::default(),
+            saved: None,
         };
 
         Ok(b)
@@ -287,18 +297,63 @@ impl Buffer {
         self
     }
 
+    /// Set cursor at `row` and `col`, clamped to the last line and to the
+    /// end of line, like when restoring the last cursor position.
+    pub fn set_xy_cursor(&mut self, row: usize, col: usize) -> &mut Self {
+        let row = cmp::min(row, self.to_last_line_idx());
+        let n = text::Format::trim_newline(&self.line(row))
+            .0
+            .chars()
+            .count();
+        let cursor = self.line_to_char(row) + cmp::min(col, n);
+        self.set_cursor(cursor)
+    }
+
+    /// Set mark, on this buffer, for index `[a-z]`.
+    pub fn set_mark(&mut self, mrk: mark::Mark) -> &mut Self {
+        mark::set_mark(&mut self.marks, mrk);
+        self
+    }
+
     /// Configure buffer as read-only.
     pub fn set_read_only(&mut self, read_only: bool) -> &mut Self {
         self.location.set_read_only(read_only);
         self
     }
 
+    /// Configure buffer's location, say after saving it as another file.
+    pub fn set_location(&mut self, loc: Location) -> &mut Self {
+        self.location = loc;
+        self
+    }
+
+    /// Mark buffer as un-modified, typically after saving it.
+    pub fn clear_modified(&mut self) -> &mut Self {
+        let cuds = self.to_change().cuds.clone();
+        let saved = Rc::downgrade(self.as_mut_change());
+        self.saved = Some((saved, cuds));
+        self
+    }
+
     /// Configure buffer's text-format. Refer [text::Format] for details.
     pub fn set_format(&mut self, format: text::Format) -> &mut Self {
         self.format = format;
         self
     }
 
+    /// Configure search patterns to ignore case.
+    pub fn set_ignore_case(&mut self, ignore_case: bool) -> &mut Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Configure search patterns to match case, if the pattern contains
+    /// upper-case characters. Applicable only when ignore-case is true.
+    pub fn set_smart_case(&mut self, smart_case: bool) -> &mut Self {
+        self.smart_case = smart_case;
+        self
+    }
+
     /// Configure shift-width for text-indentation.
     pub fn set_shift_width(&mut self, shift_width: usize) -> &mut Self {
         self.shift_width = shift_width;
@@ -481,10 +536,20 @@ impl Buffer {
         self.location.is_read_only()
     }
 
-    /// Return whether buffer is marked as modified.
-    #[inline]
+    /// Return whether buffer is marked as modified, since it was loaded
+    /// or since it was last saved.
     pub fn is_modified(&self) -> bool {
-        self.to_change().is_modified()
+        let change = self.to_change();
+        match &self.saved {
+            Some((saved, cuds)) => match saved.upgrade() {
+                Some(saved) => {
+                    let ptr: *const Change = &*change;
+                    saved.as_ptr() as *const Change != ptr || &change.cuds != cuds
+                }
+                None => true,
+            },
+            None => change.is_modified(),
+        }
     }
 
     /// Return current buffer state as string.
@@ -517,12 +582,88 @@ impl Buffer {
         self.num
     }
 
+    /// Return the content for lines, `from` till `till` both inclusive, or
+    /// the entire content if `lines` is None. Each line is terminated by
+    /// the buffer's newline format.
+    pub fn to_text(&self, lines: Option<(usize, usize)>) -> String {
+        let nl = self.format.newline();
+        let change = self.to_change();
+        let (from, till) = match lines {
+            Some((from, till)) => (from, till),
+            None => (0, change.rope.len_lines().saturating_sub(1)),
+        };
+
+        let mut text = String::default();
+        let iter = change
+            .rope
+            .lines_at(from)
+            .take((till + 1).saturating_sub(from));
+        for line in iter.map(|l| l.to_string()) {
+            let (line, n) = text::Format::trim_newline(&line);
+            text.push_str(line);
+            if n > 0 {
+                text.push_str(nl);
+            }
+        }
+        text
+    }
+
+    /// Return mark, set on this buffer, for index `[a-z]`.
+    pub fn to_mark(&self, index: char) -> Option<mark::Mark> {
+        mark::get_mark(&self.marks, index)
+    }
+
+    /// Return all marks set on this buffer.
+    pub fn to_marks(&self) -> Vec<mark::Mark> {
+        self.marks.iter().filter_map(|mrk| mrk.clone()).collect()
+    }
+
+    /// Return the last search, used to repeat it and for empty patterns.
+    pub fn to_last_search(&self) -> Option<Search> {
+        self.last_search.clone()
+    }
+
+    /// Return the line matching vim pattern `patt`, used for command-line
+    /// addresses `/patt/` and `?patt?`. Search starts from the line after
+    /// the cursor line for [DP::Right], from the line before for
+    /// [DP::Left], and wraps around the buffer. Empty pattern re-uses the
+    /// last search pattern.
+    pub fn to_pattern_line(&self, patt: &str, dp: DP) -> Result<Option<usize>> {
+        let patt = match (patt, &self.last_search) {
+            ("", Some(search)) => search.patt.clone(),
+            ("", None) => err_at!(Invalid, msg: format!("no previous pattern"))?,
+            (patt, _) => patt.to_string(),
+        };
+        let re = search::to_regex(&patt, self.ignore_case, self.smart_case)?;
+
+        let row = self.to_xy_cursor(None).row as usize;
+        let n = self.to_last_line_idx() + 1;
+        for i in 1..=n {
+            let row = match dp {
+                DP::Right => (row + i) % n,
+                DP::Left => (row + n - i) % n,
+                _ => err_at!(Fatal, msg: format!("unreachable"))?,
+            };
+            if re.is_match(&self.line(row)) {
+                return Ok(Some(row));
+            }
+        }
+        Ok(None)
+    }
+
     /// Return buffer's location.
     #[inline]
     pub fn to_location(&self) -> Location {
         self.location.clone()
     }
 
+    /// Return a copy of the underlying rope, cloning a rope is cheap and
+    /// shares the text with this buffer's current change.
+    #[inline]
+    pub fn to_rope(&self) -> Rope {
+        self.to_change().rope.clone()
+    }
+
     /// Return the underlying text, if buffer is really large this can be
     /// a costly operation.
     #[inline]
@@ -588,6 +729,12 @@ impl Buffer {
         xy_to_cursor(self, (row, col.saturating_sub(1)))
     }
 
+    /// Return the word under the cursor or the next word after the cursor,
+    /// within the cursor line.
+    pub fn to_cursor_word(&mut self) -> Result<Option<String>> {
+        Ok(keyword_under_cursor(self)?.map(|(_, word)| word))
+    }
+
     /// Convert `buffer[a..z]` into Spanline that can be rendered.
     pub fn to_span_line(&self, a: usize, z: usize) -> Result<Spanline> {
         let span: Span = {
@@ -645,6 +792,203 @@ impl Buffer {
     {
         self.inner.cud_delete(range)
     }
+
+    /// Insert `txt` as lines below line `line_idx`, or above the first
+    /// line if `line_idx` is None, as a single change. Newlines in `txt`
+    /// are converted to buffer's newline format. Cursor is moved to the
+    /// first inserted line. Return the number of lines inserted, along
+    /// with the edit event, to be applied on buffer's syntax tree.
+    pub fn insert_lines(&mut self, line_idx: Option<usize>, txt: &str) -> Result<(usize, Event)> {
+        use crate::event;
+
+        let nl = self.format.newline();
+        let lines: Vec<String> = {
+            let rope = Rope::from_str(txt);
+            let iter = rope.lines().map(|l| l.to_string());
+            let iter = iter.map(|l| text::Format::trim_newline(&l).0.to_string());
+            iter.collect()
+        };
+        let lines = match lines.last() {
+            Some(line) if line.is_empty() => lines[..(lines.len() - 1)].to_vec(),
+            _ => lines,
+        };
+        if lines.len() == 0 {
+            return Ok((0, Event::Noop));
+        }
+
+        match &mut self.inner {
+            Inner::Normal(nb) => nb.change = Change::fork(&mut nb.change),
+            _ => err_at!(Invalid, msg: format!("buffer not in normal mode"))?,
+        }
+
+        let n_lines = self.n_lines();
+        let (at, prefix) = match line_idx {
+            None => (0, false),
+            Some(line_idx) if (line_idx + 1) < n_lines => (self.line_to_char(line_idx + 1), false),
+            // below the last line, which may not be terminated by newline.
+            Some(_) => {
+                let last = self.line(n_lines.saturating_sub(1));
+                let prefix = last.len() > 0 && text::Format::trim_newline(&last).1 == 0;
+                (self.n_chars(), prefix)
+            }
+        };
+        let n = lines.len();
+        let text = match prefix {
+            true => format!("{}{}", nl, lines.join(nl)),
+            false => lines.into_iter().map(|l| l + nl).collect(),
+        };
+
+        let cursor = if_else!(prefix, at + nl.chars().count(), at);
+        self.cud_str(Some(at), &text)?;
+        self.set_cursor(cursor).clear_sticky_col();
+        Ok((n, Event::Edit(event::Edit::new_ins(at, text))))
+    }
+
+    /// Substitute matches of `subst` pattern in lines `from` till `till`,
+    /// both inclusive, as a single change. Line breaks in replacement
+    /// string are converted to buffer's newline format. Cursor is moved to
+    /// the first non-blank of the last substituted line. Return the number
+    /// of substitutions, along with the edit event, to be applied on
+    /// buffer's syntax tree.
+    pub fn substitute(
+        &mut self,
+        from: usize,
+        till: usize,
+        subst: &Substitute,
+    ) -> Result<(usize, Event)> {
+        if self.to_mode() != "normal" {
+            err_at!(Invalid, msg: format!("buffer not in normal mode"))?
+        }
+
+        let re = subst.to_regex(self.ignore_case, self.smart_case)?;
+        self.last_search = Some(subst.search.clone());
+
+        let replace = subst.replace.replace('\n', self.format.newline());
+        let mut rope = self.to_rope();
+        let (mut n, mut row) = (0, None);
+        // bottom-up, so that char-index of lines above are not disturbed.
+        for line_idx in (from..=till).rev() {
+            let line = self.line(line_idx);
+            let line = text::Format::trim_newline(&line).0;
+            let (count, new) = match subst.global {
+                true => (
+                    re.find_iter(line).count(),
+                    re.replace_all(line, replace.as_str()),
+                ),
+                false => (
+                    re.find_iter(line).take(1).count(),
+                    re.replace(line, replace.as_str()),
+                ),
+            };
+            if count > 0 {
+                let start = self.line_to_char(line_idx);
+                rope.remove(start..(start + line.chars().count()));
+                rope.insert(start, &new);
+                n += count;
+                row = row.or(Some(line_idx));
+            }
+        }
+
+        match row {
+            Some(row) => {
+                let evnt = self.fork_rope(rope)?;
+                self.set_xy_cursor(row, 0);
+                let cursor = mto_line_home(self, DP::TextCol)?;
+                self.set_cursor(cursor).clear_sticky_col();
+                Ok((n, evnt))
+            }
+            None => Ok((0, Event::Noop)),
+        }
+    }
+}
+
+impl Buffer {
+    /// Convert line endings in the entire buffer to `format`, as a single
+    /// change. Newlines inserted hereafter shall use `format`. Return the
+    /// edit event, to be applied on buffer's syntax tree.
+    pub fn convert_format(&mut self, format: text::Format) -> Result<Event> {
+        if self.to_mode() != "normal" {
+            err_at!(Invalid, msg: format!("buffer not in normal mode"))?
+        }
+
+        self.format = format;
+        let txt = self.to_text(None);
+        if txt == self.to_string() {
+            return Ok(Event::Noop);
+        }
+        self.fork_rope(Rope::from_str(&txt))
+    }
+
+    /// Reload buffer's content from its location, as a single change, so
+    /// that the reload can be undone. Buffer is marked as un-modified.
+    /// Return the edit event, to be applied on buffer's syntax tree.
+    pub fn reload(&mut self) -> Result<Event> {
+        if self.to_mode() != "normal" {
+            err_at!(Invalid, msg: format!("buffer not in normal mode"))?
+        }
+
+        let rope = self.location.refresh_stat().to_rope()?;
+        let evnt = match rope != self.to_rope() {
+            true => self.fork_rope(rope)?,
+            false => Event::Noop,
+        };
+        self.clear_modified();
+        Ok(evnt)
+    }
+
+    /// Replace buffer's content with `rope`, as a single change, like when
+    /// recovering unsaved edits from a swap file. Return the edit event,
+    /// to be applied on buffer's syntax tree.
+    pub fn set_rope(&mut self, rope: Rope) -> Result<Event> {
+        if self.to_mode() != "normal" {
+            err_at!(Invalid, msg: format!("buffer not in normal mode"))?
+        }
+        self.fork_rope(rope)
+    }
+
+    // replace buffer's content with `rope` as a new change, cursor is
+    // kept at the same row and column, as far as possible. Return the
+    // edit event spanning the text that differs between old and new
+    // content.
+    fn fork_rope(&mut self, rope: Rope) -> Result<Event> {
+        use crate::event;
+
+        let xy = self.to_xy_cursor(None);
+        let old = self.to_rope();
+        match &mut self.inner {
+            Inner::Normal(nb) => nb.change = Change::fork(&mut nb.change),
+            _ => err_at!(Fatal, msg: format!("unreachable"))?,
+        }
+
+        let (n, m) = (old.len_chars(), rope.len_chars());
+        let prefix = {
+            let iter = old.chars().zip(rope.chars());
+            iter.take_while(|(a, b)| a == b).count()
+        };
+        let suffix = {
+            let (mut a, mut b) = (old.chars_at(n), rope.chars_at(m));
+            let mut k = 0;
+            while (prefix + k) < cmp::min(n, m) && a.prev() == b.prev() {
+                k += 1;
+            }
+            k
+        };
+        let oldt = old.slice(prefix..(n - suffix)).to_string();
+        let newt = rope.slice(prefix..(m - suffix)).to_string();
+        {
+            let change = Rc::clone(self.as_mut_change());
+            change.borrow_mut().rope = rope;
+        }
+
+        self.set_xy_cursor(xy.row, xy.col).clear_sticky_col();
+        let evnt = match (oldt.len(), newt.len()) {
+            (0, 0) => Event::Noop,
+            (0, _) => Event::Edit(event::Edit::new_ins(prefix, newt)),
+            (_, 0) => Event::Edit(event::Edit::new_del(prefix, oldt)),
+            (_, _) => Event::Edit(event::Edit::new_chg(prefix, oldt, newt)),
+        };
+        Ok(evnt)
+    }
 }
 
 #[derive(Clone)]
@@ -907,20 +1251,58 @@ impl NormalBuffer {
                 _ => Event::Noop,
             },
             Event::Mt(e @ Mto::Bracket(_, _, _, _)) => mto_bracket(buf, e)?,
-            Event::Mt(e @ Mto::Pattern(_, Some(_), _)) => {
-                buf.mto_pattern = e.clone();
+            Event::Mt(Mto::Pattern(n, Some(text), dp)) => {
+                let searches = {
+                    let last = buf.last_search.as_ref().map(|s| s.patt.as_str());
+                    Search::parse(&text, dp, last)?
+                };
+                buf.last_search = searches.last().cloned();
 
-                let cursor = mto_pattern(buf, e)?;
+                let from = buf.to_char_cursor();
+                let cursor = mto_pattern(buf, n, searches)?;
                 buf.set_cursor(cursor).clear_sticky_col();
-                Event::Noop
-            }
-            Event::Mt(Mto::PatternR(n, dir)) => {
-                let e = buf.mto_pattern.clone();
-
-                let cursor = mto_pattern(buf, e.dir_xor(n, dir)?)?;
-                buf.set_cursor(cursor).clear_sticky_col();
-                Event::Noop
+                if_else!(cursor == from, Event::Noop, Event::JumpFrom(from))
             }
+            Event::Mt(Mto::PatternR(n, dir)) => match buf.last_search.clone() {
+                Some(mut search) => {
+                    // repeat the last search in the chain, `N` reverses
+                    // its direction.
+                    search.dp = match (search.dp, dir) {
+                        (dp, DP::Right) => dp,
+                        (DP::Left, _) => DP::Right,
+                        (_, _) => DP::Left,
+                    };
+                    let from = buf.to_char_cursor();
+                    let cursor = mto_pattern(buf, n, vec![search])?;
+                    buf.set_cursor(cursor).clear_sticky_col();
+                    if_else!(cursor == from, Event::Noop, Event::JumpFrom(from))
+                }
+                None => Event::Noop,
+            },
+            Event::Mt(Mto::PatternW(n, whole, dp)) => match keyword_under_cursor(buf)? {
+                Some((start, word)) => {
+                    let patt = {
+                        let word = search::escape(&word, dp);
+                        let patt = if_else!(whole, format!("\\<{}\\>", word), word);
+                        // smart-case is not applicable for word search.
+                        if_else!(buf.ignore_case, format!("\\c{}", patt), patt)
+                    };
+                    let search = Search::parse(&patt, dp, None)?;
+                    buf.last_search = search.last().cloned();
+
+                    // search from the start of the word, so that the word
+                    // under the cursor is skipped.
+                    let from = buf.to_char_cursor();
+                    buf.set_cursor(start);
+                    let cursor = match mto_pattern(buf, n, search)? {
+                        cursor if cursor == start => from,
+                        cursor => cursor,
+                    };
+                    buf.set_cursor(cursor).clear_sticky_col();
+                    if_else!(cursor == from, Event::Noop, Event::JumpFrom(from))
+                }
+                None => Event::Noop,
+            },
             evnt => evnt,
         };
 
@@ -1440,6 +1822,7 @@ fn mto_right(buf: &Buffer, mut n: usize, dp: DP) -> Result<usize> {
                 }
             }
         }
+        DP::None => cmp::min(new_cursor, buf.n_chars()),
         dp => err_at!(Fatal, msg: format!("invalid direction: {}", dp))?,
     };
     Ok(cursor)
@@ -1867,44 +2250,120 @@ fn mto_bracket(buf: &mut Buffer, e: Mto) -> Result<Event> {
     Ok(Event::Noop)
 }
 
-fn mto_pattern(buf: &mut Buffer, evnt: Mto) -> Result<usize> {
-    use regex::Regex;
+fn mto_pattern(buf: &Buffer, n: usize, searches: Vec<Search>) -> Result<usize> {
+    let (ic, sc) = (buf.ignore_case, buf.smart_case);
 
-    let (n, patt, dp) = match evnt.clone() {
-        Mto::Pattern(n, Some(patt), dp) => {
-            let msg = format!("{}", patt);
-            let patt = err_at!(BadPattern, Regex::new(&patt), msg)?;
-            (n, patt, dp)
-        }
-        _ => err_at!(Fatal, msg: format!("unreachable"))?,
-    };
+    let mut cursor = buf.to_char_cursor();
+    for (i, search) in searches.into_iter().enumerate() {
+        let n = if_else!(i == 0, n, 1);
+        let patt = search.to_regex(ic, sc)?;
+        cursor = match find_pattern(buf, &patt, cursor, n, search.dp)? {
+            Some((start, end)) => mto_search_offset(buf, search.offset, start, end),
+            None => return Ok(buf.to_char_cursor()),
+        };
+    }
+
+    Ok(cursor)
+}
 
-    let get_matches = |txt: String| -> Vec<(usize, usize)> {
-        let iter = patt.find_iter(&txt).map(|m| (m.start(), m.end()));
+// return the n-th match, as (start, end) character index, starting
+// from cursor in `dp` direction.
+fn find_pattern(
+    buf: &Buffer,
+    patt: &regex::Regex,
+    cursor: usize,
+    n: usize,
+    dp: DP,
+) -> Result<Option<(usize, usize)>> {
+    // regex matches are byte offsets within `txt`.
+    let get_matches = |from: usize, txt: String| -> Vec<(usize, usize)> {
+        let iter = patt.find_iter(&txt).map(|m| {
+            let start = from + txt[..m.start()].chars().count();
+            (start, start + txt[m.start()..m.end()].chars().count())
+        });
         iter.collect()
     };
 
-    let cursor = buf.to_char_cursor();
-    match dp {
+    let item = match dp {
         DP::Left => {
-            let txt = buf.slice(..cursor);
-            let matches: Vec<(usize, usize)> = get_matches(txt);
-            match matches.into_iter().rev().skip(n.saturating_sub(1)).next() {
-                Some((start, _)) => Ok(start),
-                None => Ok(cursor),
-            }
+            let matches = get_matches(0, buf.slice(..cursor));
+            matches.into_iter().rev().skip(n.saturating_sub(1)).next()
         }
         DP::Right => {
-            let from = cursor.saturating_add(1);
-            let txt = buf.slice(from..);
-            let matches: Vec<(usize, usize)> = get_matches(txt);
-            match matches.into_iter().skip(n.saturating_sub(1)).next() {
-                Some((start, _)) => Ok(from + start),
-                None => Ok(cursor),
-            }
+            let from = cmp::min(cursor.saturating_add(1), buf.n_chars());
+            let matches = get_matches(from, buf.slice(from..));
+            matches.into_iter().skip(n.saturating_sub(1)).next()
         }
         _ => err_at!(Fatal, msg: format!("unreachable"))?,
+    };
+
+    Ok(item)
+}
+
+fn mto_search_offset(buf: &Buffer, offset: Offset, start: usize, end: usize) -> usize {
+    let last = buf.n_chars().saturating_sub(1) as isize;
+    match offset {
+        Offset::None => start,
+        Offset::Start(n) => limit!(start as isize + n, 0, last) as usize,
+        Offset::End(n) => {
+            let end = end.saturating_sub(1).max(start) as isize;
+            limit!(end + n, 0, last) as usize
+        }
+        Offset::Line(n) => {
+            let row = buf.char_to_line(start) as isize;
+            let last_row = buf.to_last_line_idx() as isize;
+            let row = limit!(row + n, 0, last_row) as usize;
+            let home = buf.line_to_char(row);
+            home + skip_whitespace(&buf.line(row), 0, DP::Right).unwrap_or(0)
+        }
+    }
+}
+
+// return the keyword under or after the cursor, within the cursor line,
+// along with keyword's starting cursor. Keyword boundaries are located
+// using word motions, like `w`, `b` and `e`, cursor is left untouched.
+fn keyword_under_cursor(buf: &mut Buffer) -> Result<Option<(usize, String)>> {
+    let cursor = buf.to_char_cursor();
+    let res = keyword_at(buf, cursor);
+    buf.set_cursor(cursor);
+    res
+}
+
+fn keyword_at(buf: &mut Buffer, cursor: usize) -> Result<Option<(usize, String)>> {
+    let home = buf.to_line_home(Some(cursor));
+    let chars: Vec<char> = text::visual_line(&buf.line(buf.char_to_line(cursor)))
+        .chars()
+        .collect();
+    let end = home + chars.len();
+    let is_kw = |at: usize| at < end && is_keyword(chars[at - home]);
+
+    // skip over words that are not keywords, `w`.
+    let mut start = cursor;
+    while start < end && !is_kw(start) {
+        buf.set_cursor(start);
+        start = match mto_words_right(buf, 1, DP::Start)? {
+            next if next > start => next,
+            _ => end,
+        };
+    }
+    if !is_kw(start) {
+        return Ok(None);
+    }
+    // back to the start of keyword, `b`.
+    if start > home && is_kw(start - 1) {
+        buf.set_cursor(start);
+        start = mto_words_left(buf, 1, DP::End)?;
     }
+    // forward to the end of keyword, `e`.
+    let last = if is_kw(start + 1) {
+        buf.set_cursor(start);
+        mto_words_right(buf, 1, DP::End)?
+    } else {
+        start
+    };
+
+    let word = String::from_iter(chars[(start - home)..=(last - home)].iter());
+    Ok(Some((start, word)))
 }
 
 fn mod_insert(buf: &mut Buffer, repeat: usize, pos: DP) -> Result<Event> {
@@ -1998,6 +2457,12 @@ pub fn skip_whitespace(line: &str, off: usize, dp: DP) -> Result<usize> {
     Ok(n)
 }
 
+/// Alphanumeric and underscore characters make up a word.
+#[inline]
+pub fn is_keyword(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd)]
 enum MtoWord {
     St(usize), // start - (n,) number of words to move.
@@ -2030,7 +2495,7 @@ impl MtoWord {
         let last_char = rc.saturating_sub(1);
 
         let is_ws = ch.is_whitespace();
-        let is_an = ch.is_alphanumeric() || ch == '_';
+        let is_an = is_keyword(ch);
 
         // rotate the current state.
         let state = match pos {
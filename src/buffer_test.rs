@@ -25,3 +25,183 @@ fn test_newline() {
     assert_eq!(ss[2].as_str(), "how\r\n", "{}", ss[2]);
     assert_eq!(ss[3].as_str(), "are you", "{}", ss[3]);
 }
+
+#[test]
+fn test_to_text() {
+    let mut buf = {
+        let loc = Location::new_memory("hello\r\nworld\rhow\nare you".as_bytes(), "utf-8", false);
+        Buffer::from_reader(loc.unwrap()).unwrap()
+    };
+    assert_eq!(buf.to_text(None), "hello\nworld\nhow\nare you");
+    assert_eq!(buf.to_text(Some((1, 2))), "world\nhow\n");
+
+    buf.set_format(text::Format::Dos);
+    assert_eq!(buf.to_text(Some((0, 0))), "hello\r\n");
+    assert_eq!(buf.to_text(Some((2, 3))), "how\r\nare you");
+}
+
+#[test]
+fn test_convert_format() {
+    let mut buf = {
+        let loc = Location::new_memory("hello\r\nworld\r\nhow\nare you".as_bytes(), "utf-8", false);
+        Buffer::from_reader(loc.unwrap()).unwrap()
+    };
+    assert_eq!(buf.format, text::Format::Dos);
+    assert_eq!(buf.is_modified(), false);
+
+    buf.set_cursor(9);
+    // edit spans the text between the first and the last difference.
+    match buf.convert_format(text::Format::Unix).unwrap() {
+        Event::Edit(crate::event::Edit::Chg {
+            cursor: 5,
+            oldt,
+            newt,
+        }) => assert_eq!((oldt.as_str(), newt.as_str()), ("\r\nworld\r", "\nworld")),
+        _ => panic!("unexpected edit event"),
+    }
+    assert_eq!(buf.to_string(), "hello\nworld\nhow\nare you");
+    assert_eq!(buf.to_char_cursor(), 8);
+    assert_eq!(buf.is_modified(), true);
+
+    buf.convert_format(text::Format::Mac).unwrap();
+    assert_eq!(buf.to_string(), "hello\rworld\rhow\rare you");
+    assert_eq!(buf.to_text(None), "hello\rworld\rhow\rare you");
+    assert_eq!(buf.convert_format(text::Format::Mac).unwrap(), Event::Noop);
+}
+
+#[test]
+fn test_reload() {
+    use std::{ffi, fs};
+
+    let file = std::env::temp_dir().join("ted-test-reload.txt");
+    fs::write(&file, "hello\nworld\nhow are you\n").unwrap();
+
+    let loc = {
+        let f: ffi::OsString = file.clone().into_os_string();
+        Location::new_disk(&f, None).unwrap()
+    };
+    let mut buf = Buffer::from_reader(loc).unwrap();
+    assert_eq!(buf.to_location().is_changed(), false);
+
+    buf.set_cursor(14);
+    fs::write(&file, "hello\n").unwrap();
+    assert_eq!(buf.to_location().is_changed(), true);
+
+    match buf.reload().unwrap() {
+        Event::Edit(crate::event::Edit::Del { cursor: 6, txt }) => {
+            assert_eq!(txt, "world\nhow are you\n")
+        }
+        _ => panic!("unexpected edit event"),
+    }
+    assert_eq!(buf.to_string(), "hello\n");
+    assert_eq!(buf.to_xy_cursor(None).row, 0);
+    assert_eq!(buf.to_location().is_changed(), false);
+    assert_eq!(buf.is_modified(), false);
+
+    fs::remove_file(&file).ok();
+}
+
+#[test]
+fn test_keyword_under_cursor() {
+    let loc = Location::new_memory("  foo.bar(baz_1)\na b ;\nnext\n".as_bytes(), "utf-8", false);
+    let mut buf = Buffer::from_reader(loc.unwrap()).unwrap();
+
+    let mut keyword_at = |cursor: usize| {
+        buf.set_cursor(cursor);
+        let res = keyword_under_cursor(&mut buf).unwrap();
+        assert_eq!(buf.to_char_cursor(), cursor);
+        res
+    };
+
+    assert_eq!(keyword_at(0), Some((2, "foo".to_string())));
+    assert_eq!(keyword_at(2), Some((2, "foo".to_string())));
+    assert_eq!(keyword_at(3), Some((2, "foo".to_string())));
+    assert_eq!(keyword_at(4), Some((2, "foo".to_string())));
+    assert_eq!(keyword_at(5), Some((6, "bar".to_string())));
+    assert_eq!(keyword_at(9), Some((10, "baz_1".to_string())));
+    assert_eq!(keyword_at(14), Some((10, "baz_1".to_string())));
+    assert_eq!(keyword_at(15), None);
+    assert_eq!(keyword_at(17), Some((17, "a".to_string())));
+    assert_eq!(keyword_at(18), Some((19, "b".to_string())));
+    assert_eq!(keyword_at(19), Some((19, "b".to_string())));
+    assert_eq!(keyword_at(21), None);
+    assert_eq!(keyword_at(23), Some((23, "next".to_string())));
+}
+
+#[test]
+fn test_to_pattern_line() {
+    let loc = Location::new_memory("fn a\nlet x\nfn b\nlet y\n".as_bytes(), "utf-8", false);
+    let mut buf = Buffer::from_reader(loc.unwrap()).unwrap();
+
+    assert_eq!(buf.to_pattern_line("fn", DP::Right).unwrap(), Some(2));
+    assert_eq!(buf.to_pattern_line("fn", DP::Left).unwrap(), Some(2));
+    assert_eq!(buf.to_pattern_line("let", DP::Right).unwrap(), Some(1));
+    assert_eq!(buf.to_pattern_line("let", DP::Left).unwrap(), Some(3));
+    assert_eq!(buf.to_pattern_line("^fn a$", DP::Right).unwrap(), Some(0));
+    assert_eq!(buf.to_pattern_line("xyz", DP::Right).unwrap(), None);
+    assert!(buf.to_pattern_line("", DP::Right).is_err());
+
+    buf.set_cursor(buf.line_to_char(2));
+    assert_eq!(buf.to_pattern_line("fn", DP::Right).unwrap(), Some(0));
+    assert_eq!(buf.to_pattern_line("let \\w", DP::Left).unwrap(), Some(1));
+}
+
+#[test]
+fn test_insert_lines() {
+    let new_buffer = |txt: &str| {
+        let loc = Location::new_memory(txt.as_bytes(), "utf-8", false);
+        Buffer::from_reader(loc.unwrap()).unwrap()
+    };
+
+    let mut buf = new_buffer("hello\nworld\n");
+    assert_eq!(buf.is_modified(), false);
+    assert_eq!(buf.insert_lines(Some(0), "how\r\nare you").unwrap().0, 2);
+    assert_eq!(buf.to_string(), "hello\nhow\nare you\nworld\n");
+    assert_eq!(buf.to_xy_cursor(None).row, 1);
+    assert_eq!(buf.is_modified(), true);
+
+    match buf.insert_lines(None, "first\n").unwrap() {
+        (1, Event::Edit(crate::event::Edit::Ins { cursor: 0, txt })) => assert_eq!(txt, "first\n"),
+        _ => panic!("unexpected edit event"),
+    }
+    assert_eq!(buf.to_string(), "first\nhello\nhow\nare you\nworld\n");
+    assert_eq!(buf.to_char_cursor(), 0);
+
+    let mut buf = new_buffer("hello\nworld");
+    assert_eq!(buf.insert_lines(Some(1), "how\nare you\n").unwrap().0, 2);
+    assert_eq!(buf.to_string(), "hello\nworld\nhow\nare you");
+    assert_eq!(buf.to_xy_cursor(None).row, 2);
+
+    let mut buf = new_buffer("");
+    assert_eq!(buf.insert_lines(Some(0), "").unwrap().0, 0);
+    assert_eq!(buf.insert_lines(Some(0), "hello\n").unwrap().0, 1);
+    assert_eq!(buf.to_string(), "hello\n");
+}
+
+#[test]
+fn test_substitute() {
+    let loc = Location::new_memory("foo foo\r\nbar\r\n  foo\r\n".as_bytes(), "utf-8", false);
+    let mut buf = Buffer::from_reader(loc.unwrap()).unwrap();
+    buf.set_format(text::Format::Dos);
+
+    let subst = Substitute::parse("/foo/x/", None).unwrap();
+    match buf.substitute(0, 2, &subst).unwrap() {
+        (2, Event::Edit(_)) => (),
+        _ => panic!("unexpected edit event"),
+    }
+    assert_eq!(buf.to_string(), "x foo\r\nbar\r\n  x\r\n");
+    assert_eq!(buf.to_xy_cursor(None).row, 2);
+    assert_eq!(buf.to_xy_cursor(None).col, 2);
+    assert_eq!(buf.to_last_search().unwrap().patt, "foo");
+    assert_eq!(buf.is_modified(), true);
+
+    let subst = Substitute::parse(r"/\(\w\+\)/<\1\r>/g", None).unwrap();
+    assert_eq!(buf.substitute(0, 1, &subst).unwrap().0, 3);
+    assert_eq!(buf.to_string(), "<x\r\n> <foo\r\n>\r\n<bar\r\n>\r\n  x\r\n");
+
+    let subst = Substitute::parse("/xyz/abc/", None).unwrap();
+    match buf.substitute(0, 2, &subst).unwrap() {
+        (0, Event::Noop) => (),
+        _ => panic!("unexpected edit event"),
+    }
+}
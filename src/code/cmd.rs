@@ -5,10 +5,25 @@ use log::{debug, trace};
 use std::convert::TryFrom;
 
 use crate::{
-    code::{cmd_set::Set, Code},
+    buffer,
+    code::{
+        cmd_buffer::{Bdelete, Bnext, Bprevious, Buffer, Buffers},
+        cmd_dir::{Mkdir, Mkfile, Remove, Rename},
+        cmd_edit::{Edit, Finder},
+        cmd_map::{Map, Unmap},
+        cmd_quit::{Qall, Quit, Wq, Wqall, Xit},
+        cmd_read::Read,
+        cmd_session::Mksession,
+        cmd_set::{Set, Setglobal, Setlocal},
+        cmd_subst::Substitute,
+        cmd_write::{Saveas, Update, Wall, Write},
+        Code,
+    },
     colors::ColorScheme,
-    event::Event,
-    syntax, Error, Result,
+    event::{Event, DP},
+    syntax,
+    window::WinBuffer,
+    Error, Result,
 };
 
 pub trait Command {
@@ -19,7 +34,7 @@ macro_rules! commands {
     ($(($var:ident, $t:ident, $name:expr)),*) => (
         lazy_static! {
             static ref CMD_NAMES: Vec<String> = vec![
-                $($name.to_string())*
+                $($name.to_string(),)*
             ];
         }
 
@@ -54,46 +69,293 @@ macro_rules! commands {
     )
 }
 
-commands![(Set, Set, "set")];
-
-//pub fn on_tab(&mut self, s: &mut State) -> Result<()> {
-//    let span = Self::to_command_name(s);
-
-//    match self {
-//        Cmd::Initial { cmds } => {
-//            let tabc = {
-//                let choices = Self::to_cmd_names(&span, &cmds);
-//                TabComplete::new(span, choices)
-//            };
-//            let cmds: Vec<Cmd> = cmds.drain(..).collect();
-//            *self = Cmd::TabComp { tabc, cmds };
-//        }
-//        Cmd::TabComp { tabc, .. } if tabc.is_same(&span) => (),
-//        Cmd::TabComp { tabc: _, cmds } => {
-//            let tabc = {
-//                let choices = Self::to_cmd_names(&span, cmds);
-//                TabComplete::new(span, choices)
-//            };
-//            let cmds: Vec<Cmd> = cmds.drain(..).collect();
-//            *self = Cmd::TabComp { tabc, cmds };
-//        }
-//    }
-
-//    match self {
-//        Cmd::TabComp { tabc, .. } => {
-//            //use crate::window_code::Message;
-
-//            //let w = match c.to_window() {
-//            //    Window::Code(mut w) => {
-//            //        w.post(c, Message::TabComplete(tabc.clone()))?;
-//            //        Window::Code(w)
-//            //    }
-//            //    w => w,
-//            //};
-//            //c.set_window(w);
-//        }
-//        Cmd::Initial { .. } => error!("unreachable"),
-//    }
-
-//    Ok(())
-//}
+commands![
+    (Set, Set, "set"),
+    (Setlocal, Setlocal, "setlocal"),
+    (Setglobal, Setglobal, "setglobal"),
+    (Edit, Edit, "edit"),
+    (Finder, Finder, "finder"),
+    (Buffer, Buffer, "buffer"),
+    (Bnext, Bnext, "bnext"),
+    (Bprevious, Bprevious, "bprevious"),
+    (Bdelete, Bdelete, "bdelete"),
+    (Buffers, Buffers, "buffers"),
+    (Write, Write, "write"),
+    (Update, Update, "update"),
+    (Saveas, Saveas, "saveas"),
+    (Wall, Wall, "wall"),
+    (Read, Read, "read"),
+    (Substitute, Substitute, "substitute"),
+    (Quit, Quit, "quit"),
+    (Wq, Wq, "wq"),
+    (Xit, Xit, "xit"),
+    (Qall, Qall, "qall"),
+    (Wqall, Wqall, "wqall"),
+    (Mksession, Mksession, "mksession"),
+    (Mkdir, Mkdir, "mkdir"),
+    (Mkfile, Mkfile, "mkfile"),
+    (Rename, Rename, "rename"),
+    (Remove, Remove, "remove"),
+    (Nmap, Map, "nmap"),
+    (Imap, Map, "imap"),
+    (Cmap, Map, "cmap"),
+    (Noremap, Map, "noremap"),
+    (Inoremap, Map, "inoremap"),
+    (Cnoremap, Map, "cnoremap"),
+    (Unmap, Unmap, "unmap"),
+    (Iunmap, Unmap, "iunmap"),
+    (Cunmap, Unmap, "cunmap")
+];
+
+/// Resolve command-line `range` into line indices, from and till both
+/// inclusive and starting from ZERO. Addresses can be a line number, `.`
+/// for the cursor line, `$` for the last line, `'x` for mark `x`, `/pat/`
+/// for the next line matching `pat` or `?pat?` for the previous line
+/// matching `pat`, followed by optional `+n` or `-n` offsets. `%` is same
+/// as `1,$`.
+pub fn to_line_range(buf: &buffer::Buffer, range: &str) -> Result<(usize, usize)> {
+    let last = buf.to_last_line_idx();
+    let range = range.trim();
+    if range == "%" {
+        return Ok((0, last));
+    }
+
+    let (from, till) = split_range(range);
+    let from = to_line_address(buf, from)?;
+    let till = match till {
+        Some(till) => to_line_address(buf, till)?,
+        None => from,
+    };
+    match (from, till) {
+        (from, till) if from > till => err_at!(Invalid, msg: format!("backwards range {}", range)),
+        (_, till) if till > last => err_at!(Invalid, msg: format!("invalid range {}", range)),
+        (from, till) => Ok((from, till)),
+    }
+}
+
+fn to_line_address(buf: &buffer::Buffer, addr: &str) -> Result<usize> {
+    let cursor_row = buf.to_xy_cursor(None).row;
+    let chars: Vec<char> = addr.trim().chars().collect();
+
+    let (mut row, mut i) = match chars.first() {
+        Some('.') => (cursor_row as isize, 1),
+        Some('$') => (buf.to_last_line_idx() as isize, 1),
+        Some('\'') => match chars.get(1).map(|ch| buf.to_mark(*ch)) {
+            Some(Some(mark)) => (mark.to_row() as isize, 2),
+            _ => err_at!(Invalid, msg: format!("mark not set {}", addr))?,
+        },
+        Some(ch) if ch.is_ascii_digit() => {
+            let n = chars.iter().take_while(|ch| ch.is_ascii_digit()).count();
+            let num: String = chars[..n].iter().collect();
+            let num = err_at!(Invalid, num.parse::<isize>())?;
+            (num.saturating_sub(1), n)
+        }
+        Some(ch) if *ch == '/' || *ch == '?' => {
+            let (patt, n) = to_pattern(&chars, *ch);
+            let dp = if_else!(*ch == '/', DP::Right, DP::Left);
+            match buf.to_pattern_line(&patt, dp)? {
+                Some(row) => (row as isize, n),
+                None => err_at!(Invalid, msg: format!("pattern not found {}", addr))?,
+            }
+        }
+        Some('+') | Some('-') | None => (cursor_row as isize, 0),
+        Some(_) => err_at!(Invalid, msg: format!("invalid address {}", addr))?,
+    };
+
+    while i < chars.len() {
+        let sign = match chars[i] {
+            '+' => 1,
+            '-' => -1,
+            _ => err_at!(Invalid, msg: format!("invalid address {}", addr))?,
+        };
+        i += 1;
+        let n = chars[i..]
+            .iter()
+            .take_while(|ch| ch.is_ascii_digit())
+            .count();
+        let num: String = chars[i..(i + n)].iter().collect();
+        row += sign * if_else!(n > 0, err_at!(Invalid, num.parse::<isize>())?, 1);
+        i += n;
+    }
+
+    if row < 0 {
+        err_at!(Invalid, msg: format!("invalid address {}", addr))
+    } else {
+        Ok(row as usize)
+    }
+}
+
+// split range at the first `,` or `;` that is not within a pattern.
+fn split_range(range: &str) -> (&str, Option<&str>) {
+    let mut delim = None;
+    let mut iter = range.char_indices();
+    while let Some((off, ch)) = iter.next() {
+        match (delim, ch) {
+            (Some(_), '\\') => {
+                iter.next();
+            }
+            (Some(d), ch) if ch == d => delim = None,
+            (Some(_), _) => (),
+            (None, '/') | (None, '?') => delim = Some(ch),
+            (None, ',') | (None, ';') => return (&range[..off], Some(&range[off + 1..])),
+            (None, _) => (),
+        }
+    }
+    (range, None)
+}
+
+// return the pattern, delimited by `delim`, at the start of `chars` and
+// the number of characters consumed. Escaped delimiters are unescaped,
+// closing delimiter is optional at the end.
+fn to_pattern(chars: &[char], delim: char) -> (String, usize) {
+    let mut patt = String::default();
+    let mut i = 1;
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            ('\\', Some(ch)) if *ch == delim => {
+                patt.push(delim);
+                i += 2;
+            }
+            ('\\', Some(ch)) => {
+                patt.push('\\');
+                patt.push(*ch);
+                i += 2;
+            }
+            (ch, _) if ch == delim => return (patt, i + 1),
+            (ch, _) => {
+                patt.push(ch);
+                i += 1;
+            }
+        }
+    }
+    (patt, i)
+}
+
+/// Return the span, at the end of command-line `line`, to complete and
+/// the list of matching choices. `line` excludes the leader and the text
+/// after the cursor. Every choice starts with the span.
+pub fn to_completions(app: &Code, line: &str) -> (String, Vec<String>) {
+    let range = |ch: char| ch.is_ascii_digit() || ".,;%$+-' ".contains(ch);
+    let line = line.trim_start_matches(range);
+
+    let (name, span) = match line.find(char::is_whitespace) {
+        Some(_) => {
+            let name = line.split_whitespace().next().unwrap_or("");
+            let span = match line.rfind(char::is_whitespace) {
+                Some(off) => &line[off + 1..],
+                None => line,
+            };
+            (name, span)
+        }
+        None => {
+            let iter = CMD_NAMES.iter().filter(|n| n.starts_with(line));
+            return (line.to_string(), iter.cloned().collect());
+        }
+    };
+
+    let mut choices: Vec<String> = match name {
+        "set" | "se" | "setlocal" | "setl" | "setglobal" | "setg" => match span.find('=') {
+            Some(off) => to_option_values(app, &span[..off]),
+            None => to_option_names(app),
+        },
+        "e" | "edit" | "w" | "write" | "up" | "update" | "sav" | "saveas" | "wq" | "x" | "xit"
+        | "r" | "read" | "mks" | "mksession" | "mkdir" | "mkfile" | "ren" | "rename" => {
+            to_paths(span)
+        }
+        "b" | "buffer" | "bd" | "bdelete" => app.buffers.iter().map(|b| b.to_id()).collect(),
+        _ => vec![],
+    };
+    choices.retain(|c| c.starts_with(span));
+    choices.sort();
+    choices.dedup();
+
+    (span.to_string(), choices)
+}
+
+fn to_option_names(app: &Code) -> Vec<String> {
+    use crate::{
+        code::config::{self, Config},
+        state,
+    };
+
+    let iter = Config::to_option_names().into_iter();
+    let iter = iter.chain(state::Config::to_option_names().into_iter());
+    let iter = iter.chain(config::FILE_OPTIONS.iter().cloned());
+
+    let mut names = vec![];
+    for name in iter {
+        // file option `binary` is the only boolean file option.
+        let is_bool = app.config.is_bool_option(name);
+        let is_bool = is_bool.or(app.state_config.is_bool_option(name));
+        match is_bool.unwrap_or(name == "binary") {
+            true => {
+                names.push(format!("no{}", name));
+                names.push(name.to_string());
+            }
+            false => names.push(format!("{}=", name)),
+        }
+    }
+    names.push("all".to_string());
+    names
+}
+
+// complete the value of option `name`, enumerated options list all their
+// values, other options offer their current value.
+fn to_option_values(app: &Code, name: &str) -> Vec<String> {
+    use crate::text;
+
+    let values: Vec<String> = match name {
+        "color_scheme" | "scheme" => app.schemes.iter().map(|s| s.name.clone()).collect(),
+        "fileencoding" => text::ENCODINGS.iter().map(|s| s.to_string()).collect(),
+        "fileformat" => text::FORMATS.iter().map(|s| s.to_string()).collect(),
+        name => {
+            let arg = app.config.to_option(name);
+            return arg
+                .or(app.state_config.to_option(name))
+                .into_iter()
+                .collect();
+        }
+    };
+    values
+        .into_iter()
+        .map(|v| format!("{}={}", name, v))
+        .collect()
+}
+
+// list directory entries, directories are suffixed with `/`.
+fn to_paths(span: &str) -> Vec<String> {
+    use std::{fs, path};
+
+    let (dir, prefix) = match span.rfind('/') {
+        Some(off) => (&span[..=off], &span[off + 1..]),
+        None => ("", span),
+    };
+    let dir_path: path::PathBuf = match dir {
+        "" => ".".into(),
+        dir if dir.starts_with("~/") => match dirs::home_dir() {
+            Some(home) => home.join(&dir[2..]),
+            None => return vec![],
+        },
+        dir => dir.into(),
+    };
+
+    let entries = match fs::read_dir(&dir_path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            debug!("completion, read_dir {:?} : {}", dir_path, err);
+            return vec![];
+        }
+    };
+    let mut paths = vec![];
+    for entry in entries.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') && !prefix.starts_with('.') {
+            continue;
+        }
+        match entry.file_type() {
+            Ok(ft) if ft.is_dir() => paths.push(format!("{}{}/", dir, name)),
+            _ => paths.push(format!("{}{}", dir, name)),
+        }
+    }
+    paths
+}
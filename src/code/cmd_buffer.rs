@@ -0,0 +1,114 @@
+#[allow(unused_imports)]
+use log::{debug, error, trace};
+
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event, DP},
+    syntax, Result,
+};
+
+/// `:b[uffer] [N|name]`, switch to buffer by its number or by its name,
+/// name can be a unique sub-string of buffer name. Without argument
+/// switch to the alternate buffer.
+pub struct Buffer {
+    name: Option<String>,
+}
+
+impl Buffer {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let name = to_arg(&syn, "buffer_name");
+        Ok(Buffer { name })
+    }
+}
+
+impl Command for Buffer {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::Buffer(self.name.clone())))
+    }
+}
+
+/// `:bn[ext] [N]`, switch to N-th next buffer, in the order of buffer
+/// numbers.
+pub struct Bnext {
+    n: usize,
+}
+
+impl Bnext {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Bnext { n: to_count(&syn) })
+    }
+}
+
+impl Command for Bnext {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::BufferRotate(self.n, DP::Right)))
+    }
+}
+
+/// `:bp[revious] [N]`, switch to N-th previous buffer, in the order of
+/// buffer numbers.
+pub struct Bprevious {
+    n: usize,
+}
+
+impl Bprevious {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Bprevious { n: to_count(&syn) })
+    }
+}
+
+impl Command for Bprevious {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::BufferRotate(self.n, DP::Left)))
+    }
+}
+
+/// `:bd[elete][!] [N|name]`, delete buffer, default is current buffer.
+/// Modified buffers are deleted only with `!`.
+pub struct Bdelete {
+    name: Option<String>,
+    force: bool,
+}
+
+impl Bdelete {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let name = to_arg(&syn, "buffer_name");
+        let force = to_arg(&syn, "bang").is_some();
+        Ok(Bdelete { name, force })
+    }
+}
+
+impl Command for Bdelete {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let appn = Appn::BufferDelete(self.name.clone(), self.force);
+        Ok(Event::Appn(appn))
+    }
+}
+
+/// `:buffers`, `:ls` or `:files`, list all buffers.
+pub struct Buffers;
+
+impl Buffers {
+    pub fn new(_syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Buffers)
+    }
+}
+
+impl Command for Buffers {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::Buffers))
+    }
+}
+
+fn to_arg(syn: &syntax::CodeCmd, kind: &str) -> Option<String> {
+    let mut iter = syn.to_command_args()?.into_iter();
+    iter.find(|(k, _)| k == kind).map(|(_, text)| text)
+}
+
+fn to_count(syn: &syntax::CodeCmd) -> usize {
+    match to_arg(syn, "count").map(|n| n.parse::<usize>()) {
+        Some(Ok(n)) if n > 0 => n,
+        _ => 1,
+    }
+}
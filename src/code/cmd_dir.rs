@@ -0,0 +1,94 @@
+#[allow(unused_imports)]
+use log::{debug, error, trace};
+
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event, FileOp},
+    syntax, Result,
+};
+
+/// `:mkdir {dir}`, create directory `dir` in the listed directory.
+pub struct Mkdir {
+    dir: String,
+}
+
+impl Mkdir {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Mkdir {
+            dir: to_file_name(syn),
+        })
+    }
+}
+
+impl Command for Mkdir {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let op = FileOp::Create(self.dir.clone(), true);
+        Ok(Event::Appn(Appn::FileOp(op)))
+    }
+}
+
+/// `:mkfile {file}`, create an empty file in the listed directory.
+pub struct Mkfile {
+    file: String,
+}
+
+impl Mkfile {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Mkfile {
+            file: to_file_name(syn),
+        })
+    }
+}
+
+impl Command for Mkfile {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let op = FileOp::Create(self.file.clone(), false);
+        Ok(Event::Appn(Appn::FileOp(op)))
+    }
+}
+
+/// `:ren[ame] {file}`, rename file, or directory, under cursor in the
+/// directory listing.
+pub struct Rename {
+    file: String,
+}
+
+impl Rename {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Rename {
+            file: to_file_name(syn),
+        })
+    }
+}
+
+impl Command for Rename {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let op = FileOp::Rename(self.file.clone());
+        Ok(Event::Appn(Appn::FileOp(op)))
+    }
+}
+
+/// `:remove` or `:rm`, delete file, or directory along with its content,
+/// under cursor in the directory listing.
+pub struct Remove;
+
+impl Remove {
+    pub fn new(_syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Remove)
+    }
+}
+
+impl Command for Remove {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::FileOp(FileOp::Delete)))
+    }
+}
+
+fn to_file_name(syn: syntax::CodeCmd) -> String {
+    let args = syn.to_command_args().unwrap_or(vec![]);
+    let mut iter = args.into_iter().filter(|(kind, _)| kind == "file_name");
+    iter.next()
+        .map(|(_, text)| text)
+        .unwrap_or(String::default())
+}
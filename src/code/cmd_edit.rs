@@ -1,19 +1,56 @@
-#[derive(Clone)]
-pub enum Edit {
-    Initial {
-        name: String,
-    },
-    TabComp {
-        name: String,
-        choices: Vec<String>,
-        choice: usize,
-    },
+#[allow(unused_imports)]
+use log::{debug, error, trace};
+
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event},
+    syntax, Result,
+};
+
+/// `:e[dit][!] [file]`, edit `file` in the edit window. Without `file`
+/// re-edit the current buffer, `!` discards its modifications.
+pub struct Edit {
+    file: Option<String>,
+    force: bool,
 }
 
-impl Default for Edit {
-    fn default() -> Edit {
-        Edit::Initial {
-            name: "edit".to_string(),
+impl Edit {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Edit {
+            file: None,
+            force: false,
+        };
+        for (kind, text) in syn.to_command_args().unwrap_or(vec![]) {
+            match kind.as_str() {
+                "bang" => val.force = true,
+                "file_name" => val.file = Some(text),
+                _ => (),
+            }
         }
+        Ok(val)
+    }
+}
+
+impl Command for Edit {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let appn = Appn::Edit(self.file.clone(), self.force);
+        Ok(Event::Appn(appn))
+    }
+}
+
+/// `:finder`, pick a file, from current directory and its sub-directories,
+/// by fuzzy matching its path and edit the file.
+pub struct Finder;
+
+impl Finder {
+    pub fn new(_syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Finder)
+    }
+}
+
+impl Command for Finder {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::Finder))
     }
 }
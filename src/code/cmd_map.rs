@@ -0,0 +1,87 @@
+#[allow(unused_imports)]
+use log::{debug, error, trace};
+
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event},
+    keymap::MapMode,
+    syntax, Error, Result,
+};
+
+/// `:nm[ap] {lhs} {rhs}`, `:im[ap] {lhs} {rhs}` and `:cm[ap] {lhs} {rhs}`,
+/// map key sequence `lhs` to `rhs` in normal, insert and command-line mode.
+/// `rhs` is remapped, use `:no[remap]`, `:ino[remap]` and `:cno[remap]`
+/// for non-recursive mappings. Without `rhs`, list mappings starting with
+/// `lhs`, without arguments list all mappings for the mode. Keys are
+/// specified in key-notation, like `<C-w>`, `<Esc>`, `<leader>`.
+pub struct Map {
+    mode: MapMode,
+    noremap: bool,
+    lhs: Option<String>,
+    rhs: Option<String>,
+}
+
+impl Map {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let name = syn.to_command_name().unwrap_or("".to_string());
+        let (mode, noremap) = match MapMode::from_command(&name) {
+            Some(val) => val,
+            None => err_at!(Invalid, msg: format!("command {}", name))?,
+        };
+        let mut val = Map {
+            mode,
+            noremap,
+            lhs: None,
+            rhs: None,
+        };
+        for (kind, text) in syn.to_command_args().unwrap_or(vec![]) {
+            match kind.as_str() {
+                "map_lhs" => val.lhs = Some(text),
+                "map_rhs" => val.rhs = Some(text),
+                _ => (),
+            }
+        }
+        Ok(val)
+    }
+}
+
+impl Command for Map {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let appn = Appn::Map {
+            mode: self.mode,
+            noremap: self.noremap,
+            lhs: self.lhs.clone(),
+            rhs: self.rhs.clone(),
+        };
+        Ok(Event::Appn(appn))
+    }
+}
+
+/// `:unm[ap] {lhs}`, `:iu[nmap] {lhs}` and `:cu[nmap] {lhs}`, remove the
+/// mapping for `lhs` in normal, insert and command-line mode.
+pub struct Unmap {
+    mode: MapMode,
+    lhs: String,
+}
+
+impl Unmap {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mode = match syn.to_command_name().as_ref().map(|s| s.as_str()) {
+            Some("iunmap") => MapMode::Insert,
+            Some("cunmap") => MapMode::Command,
+            _ => MapMode::Normal,
+        };
+        let args = syn.to_command_args().unwrap_or(vec![]);
+        match args.into_iter().find(|(kind, _)| kind == "map_lhs") {
+            Some((_, lhs)) => Ok(Unmap { mode, lhs }),
+            None => err_at!(Invalid, msg: format!("argument required")),
+        }
+    }
+}
+
+impl Command for Unmap {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::Unmap(self.mode, self.lhs.clone())))
+    }
+}
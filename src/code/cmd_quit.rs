@@ -0,0 +1,132 @@
+#[allow(unused_imports)]
+use log::{debug, error, trace};
+
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event},
+    syntax, Result,
+};
+
+/// `:q[uit][!]`, quit the application. Quitting with modified buffers
+/// requires `!`, which discards all modifications.
+pub struct Quit {
+    force: bool,
+}
+
+impl Quit {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let force = is_bang(&syn);
+        Ok(Quit { force })
+    }
+}
+
+impl Command for Quit {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::Quit(self.force)))
+    }
+}
+
+/// `:[range]wq[!] [file]`, write buffer, like `:write`, and quit.
+/// `:[range]x[it][!] [file]`, same as `:wq`, but writes only when the
+/// buffer is modified.
+pub struct Wq {
+    range: Option<String>,
+    file: Option<String>,
+    force: bool,
+    update: bool,
+}
+
+impl Wq {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Wq {
+            range: syn.to_range(),
+            file: None,
+            force: false,
+            update: false,
+        };
+        for (kind, text) in syn.to_command_args().unwrap_or(vec![]) {
+            match kind.as_str() {
+                "bang" => val.force = true,
+                "file_name" => val.file = Some(text),
+                _ => (),
+            }
+        }
+        Ok(val)
+    }
+}
+
+impl Command for Wq {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let mut evnt = Event::Appn(Appn::Write {
+            range: self.range.clone(),
+            file: self.file.clone(),
+            append: false,
+            force: self.force,
+            update: self.update,
+        });
+        evnt.push(Event::Appn(Appn::Quit(self.force)));
+        Ok(evnt)
+    }
+}
+
+pub struct Xit(Wq);
+
+impl Xit {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Wq::new(syn)?;
+        val.update = true;
+        Ok(Xit(val))
+    }
+}
+
+impl Command for Xit {
+    fn on_command(&mut self, app: &mut Code) -> Result<Event> {
+        self.0.on_command(app)
+    }
+}
+
+/// `:qa[ll][!]`, same as `:quit`, modifications in any buffer are
+/// discarded only with `!`.
+pub struct Qall {
+    force: bool,
+}
+
+impl Qall {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let force = is_bang(&syn);
+        Ok(Qall { force })
+    }
+}
+
+impl Command for Qall {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::Quit(self.force)))
+    }
+}
+
+/// `:wqa[ll][!]` and `:xa[ll][!]`, write all modified buffers and quit.
+/// Read-only buffers are written only with `!`.
+pub struct Wqall {
+    force: bool,
+}
+
+impl Wqall {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let force = is_bang(&syn);
+        Ok(Wqall { force })
+    }
+}
+
+impl Command for Wqall {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let mut evnt = Event::Appn(Appn::WriteAll(self.force));
+        evnt.push(Event::Appn(Appn::Quit(self.force)));
+        Ok(evnt)
+    }
+}
+
+fn is_bang(syn: &syntax::CodeCmd) -> bool {
+    let args = syn.to_command_args().unwrap_or(vec![]);
+    args.iter().any(|(kind, _)| kind == "bang")
+}
@@ -2,47 +2,50 @@
 use log::{debug, error, trace};
 
 use crate::{
-    code::{config::Config, Code},
-    event::Event,
-    Result,
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event},
+    syntax, Result,
 };
 
-#[derive(Clone, Default)]
-pub struct Set {
-    param: String,
+/// `:[line]r[ead] [file]`, insert `file`, or the buffer's own file, below
+/// `line`, which defaults to the cursor line. Line `0` inserts above the
+/// first line. `:[line]r[ead] !{cmd}` insert the standard output of shell
+/// command `cmd`.
+pub struct Read {
+    range: Option<String>,
+    file: Option<String>,
+    shell: bool,
 }
 
-impl Set {
-    pub fn new(param: String) -> Self {
-        Set { param }
-    }
-}
-
-impl Set {
-    pub fn on_command(&mut self, app: &mut Code) -> Result<Event> {
-        let config: &mut Config = app.as_mut();
-        match self.param.as_str() {
-            "wrap" => {
-                config.wrap = true;
-                debug!("set all windows to wrap text");
-            }
-            "nowrap" => {
-                config.wrap = false;
-                debug!("set all windows to non-wrap text");
-            }
-            "ro" => {
-                config.read_only = true;
-                debug!("set default file open to read-only mode");
-            }
-            "noro" => {
-                config.read_only = false;
-                debug!("set default file open to read-write mode");
-            }
-            _ => {
-                error!("invalid configuration command {}", self.param);
+impl Read {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Read {
+            range: syn.to_range(),
+            file: None,
+            shell: false,
+        };
+        for (kind, text) in syn.to_command_args().unwrap_or(vec![]) {
+            match kind.as_str() {
+                "file_name" => val.file = Some(text),
+                "shell_cmd" => {
+                    val.file = Some(text);
+                    val.shell = true;
+                }
+                _ => (),
             }
         }
+        Ok(val)
+    }
+}
 
-        Ok(Event::Noop)
+impl Command for Read {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let appn = Appn::Read {
+            range: self.range.clone(),
+            file: self.file.clone(),
+            shell: self.shell,
+        };
+        Ok(Event::Appn(appn))
     }
 }
@@ -0,0 +1,41 @@
+#[allow(unused_imports)]
+use log::{debug, error, trace};
+
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event},
+    syntax, Result,
+};
+
+/// `:mks[ession][!] [file]`, save buffers, their cursor, marks and options,
+/// global marks and tabs to `file`, default is `Session.toml`. Overwriting
+/// an existing file requires `!`.
+pub struct Mksession {
+    file: Option<String>,
+    force: bool,
+}
+
+impl Mksession {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Mksession {
+            file: None,
+            force: false,
+        };
+        for (kind, text) in syn.to_command_args().unwrap_or(vec![]) {
+            match kind.as_str() {
+                "bang" => val.force = true,
+                "file_name" => val.file = Some(text),
+                _ => (),
+            }
+        }
+        Ok(val)
+    }
+}
+
+impl Command for Mksession {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let appn = Appn::MkSession(self.file.clone(), self.force);
+        Ok(Event::Appn(appn))
+    }
+}
@@ -1,72 +1,76 @@
 #[allow(unused_imports)]
 use log::{debug, error, trace};
 
-use crate::{code::cmd::Command, code::Code, event::Event, syntax, Result};
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    config::Scope,
+    event::{Appn, Event},
+    syntax, Result,
+};
 
+/// `:se[t] [arg...]`, where each `arg` can be, `opt` or `noopt` or
+/// `opt!` or `opt?` or `opt=val` or `opt+=val` or `opt-=val` or `all`.
+/// Without arguments, same as `:set all`. Options local to window or
+/// buffer are set on both, global value and local value.
 pub struct Set {
-    #[allow(dead_code)]
-    inner: Inner,
-}
-
-enum Inner {
-    #[allow(dead_code)]
-    Config {
-        param: String,
-    },
-    None,
+    scope: Scope,
+    args: Vec<String>,
 }
 
 impl Set {
     pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
-        match Self::convert(syn) {
-            Some(inner) => Ok(Set { inner }),
-            None => Ok(Set { inner: Inner::None }),
-        }
+        Ok(Self::new_scope(syn, Scope::Both))
     }
 
-    fn convert(syn: syntax::CodeCmd) -> Option<Inner> {
-        let tree = syn.into_parse_tree()?;
+    fn new_scope(syn: syntax::CodeCmd, scope: Scope) -> Self {
+        let args: Vec<String> = {
+            let iter = syn.to_command_args().unwrap_or(vec![]).into_iter();
+            iter.filter_map(|(kind, text)| if_else!(kind == "set_arg", Some(text), None))
+                .collect()
+        };
+        Set { scope, args }
+    }
+}
 
-        let node_cmd = {
-            let root = tree.root_node();
-            root.child(root.child_count().saturating_sub(1))?
+impl Command for Set {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let args = match self.args.len() {
+            0 => vec!["all".to_string()],
+            _ => self.args.clone(),
         };
+        Ok(Event::Appn(Appn::Set(self.scope, args)))
+    }
+}
 
-        let node_set = node_cmd.child(0)?;
-        assert_eq!(node_set.kind(), "set", "{}", node_set.kind());
+/// `:setl[ocal] [arg...]`, same as `:set` but set only the local value
+/// for options local to window or buffer.
+pub struct Setlocal(Set);
 
-        let node_config_param = node_set.child(1)?;
-        let param = node_config_param.child(0)?.kind().to_string();
-        Some(Inner::Config { param })
+impl Setlocal {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Setlocal(Set::new_scope(syn, Scope::Local)))
     }
 }
 
-impl Command for Set {
-    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
-        // use crate::code::config::Config
-        //let config: &mut Config = app.as_mut();
-        //match self.args.as_str() {
-        //    "wrap" => {
-        //        config.wrap = true;
-        //        debug!("set all windows to wrap text");
-        //    }
-        //    "nowrap" => {
-        //        config.wrap = false;
-        //        debug!("set all windows to non-wrap text");
-        //    }
-        //    "ro" => {
-        //        config.read_only = true;
-        //        debug!("set default file open to read-only mode");
-        //    }
-        //    "noro" => {
-        //        config.read_only = false;
-        //        debug!("set default file open to read-write mode");
-        //    }
-        //    _ => {
-        //        error!("invalid configuration command {}", self.args);
-        //    }
-        //}
+impl Command for Setlocal {
+    fn on_command(&mut self, app: &mut Code) -> Result<Event> {
+        self.0.on_command(app)
+    }
+}
+
+/// `:setg[lobal] [arg...]`, same as `:set` but set only the global value
+/// for options local to window or buffer.
+pub struct Setglobal(Set);
+
+impl Setglobal {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        Ok(Setglobal(Set::new_scope(syn, Scope::Global)))
+    }
+}
 
-        Ok(Event::Noop)
+impl Command for Setglobal {
+    fn on_command(&mut self, app: &mut Code) -> Result<Event> {
+        self.0.on_command(app)
     }
 }
@@ -0,0 +1,42 @@
+#[allow(unused_imports)]
+use log::{debug, error, trace};
+
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event},
+    syntax, Result,
+};
+
+/// `:[range]s[ubstitute]/{pattern}/{string}/[flags]`, substitute
+/// `pattern` with `string` in `range` lines, which defaults to the cursor
+/// line. Refer [crate::search::Substitute] for syntax.
+pub struct Substitute {
+    range: Option<String>,
+    args: String,
+}
+
+impl Substitute {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Substitute {
+            range: syn.to_range(),
+            args: String::default(),
+        };
+        for (kind, text) in syn.to_command_args().unwrap_or(vec![]) {
+            if kind == "subst_args" {
+                val.args = text;
+            }
+        }
+        Ok(val)
+    }
+}
+
+impl Command for Substitute {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let appn = Appn::Substitute {
+            range: self.range.clone(),
+            args: self.args.clone(),
+        };
+        Ok(Event::Appn(appn))
+    }
+}
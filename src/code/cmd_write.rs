@@ -1,19 +1,123 @@
-#[derive(Clone)]
-pub enum Write {
-    Initial {
-        name: String,
-    },
-    TabComp {
-        name: String,
-        choices: Vec<String>,
-        choice: usize,
-    },
-}
-
-impl Default for Write {
-    fn default() -> Write {
-        Write::Initial {
-            name: "write".to_string(),
+#[allow(unused_imports)]
+use log::{debug, error, trace};
+
+use crate::{
+    code::cmd::Command,
+    code::Code,
+    event::{Appn, Event},
+    syntax, Result,
+};
+
+/// `:[range]w[rite][!] [>>] [file]`, write buffer to its file or to
+/// `file`. With `>>` lines are appended to the file. Writing to an
+/// existing file, other than the buffer's, or to a read-only buffer
+/// requires `!`. `:up[date]` is same as `:write`, but writes only when
+/// the buffer is modified.
+pub struct Write {
+    range: Option<String>,
+    file: Option<String>,
+    append: bool,
+    force: bool,
+    update: bool,
+}
+
+impl Write {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Write {
+            range: syn.to_range(),
+            file: None,
+            append: false,
+            force: false,
+            update: false,
+        };
+        for (kind, text) in syn.to_command_args().unwrap_or(vec![]) {
+            match kind.as_str() {
+                "bang" => val.force = true,
+                "append" => val.append = true,
+                "file_name" => val.file = Some(text),
+                _ => (),
+            }
         }
+        Ok(val)
+    }
+}
+
+impl Command for Write {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let appn = Appn::Write {
+            range: self.range.clone(),
+            file: self.file.clone(),
+            append: self.append,
+            force: self.force,
+            update: self.update,
+        };
+        Ok(Event::Appn(appn))
+    }
+}
+
+pub struct Update(Write);
+
+impl Update {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Write::new(syn)?;
+        val.update = true;
+        Ok(Update(val))
+    }
+}
+
+impl Command for Update {
+    fn on_command(&mut self, app: &mut Code) -> Result<Event> {
+        self.0.on_command(app)
+    }
+}
+
+/// `:sav[eas][!] {file}`, save buffer as `file` and make it the buffer's
+/// file. Overwriting an existing file requires `!`.
+pub struct Saveas {
+    file: String,
+    force: bool,
+}
+
+impl Saveas {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let mut val = Saveas {
+            file: String::default(),
+            force: false,
+        };
+        for (kind, text) in syn.to_command_args().unwrap_or(vec![]) {
+            match kind.as_str() {
+                "bang" => val.force = true,
+                "file_name" => val.file = text,
+                _ => (),
+            }
+        }
+        Ok(val)
+    }
+}
+
+impl Command for Saveas {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        let appn = Appn::SaveAs(self.file.clone(), self.force);
+        Ok(Event::Appn(appn))
+    }
+}
+
+/// `:wa[ll][!]`, write all modified buffers. Read-only buffers are
+/// written only with `!`.
+pub struct Wall {
+    force: bool,
+}
+
+impl Wall {
+    pub fn new(syn: syntax::CodeCmd) -> Result<Self> {
+        let args = syn.to_command_args().unwrap_or(vec![]);
+        let force = args.iter().any(|(kind, _)| kind == "bang");
+        Ok(Wall { force })
+    }
+}
+
+impl Command for Wall {
+    fn on_command(&mut self, _app: &mut Code) -> Result<Event> {
+        Ok(Event::Appn(Appn::WriteAll(self.force)))
     }
 }
@@ -7,10 +7,48 @@ use crate::{Error, Result};
 config![
     // read-only will force all files to be opened in read-only mode.
     (read_only, bool, false),
+    // silently reload un-modified buffers, when their file is changed on
+    // disk, reload can be undone. Changes are detected when the edit window
+    // regains focus within the editor, not when the terminal regains focus.
+    (autoread, bool, false),
     (scroll_off, u16, 0),
     (line_number, bool, true),
     (wrap, bool, true),
+    // number of spaces to use for each step of indent.
+    (shift_width, usize, 4),
+    // search patterns are case insensitive.
+    (ignore_case, bool, false),
+    // with ignore_case, patterns having upper-case letters match case.
+    (smart_case, bool, false),
+    // keep a swap file, for each modified buffer, to recover unsaved
+    // edits after a crash.
+    (swap_file, bool, true),
+    // number of events after which swap files are written.
+    (update_count, usize, 200),
+    // jump to the last cursor position, when a file is opened again.
+    (remember_cursor, bool, true),
+    // number of entries to remember in each command-line history.
+    (history_size, usize, 100),
+    // key to substitute for `<leader>` when defining mappings.
+    (map_leader, char, '\\'),
+    // wait for timeout_len milliseconds to resolve an ambiguous mapping,
+    // when disabled wait for the next key.
+    (timeout, bool, true),
+    (timeout_len, u64, 1000),
     (left_margin_char, char, '|'),
     (top_margin_char, char, '-'),
     (color_scheme, String, "default".to_string())
 ];
+
+/// Options local to edit window, `:setlocal` on these options apply to
+/// the window, until it switches to another buffer.
+pub const WINDOW_OPTIONS: [&'static str; 3] = ["wrap", "scroll_off", "line_number"];
+
+/// Options local to buffer, `:setlocal` on these options apply to the
+/// buffer.
+pub const BUFFER_OPTIONS: [&'static str; 3] = ["ignore_case", "smart_case", "shift_width"];
+
+/// Options describing buffer's file, they are kept with the buffer, and
+/// its location, instead of configuration. Setting `binary` opens the
+/// buffer's file in hex window.
+pub const FILE_OPTIONS: [&'static str; 3] = ["fileencoding", "fileformat", "binary"];
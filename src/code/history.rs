@@ -0,0 +1,202 @@
+#[allow(unused_imports)]
+use log::{debug, warn};
+
+use std::{fs, path};
+
+use crate::{Error, Result};
+
+/// Type of command-line history.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// `:` ex-commands.
+    Cmd,
+    /// `/` and `?` search patterns.
+    Search,
+    /// `:!` filter commands.
+    Filter,
+}
+
+impl Kind {
+    fn to_tag(&self) -> char {
+        match self {
+            Kind::Cmd => ':',
+            Kind::Search => '/',
+            Kind::Filter => '!',
+        }
+    }
+
+    fn from_tag(tag: char) -> Option<Kind> {
+        match tag {
+            ':' => Some(Kind::Cmd),
+            '/' => Some(Kind::Search),
+            '!' => Some(Kind::Filter),
+            _ => None,
+        }
+    }
+}
+
+/// Command-line history, persisted in `~/.ted/history`. Each line in the
+/// file is an entry, prefixed with its tag, `:` for commands, `/` for
+/// searches and `!` for filters. Entries are ordered from oldest to newest.
+pub struct History {
+    limit: usize,
+    cmds: Vec<String>,
+    searches: Vec<String>,
+    filters: Vec<String>,
+}
+
+impl History {
+    /// Load history from `~/.ted/history`, keep atmost `limit` entries
+    /// for each kind.
+    pub fn load(limit: usize) -> History {
+        match Self::to_file() {
+            Some(file) => Self::load_file(&file, limit),
+            None => Self::new(limit),
+        }
+    }
+
+    /// Persist history into `~/.ted/history`.
+    pub fn save(&self) -> Result<()> {
+        match Self::to_file() {
+            Some(file) => self.save_file(&file),
+            None => err_at!(IOError, msg: format!("can't find home-directory")),
+        }
+    }
+
+    /// Add a new entry and append it to `~/.ted/history`, so that entries
+    /// are not lost if the application does not quit cleanly. Duplicate
+    /// entries in the file are compacted on [History::save].
+    pub fn add_append(&mut self, kind: Kind, entry: &str) -> Result<()> {
+        self.add(kind, entry);
+        if entry.len() == 0 || entry.contains('\n') {
+            return Ok(());
+        }
+        match Self::to_file() {
+            Some(file) => Self::append_file(&file, kind, entry),
+            None => err_at!(IOError, msg: format!("can't find home-directory")),
+        }
+    }
+
+    fn new(limit: usize) -> History {
+        History {
+            limit,
+            cmds: Vec::default(),
+            searches: Vec::default(),
+            filters: Vec::default(),
+        }
+    }
+
+    fn load_file(file: &path::Path, limit: usize) -> History {
+        let mut history = Self::new(limit);
+        if !file.exists() {
+            return history;
+        }
+        match fs::read_to_string(file) {
+            Ok(s) => {
+                for line in s.lines() {
+                    let mut chars = line.chars();
+                    match chars.next().map(Kind::from_tag) {
+                        Some(Some(kind)) => history.add(kind, chars.as_str()),
+                        _ => warn!("invalid history entry {:?}", line),
+                    }
+                }
+            }
+            Err(err) => warn!("fail reading history {:?}: {}", file, err),
+        }
+        debug!("loaded history from {:?}", file);
+
+        history
+    }
+
+    fn save_file(&self, file: &path::Path) -> Result<()> {
+        if let Some(dir) = file.parent() {
+            err_at!(IOError, fs::create_dir_all(dir))?;
+        }
+
+        let mut s = String::default();
+        for kind in [Kind::Cmd, Kind::Search, Kind::Filter].iter() {
+            for entry in self.as_entries(*kind).iter() {
+                s.push(kind.to_tag());
+                s.push_str(entry);
+                s.push('\n');
+            }
+        }
+        err_at!(IOError, fs::write(file, s))
+    }
+
+    fn append_file(file: &path::Path, kind: Kind, entry: &str) -> Result<()> {
+        use std::io::Write;
+
+        if let Some(dir) = file.parent() {
+            err_at!(IOError, fs::create_dir_all(dir))?;
+        }
+
+        let mut fd = {
+            let mut opts = fs::OpenOptions::new();
+            err_at!(IOError, opts.append(true).create(true).open(file))?
+        };
+        let line = format!("{}{}\n", kind.to_tag(), entry);
+        err_at!(IOError, fd.write_all(line.as_bytes()))
+    }
+
+    /// Add a new entry, duplicate entries are moved to the end.
+    pub fn add(&mut self, kind: Kind, entry: &str) {
+        if entry.len() == 0 || entry.contains('\n') {
+            return;
+        }
+
+        let limit = self.limit;
+        let entries = self.as_mut_entries(kind);
+        entries.retain(|e| e != entry);
+        entries.push(entry.to_string());
+        if entries.len() > limit {
+            entries.drain(..(entries.len() - limit));
+        }
+    }
+
+    /// Return entries, from oldest to newest.
+    pub fn as_entries(&self, kind: Kind) -> &[String] {
+        match kind {
+            Kind::Cmd => &self.cmds,
+            Kind::Search => &self.searches,
+            Kind::Filter => &self.filters,
+        }
+    }
+
+    /// Return the entry, older than `off` and starting with `prefix`.
+    /// `off` is an index into entries, if None start from newest.
+    pub fn to_older(&self, kind: Kind, off: Option<usize>, prefix: &str) -> Option<usize> {
+        let entries = self.as_entries(kind);
+        let off = off.unwrap_or(entries.len());
+        (0..off)
+            .rev()
+            .filter(|i| entries[*i].starts_with(prefix))
+            .next()
+    }
+
+    /// Return the entry, newer than `off` and starting with `prefix`.
+    pub fn to_newer(&self, kind: Kind, off: usize, prefix: &str) -> Option<usize> {
+        let entries = self.as_entries(kind);
+        let from = off.saturating_add(1);
+        (from..entries.len())
+            .filter(|i| entries[*i].starts_with(prefix))
+            .next()
+    }
+
+    fn as_mut_entries(&mut self, kind: Kind) -> &mut Vec<String> {
+        match kind {
+            Kind::Cmd => &mut self.cmds,
+            Kind::Search => &mut self.searches,
+            Kind::Filter => &mut self.filters,
+        }
+    }
+
+    fn to_file() -> Option<path::PathBuf> {
+        let home = dirs::home_dir()?.into_os_string();
+        Some([home, ".ted".into(), "history".into()].iter().collect())
+    }
+}
+
+#[cfg(test)]
+#[path = "history_test.rs"]
+mod history_test;
@@ -0,0 +1,83 @@
+use super::*;
+
+#[test]
+fn test_history_add() {
+    let mut history = History::new(3);
+    history.add(Kind::Cmd, "set wrap");
+    history.add(Kind::Cmd, "w");
+    history.add(Kind::Cmd, "set wrap");
+    history.add(Kind::Cmd, "");
+    history.add(Kind::Cmd, "echo\nhello");
+    history.add(Kind::Search, "hello");
+
+    assert_eq!(history.as_entries(Kind::Cmd), &["w", "set wrap"]);
+    assert_eq!(history.as_entries(Kind::Search), &["hello"]);
+    assert_eq!(history.as_entries(Kind::Filter).len(), 0);
+
+    history.add(Kind::Cmd, "e a.txt");
+    history.add(Kind::Cmd, "e b.txt");
+    assert_eq!(
+        history.as_entries(Kind::Cmd),
+        &["set wrap", "e a.txt", "e b.txt"]
+    );
+}
+
+#[test]
+fn test_history_recall() {
+    let mut history = History::new(10);
+    for entry in ["set wrap", "e a.txt", "set nowrap", "w"].iter() {
+        history.add(Kind::Cmd, entry);
+    }
+
+    assert_eq!(history.to_older(Kind::Cmd, None, ""), Some(3));
+    assert_eq!(history.to_older(Kind::Cmd, None, "set"), Some(2));
+    assert_eq!(history.to_older(Kind::Cmd, Some(2), "set"), Some(0));
+    assert_eq!(history.to_older(Kind::Cmd, Some(0), "set"), None);
+    assert_eq!(history.to_newer(Kind::Cmd, 0, "set"), Some(2));
+    assert_eq!(history.to_newer(Kind::Cmd, 2, "set"), None);
+    assert_eq!(history.to_older(Kind::Search, None, ""), None);
+}
+
+#[test]
+fn test_history_file() {
+    let file = std::env::temp_dir().join("ted-test-history");
+
+    let mut history = History::new(10);
+    history.add(Kind::Cmd, "set wrap");
+    history.add(Kind::Search, "hello");
+    history.add(Kind::Filter, "sort");
+    history.add(Kind::Cmd, "w");
+    history.save_file(&file).unwrap();
+
+    let history = History::load_file(&file, 10);
+    assert_eq!(history.as_entries(Kind::Cmd), &["set wrap", "w"]);
+    assert_eq!(history.as_entries(Kind::Search), &["hello"]);
+    assert_eq!(history.as_entries(Kind::Filter), &["sort"]);
+
+    // invalid entries are skipped and limit is applied while loading.
+    fs::write(&file, ":a\n:b\nxinvalid\n:c\n\n/d\n").unwrap();
+    let history = History::load_file(&file, 2);
+    assert_eq!(history.as_entries(Kind::Cmd), &["b", "c"]);
+    assert_eq!(history.as_entries(Kind::Search), &["d"]);
+
+    fs::remove_file(&file).unwrap();
+    let history = History::load_file(&file, 2);
+    assert_eq!(history.as_entries(Kind::Cmd).len(), 0);
+}
+
+#[test]
+fn test_history_append() {
+    let file = std::env::temp_dir().join("ted-test-history-append");
+    fs::remove_file(&file).ok();
+
+    History::append_file(&file, Kind::Cmd, "set wrap").unwrap();
+    History::append_file(&file, Kind::Search, "hello").unwrap();
+    History::append_file(&file, Kind::Cmd, "w").unwrap();
+    History::append_file(&file, Kind::Cmd, "set wrap").unwrap();
+
+    let history = History::load_file(&file, 10);
+    assert_eq!(history.as_entries(Kind::Cmd), &["w", "set wrap"]);
+    assert_eq!(history.as_entries(Kind::Search), &["hello"]);
+
+    fs::remove_file(&file).unwrap();
+}
@@ -1,33 +1,53 @@
 mod cmd;
+mod cmd_buffer;
+mod cmd_dir;
+mod cmd_edit;
+mod cmd_map;
+mod cmd_quit;
+mod cmd_read;
+mod cmd_session;
 mod cmd_set;
-//mod cmd_edit;
+mod cmd_subst;
+mod cmd_write;
 //mod cmd_file;
-//mod cmd_write;
 
 mod config;
+mod history;
+mod session;
 mod window_cmd;
 mod window_edit;
 mod window_file;
 
 #[allow(unused_imports)]
 use log::{debug, error, trace};
+use regex::Regex;
 use toml;
 
-use std::{convert::TryFrom, ffi, mem, sync::mpsc};
+use std::{cmp, convert::TryFrom, ffi, mem, path, process, sync::mpsc, time};
 
 use crate::{
     app::Application,
     buffer::Buffer,
-    code::config::Config,
+    code::config::{Config, ConfigToml},
+    code::history::History,
+    code::session::{Positions, Session},
     code::window_cmd::WindowCmd,
     code::window_file::WindowFile,
     colors::ColorScheme,
+    config::{Scope, SetOpt},
     event::{self, Event},
+    keymap::{self, MapMode, Mappings},
     location::Location,
     mark,
     pubsub::{self, PubSub},
+    search::Substitute,
     state::{self, State},
-    window::{Coord, Cursor, Window, WindowLess, WindowPrompt, WindowStatus, WindowSuggest},
+    swap, syntax, text,
+    window::WindowSuggest,
+    window::{
+        Coord, Cursor, WinBuffer, Window, WindowFinder, WindowHex, WindowLess, WindowPrompt,
+        WindowStatus,
+    },
     Error, Result,
 };
 
@@ -37,6 +57,10 @@ pub struct Code {
     config_value: toml::Value,
     // application only configuration.
     config: Config,
+    // a copy of application-state's configuration, updated by `:set`.
+    state_config: state::Config,
+    // buffer-local options, for buffer number.
+    locals: Vec<(usize, ConfigToml)>,
     // screen coordinate for this application.
     coord: Coord,
     // application local subscribe-publish instance, also includes
@@ -48,6 +72,14 @@ pub struct Code {
     buffers: Vec<Buffer>,
     // list of global marks,
     marks: mark::Marks,
+    // command-line history.
+    history: History,
+    // last cursor position of files.
+    positions: Positions,
+    // user mappings, from `[code.keymap]` and `:map` commands.
+    keymaps: Mappings,
+    // number of events since swap files were last written.
+    n_events: usize,
 
     // application state machine
     inner: Inner,
@@ -58,6 +90,11 @@ enum Inner {
     Prompt(Prompt),
     Command(Command),
     Less(Less),
+    Hex(Hex),
+    Finder(Finder),
+    Changed(Changed),
+    Recover(Recover),
+    Confirm(Confirm),
     None,
 }
 
@@ -68,9 +105,24 @@ impl Inner {
             Inner::Prompt(val) => val.edit,
             Inner::Command(val) => val.edit,
             Inner::Less(val) => val.edit,
+            Inner::Hex(val) => val.edit,
+            Inner::Finder(val) => val.edit,
+            Inner::Changed(val) => val.edit,
+            Inner::Recover(val) => val.edit,
+            Inner::Confirm(val) => val.edit,
             Inner::None => unreachable!(),
         }
     }
+
+    fn is_prompt(&self) -> bool {
+        match self {
+            Inner::Prompt(_) => true,
+            Inner::Changed(_) => true,
+            Inner::Recover(_) => true,
+            Inner::Confirm(_) => true,
+            _ => false,
+        }
+    }
 }
 
 struct Edit {
@@ -96,6 +148,48 @@ struct Less {
     wless: WindowLess,
 }
 
+struct Hex {
+    edit: Edit,
+    whex: WindowHex,
+}
+
+struct Finder {
+    edit: Edit,
+    wfind: WindowFinder,
+}
+
+// buffer `id`, whose file was changed on disk, waiting for the user to
+// reload, keep or diff the buffer.
+struct Changed {
+    edit: Edit,
+    id: String,
+    prompt: WindowPrompt,
+}
+
+// swap file, left behind for buffer `id`, waiting for the user to recover,
+// diff or delete the swap file.
+struct Recover {
+    edit: Edit,
+    id: String,
+    journal: Option<swap::Journal>,
+    prompt: WindowPrompt,
+}
+
+// file operation in directory listing, waiting for the user to confirm.
+struct Confirm {
+    edit: Edit,
+    op: DirOp,
+    prompt: WindowPrompt,
+}
+
+// file operation resolved into paths, flag in `Create` and `Delete` is
+// true for directories.
+enum DirOp {
+    Create(path::PathBuf, bool),
+    Rename(path::PathBuf, path::PathBuf),
+    Delete(path::PathBuf, bool),
+}
+
 impl Default for Inner {
     fn default() -> Inner {
         Inner::None
@@ -134,30 +228,65 @@ impl<'a> TryFrom<(&'a State, Coord)> for Code {
             toml::to_string(&config).unwrap(),
         );
 
+        let keymaps = {
+            let value = crate::config::to_section(state.config_value.clone(), "code.keymap");
+            Mappings::from_toml(&value, config.map_leader)?
+        };
+
         let mut app = Code {
             config_value: state.config_value.clone(),
             config: config.clone(),
+            state_config: state.config.clone(),
+            locals: Vec::default(),
             coord,
             schemes: state.schemes.clone(),
             subscribers: state.subscribers.clone(),
             buffers: Vec::default(),
             marks: mark::new_marks(),
+            history: History::load(config.history_size),
+            positions: Positions::load(),
+            keymaps,
+            n_events: 0,
             inner: Inner::default(),
         };
 
-        let (buffers, prompts) = {
+        let (buffers, mut prompts) = {
             let files = {
+                // in binary mode, files are decoded as latin1, which maps
+                // every byte to a character, so that any file can be opened.
+                let enc = match (&state.opts.encoding, state.opts.binary) {
+                    (None, true) => Some("latin1".to_string()),
+                    (enc, _) => enc.clone(),
+                };
                 let iter = state.opts.files.iter();
-                iter.map(|f| (f.clone(), format!("utf-8"))).collect()
+                iter.map(|f| (f.clone(), enc.clone())).collect()
             };
-            match app.open_cmd_files(files) {
-                (bufs, ps) if bufs.len() == 0 => (vec![Buffer::empty()], ps),
-                (bufs, ps) => (bufs, ps),
-            }
+            app.open_cmd_files(files, state.stdin.clone())
+        };
+        app.buffers = buffers;
+
+        // session buffers are opened after the files from command line.
+        let tab = match &state.opts.session {
+            Some(file) => match app.restore_session(file) {
+                Ok((tab, ps)) => {
+                    prompts.extend(ps);
+                    tab
+                }
+                Err(err) => {
+                    prompts.push(app.to_error_prompt(err.to_error()));
+                    None
+                }
+            },
+            None => None,
         };
+        if app.buffers.len() == 0 {
+            let mut buf = Buffer::empty();
+            app.config_buffer(&mut buf);
+            app.buffers.push(buf);
+        }
 
-        let edit = {
-            let buffer = buffers.first().unwrap();
+        let mut edit = {
+            let buffer = app.buffers.first().unwrap();
             let scheme = app.to_color_scheme(None);
             Edit {
                 wfile: (&app, buffer, app.to_coord_wfile()).into(),
@@ -166,11 +295,25 @@ impl<'a> TryFrom<(&'a State, Coord)> for Code {
             }
         };
 
-        app.buffers = buffers;
+        // files from command line are shown, instead of session's tab.
+        match tab {
+            Some(tab) if state.opts.files.len() == 0 => {
+                if let Err(err) = app.restore_tab(&mut edit, tab) {
+                    prompts.push(app.to_error_prompt(err.to_error()));
+                }
+            }
+            _ => (),
+        }
+
+        let loc = app.buffers.first().unwrap().to_location();
         app.inner = if prompts.len() > 0 {
             Inner::Prompt(Prompt { edit, prompts })
+        } else if state.opts.binary && state.opts.files.len() > 0 {
+            let scheme = app.to_color_scheme(None);
+            let whex = WindowHex::new(loc, app.to_coord_wfile(), scheme)?;
+            Inner::Hex(Hex { edit, whex })
         } else {
-            Inner::Edit(edit)
+            app.on_focus(edit)
         };
         Ok(app)
     }
@@ -226,6 +369,19 @@ impl Code {
         coord
     }
 
+    // floating window, centered on edit window.
+    #[inline]
+    fn to_coord_wfinder(&self) -> Coord {
+        let mut coord = self.to_coord_wfile();
+        let hgt = cmp::min(cmp::max(coord.hgt * 3 / 5, 2), coord.hgt);
+        let wth = coord.wth * 4 / 5;
+        coord.row += (coord.hgt - hgt) / 2;
+        coord.col += (coord.wth - wth) / 2;
+        coord.hgt = hgt;
+        coord.wth = wth;
+        coord
+    }
+
     #[inline]
     fn to_coord_wsugg(&self) -> Coord {
         let mut coord = self.coord;
@@ -267,18 +423,96 @@ impl Code {
     pub fn to_wsugg(&self) -> WindowSuggest {
         WindowSuggest::new(self.to_coord_wsugg(), self.to_color_scheme(None))
     }
+
+    pub fn as_history(&self) -> &History {
+        &self.history
+    }
+
+    /// Add an entry to command-line history, entry is appended to the
+    /// history file right away, and the file is compacted when the
+    /// application quits.
+    pub fn add_history(&mut self, kind: history::Kind, entry: &str) {
+        if let Err(err) = self.history.add_append(kind, entry) {
+            error!("fail appending history: {}", err);
+        }
+    }
 }
 
 impl Code {
-    fn open_cmd_files(&self, files: Vec<(String, String)>) -> (Vec<Buffer>, Vec<WindowPrompt>) {
+    // apply application configuration on a newly created buffer.
+    fn config_buffer(&self, buf: &mut Buffer) {
+        let config = self.to_buffer_config(buf);
+        buf.set_ignore_case(config.ignore_case)
+            .set_smart_case(config.smart_case)
+            .set_shift_width(config.shift_width);
+    }
+
+    // command-line, and search-line, are allowed only in normal mode.
+    fn is_cmd_leader(&self, edit: &Edit, ch: char) -> bool {
+        use crate::event::Event::{Noop, N};
+
+        let normal = match self.as_buffer(&edit.wfile.to_buffer_id()) {
+            Some(buf) => buf.to_mode() == "normal",
+            None => false,
+        };
+        let prefix = match edit.wfile.to_event_prefix() {
+            Noop | N(_) => true,
+            _ => false,
+        };
+        normal && prefix && (ch == ':' || ch == '/' || ch == '?')
+    }
+
+    fn to_disk_buffer(&self, loc: Location) -> Result<Buffer> {
+        let read_only = loc.is_read_only();
+        let loc_msg = loc.to_string();
+        let mut buf = Buffer::from_reader(loc)?;
+        if let (format, true) = text::Format::detect_chunks(buf.to_rope().chunks()) {
+            let msg = format!("{} has mixed line endings, fileformat={}", loc_msg, format);
+            let status = pubsub::Notify::Status(vec![msg.clone().into()]);
+            if let Err(err) = self.notify("code", status) {
+                debug!("{}: {}", msg, err);
+            }
+        }
+        self.config_buffer(&mut buf);
+        if read_only {
+            debug!("opening {} in read-mode", loc_msg);
+            buf.set_read_only(true);
+        } else {
+            debug!("opening {} in write-mode", loc_msg);
+            buf.set_read_only(self.config.read_only);
+        }
+        Ok(buf)
+    }
+
+    // open a file for editing, common to files from command line and
+    // `:e`, resuming from its last cursor position.
+    fn open_disk_buffer(&self, loc: Location) -> Result<Buffer> {
+        let mut buf = self.to_disk_buffer(loc)?;
+        self.restore_position(&mut buf);
+        Ok(buf)
+    }
+
+    // open files from command line, file `-` is opened with `stdin`.
+    fn open_cmd_files(
+        &self,
+        files: Vec<(String, Option<String>)>,
+        stdin: Option<Location>,
+    ) -> (Vec<Buffer>, Vec<WindowPrompt>) {
         let coord = self.to_coord_wprompt();
         let (mut buffers, mut prompts) = (vec![], vec![]);
         let scheme = self.to_color_scheme(None);
 
         let mut locs: Vec<Location> = vec![];
         for (f, e) in files.into_iter() {
+            match (f.as_str(), &stdin) {
+                ("-", Some(loc)) => {
+                    locs.push(loc.clone());
+                    continue;
+                }
+                _ => (),
+            }
             let f: ffi::OsString = f.into();
-            match Location::new_disk(&f, &e) {
+            match Location::new_disk(&f, e.as_deref()) {
                 Ok(loc) => locs.push(loc),
                 Err(err) => {
                     let lines = vec![
@@ -292,19 +526,9 @@ impl Code {
         }
 
         for loc in locs.into_iter() {
-            let read_only = loc.is_read_only();
             let loc_msg = loc.to_string();
-            match Buffer::from_reader(loc) {
-                Ok(mut buf) if read_only => {
-                    debug!("opening {} in read-mode", loc_msg);
-                    buf.set_read_only(true);
-                    buffers.push(buf);
-                }
-                Ok(mut buf) => {
-                    debug!("opening {} in write-mode", loc_msg);
-                    buf.set_read_only(self.config.read_only);
-                    buffers.push(buf);
-                }
+            match self.open_disk_buffer(loc) {
+                Ok(buf) => buffers.push(buf),
                 Err(err) => {
                     let lines = vec![
                         format!("error opening {} : {}", loc_msg, err.to_error()),
@@ -320,143 +544,1953 @@ impl Code {
     }
 }
 
-impl Application for Code {
-    fn subscribe(&mut self, topic: &str, tx: mpsc::Sender<pubsub::Notify>) {
-        self.subscribers.subscribe(topic, tx);
-    }
+/// Option commands, operating on application's and application-state's
+/// configuration.
+impl Code {
+    // apply `:set` arguments, return `Appn::Less` for `:set all` and
+    // `Appn::StateOption` for options owned by application-state.
+    fn on_set_cmd(&mut self, edit: &mut Edit, scope: Scope, args: Vec<String>) -> Result<Event> {
+        use crate::event::Appn;
 
-    fn notify(&self, topic: &str, msg: pubsub::Notify) -> Result<()> {
-        self.subscribers.notify(topic, msg)
-    }
+        let old_config = self.config.clone();
 
-    fn to_cursor(&self) -> Option<Cursor> {
-        match &self.inner {
-            Inner::Edit(val) => val.wfile.to_cursor(),
-            Inner::Prompt(val) => val.prompts[0].to_cursor(),
-            Inner::Command(val) => val.wcmd.to_cursor(),
-            Inner::Less(val) => val.wless.to_cursor(),
-            Inner::None => None,
+        let mut evnt = Event::default();
+        let mut shows = vec![];
+        let mut changed = false;
+        let res = args.into_iter().try_for_each(|arg| {
+            let local = match scope {
+                Scope::Global => self.config.clone(),
+                Scope::Both | Scope::Local => self.to_local_config(edit),
+            };
+            if arg == "all" {
+                let wless = self.to_options_less(&local)?;
+                evnt.push(Event::Appn(Appn::Less(Box::new(wless))));
+                return Ok(());
+            }
+
+            let (name, op) = SetOpt::parse(&arg, |name| {
+                let val = self.config.is_bool_option(name);
+                let val = val.or(self.state_config.is_bool_option(name));
+                val.or(if_else!(
+                    config::FILE_OPTIONS.contains(&name),
+                    Some(name == "binary"),
+                    None
+                ))
+            })?;
+            if config::FILE_OPTIONS.contains(&name.as_str()) {
+                let res = self.on_file_option(edit, &name, op, &mut shows);
+                return res.map(|e| evnt.push(e));
+            }
+            let is_local = {
+                let name = name.as_str();
+                config::WINDOW_OPTIONS.contains(&name) || config::BUFFER_OPTIONS.contains(&name)
+            };
+            match (op, scope) {
+                (SetOpt::Show, _) => {
+                    let val = local.to_option(&name);
+                    shows.extend(val.or(self.state_config.to_option(&name)))
+                }
+                (_, Scope::Local) if !is_local => {
+                    err_at!(Invalid, msg: format!("{} is a global option", name))?
+                }
+                (op, _) if self.config.is_bool_option(&name).is_none() => {
+                    self.state_config.set_option(&name, &op)?;
+                    evnt.push(Event::Appn(Appn::StateOption(name, op)));
+                }
+                (op, Scope::Global) => {
+                    let mut config = self.config.clone();
+                    config.set_option(&name, &op)?;
+                    self.check_config(&config)?;
+                    self.config = config;
+                    changed = true;
+                }
+                (op, scope) => {
+                    let mut config = local;
+                    config.set_option(&name, &op)?;
+                    self.check_config(&config)?;
+                    if scope == Scope::Both {
+                        self.config.copy_option(&name, &config)?;
+                    }
+                    if is_local {
+                        self.set_local_option(edit, &name, &config)?;
+                    }
+                    changed = true;
+                }
+            }
+            Ok(())
+        });
+
+        // options that were set before an error remain set.
+        if changed {
+            self.apply_config(edit, &old_config)?;
         }
+        res?;
+
+        if shows.len() > 0 {
+            let spans = vec![shows.join("  ").into()];
+            self.notify("code", pubsub::Notify::Status(spans))?;
+        }
+        Ok(evnt)
     }
 
-    fn on_event(&mut self, evnt: Event) -> Result<Event> {
-        use crate::event::Mto;
+    // file options are applied on the location of buffer in edit window,
+    // return `Appn::Hex` for `:set binary`.
+    fn on_file_option(
+        &mut self,
+        edit: &mut Edit,
+        name: &str,
+        op: SetOpt,
+        shows: &mut Vec<String>,
+    ) -> Result<Event> {
+        let id = edit.wfile.to_buffer_id();
+        if name == "binary" {
+            return self.on_binary_option(&id, op, shows);
+        }
 
-        let inner = mem::replace(&mut self.inner, Inner::default());
-        let (mut inner, evnt) = match (inner, evnt.clone()) {
-            (Inner::Edit(edit), Event::Mr(mrk)) => {
-                debug!("code event {}", evnt);
-                mark::set_mark(&mut self.marks, mrk);
-                (Inner::Edit(edit), Event::Noop)
-            }
-            (Inner::Edit(edit), Event::Mt(Mto::Jump('`', _mindex))) => {
-                debug!("code event {}", evnt);
-                // TODO: use `:buffer` command to load the buffer.
-                // TODO: set the cursor, clear-sticky-col
-                (Inner::Edit(edit), Event::Noop)
+        let buf = match self.as_mut_buffer(&id) {
+            Some(buf) => buf,
+            None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+        };
+        let mut loc = buf.to_location();
+        let evnt = match (name, op) {
+            ("fileencoding", SetOpt::Show) => {
+                shows.push(format!("fileencoding={}", loc.to_encoding()));
+                Event::Noop
             }
-            (Inner::Edit(edit), Event::Mt(Mto::Jump('\'', _mindex))) => {
-                debug!("code event {}", evnt);
-                // TODO: use `:buffer` command to load the buffer.
-                // TODO: set the cursor, clear-sticky-col
-                // TODO: mto_line_home
-                (Inner::Edit(edit), Event::Noop)
+            ("fileencoding", SetOpt::Assign(val)) => {
+                loc.set_encoding(&val)?;
+                buf.set_location(loc);
+                Event::Noop
             }
-            (Inner::Edit(edit), Event::Mt(Mto::Jump(typ, mindex))) => {
-                debug!("code event {}", evnt);
-                (Inner::Edit(edit), Event::Mt(Mto::Jump(typ, mindex)))
+            ("fileformat", SetOpt::Show) => {
+                shows.push(format!("fileformat={}", buf.format));
+                Event::Noop
             }
-            (Inner::Edit(edit), Event::Char(':', m)) if m.is_empty() => {
-                debug!("code event {}", evnt);
-                let prefix = edit.wfile.to_event_prefix();
-                let wcmd = WindowCmd::new(self.to_coord_wcmd(), self)?;
-                let mut val = Command { edit, wcmd };
-                let evnt = val.wcmd.on_event(self, prefix)?;
-                (Inner::Command(val), evnt)
+            ("fileformat", SetOpt::Assign(val)) => buf.convert_format(val.parse()?)?,
+            (name, _) => err_at!(Invalid, msg: format!("invalid operation on {}", name))?,
+        };
+        // update the syntax tree, like any other edit on buffer.
+        edit.wfile.on_event(self, evnt)?;
+        Ok(Event::Noop)
+    }
+
+    // buffers are always edited as text, setting `binary` opens buffer's
+    // file in hex window.
+    fn on_binary_option(&self, id: &str, op: SetOpt, shows: &mut Vec<String>) -> Result<Event> {
+        use crate::event::Appn;
+
+        match op {
+            SetOpt::Show => shows.push("nobinary".to_string()),
+            SetOpt::On | SetOpt::Toggle => {
+                let loc = match self.as_buffer(id) {
+                    Some(buf) if buf.is_modified() => {
+                        let msg = format!("buffer {:?} is modified, write it first", id);
+                        err_at!(Invalid, msg: msg)?
+                    }
+                    Some(buf) => buf.to_location(),
+                    None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+                };
+                let scheme = self.to_color_scheme(None);
+                let whex = WindowHex::new(loc, self.to_coord_wfile(), scheme)?;
+                return Ok(Event::Appn(Appn::Hex(Box::new(whex))));
             }
-            (Inner::Edit(mut edit), evnt) => {
-                let evnt = edit.wfile.on_event(self, evnt)?;
-                (Inner::Edit(edit), evnt)
+            SetOpt::Off => (),
+            _ => err_at!(Invalid, msg: format!("invalid operation on binary"))?,
+        }
+        Ok(Event::Noop)
+    }
+
+    // reload buffer in edit window, after its file was written from the
+    // hex window.
+    fn reload_hex_buffer(&mut self, edit: &mut Edit) -> Result<()> {
+        let id = edit.wfile.to_buffer_id();
+        self.reload_buffer(&id, false)?;
+        self.switch_buffer(edit, &id)
+    }
+
+    fn check_config(&self, config: &Config) -> Result<()> {
+        let name = &config.color_scheme;
+        if self.schemes.iter().any(|scheme| &scheme.name == name) {
+            Ok(())
+        } else {
+            err_at!(Invalid, msg: format!("no color scheme {:?}", name))
+        }
+    }
+
+    // set window-local, or buffer-local, option `name` from `config`.
+    fn set_local_option(&mut self, edit: &mut Edit, name: &str, config: &Config) -> Result<()> {
+        if config::WINDOW_OPTIONS.contains(&name) {
+            edit.wfile.set_local_option(name, config)?;
+            return Ok(());
+        }
+
+        let num = match self.as_buffer(&edit.wfile.to_buffer_id()) {
+            Some(buf) => buf.to_num(),
+            None => return Ok(()),
+        };
+        match self.locals.iter_mut().find(|(n, _)| *n == num) {
+            Some((_, local)) => local.copy_option(name, config)?,
+            None => {
+                let mut local = ConfigToml::default();
+                local.copy_option(name, config)?;
+                self.locals.push((num, local));
             }
-            (Inner::Prompt(mut prompt), evnt) => {
-                let evnt = prompt.prompts[0].on_event(evnt)?;
-                if let Some(_) = prompt.prompts[0].prompt_match() {
-                    prompt.prompts.remove(0);
-                }
-                match prompt.prompts.len() {
-                    0 => (Inner::Edit(prompt.edit), evnt),
-                    _ => (Inner::Prompt(prompt), evnt),
-                }
+        }
+        Ok(())
+    }
+
+    // apply changed configuration on buffers and windows.
+    fn apply_config(&mut self, edit: &mut Edit, old: &Config) -> Result<()> {
+        let mut buffers = mem::replace(&mut self.buffers, vec![]);
+        buffers.iter_mut().for_each(|buf| self.config_buffer(buf));
+        self.buffers = buffers;
+
+        if old.color_scheme != self.config.color_scheme {
+            let scheme = self.to_color_scheme(None);
+            edit.wsugg = WindowSuggest::new(self.to_coord_wsugg(), scheme);
+            // window is re-built to apply the new color scheme.
+            let id = edit.wfile.to_buffer_id();
+            self.switch_buffer(edit, &id)
+        } else {
+            edit.wfile.set_config(self);
+            Ok(())
+        }
+    }
+
+    /// Return configuration for buffer, global configuration overridden
+    /// by file-type configuration and then by buffer-local options.
+    pub fn to_buffer_config(&self, buf: &Buffer) -> Config {
+        let mut config = self.config.clone();
+
+        let ftype = syntax::to_file_type(buf);
+        if ftype.len() > 0 {
+            let value = crate::config::read_ftype_config(&ftype).map(|value| {
+                let value = crate::config::to_section(value, "code");
+                ConfigToml::try_from(value)
+            });
+            match value {
+                Ok(Ok(ctml)) => config = config.mixin(ctml),
+                Ok(Err(err)) | Err(err) => error!("config for ftype {}: {}", ftype, err),
             }
-            (Inner::Command(mut cmd), evnt) => {
-                let evnt = cmd.wcmd.on_event(self, evnt)?;
-                (Inner::Command(cmd), evnt)
+        }
+
+        let num = buf.to_num();
+        match self.locals.iter().find(|(n, _)| *n == num) {
+            Some((_, local)) => config.mixin(local.clone()),
+            None => config,
+        }
+    }
+
+    // return configuration for buffer shown in edit window, along with
+    // window-local options.
+    fn to_local_config(&self, edit: &Edit) -> Config {
+        match self.as_buffer(&edit.wfile.to_buffer_id()) {
+            Some(buf) => {
+                let config = self.to_buffer_config(buf);
+                config.mixin(edit.wfile.to_local_options())
             }
-            (Inner::Less(mut less), evnt) => {
-                let evnt = less.wless.on_event(evnt)?;
-                (Inner::Less(less), evnt)
+            None => self.config.clone(),
+        }
+    }
+
+    fn to_options_less(&self, config: &Config) -> Result<WindowLess> {
+        let mut lines: Vec<String> = {
+            let iter = Config::to_option_names().into_iter();
+            let iter = iter.chain(state::Config::to_option_names().into_iter());
+            let iter =
+                iter.map(|name| config.to_option(name).or(self.state_config.to_option(name)));
+            iter.filter_map(|x| x).collect()
+        };
+        lines.sort();
+
+        let scheme = self.to_color_scheme(None);
+        WindowLess::new("options", &lines.join("\n"), self.to_coord_wfile(), scheme)
+    }
+}
+
+/// Sessions, saving and restoring the editing state, and the last cursor
+/// position of files.
+impl Code {
+    // save buffers backed by files, global marks and the edit window, as
+    // the only tab, to session `file`.
+    fn mk_session(&self, edit: &Edit, file: Option<String>, force: bool) -> Result<()> {
+        let file = match file {
+            Some(file) => self.expand_file_name(edit, &file)?,
+            None => session::SESSION_FILE.to_string(),
+        };
+
+        let buffers = {
+            let mut bufs: Vec<&Buffer> = {
+                let iter = self.buffers.iter();
+                iter.filter(|buf| self.is_disk_buffer(&buf.to_id()))
+                    .collect()
+            };
+            bufs.sort_by_key(|buf| buf.to_num());
+            bufs.into_iter()
+                .map(|buf| self.to_buffer_state(buf))
+                .collect()
+        };
+        let marks = {
+            let iter = self.marks.iter().filter_map(|mrk| mrk.as_ref());
+            let iter = iter.filter(|mrk| self.is_disk_buffer(&mrk.to_buffer_id()));
+            iter.map(|mrk| session::MarkState {
+                index: mrk.to_index(),
+                file: Some(mrk.to_buffer_id()),
+                cursor: mrk.to_cursor(),
+            })
+            .collect()
+        };
+        let tab = {
+            let id = edit.wfile.to_buffer_id();
+            let altn_id = edit.wfile.to_altn_buffer_id();
+            let config = self.config.clone().mixin(edit.wfile.to_local_options());
+            session::TabState {
+                app: "code".to_string(),
+                active: true,
+                current: if_else!(self.is_disk_buffer(&id), Some(id), None),
+                alternate: altn_id.filter(|id| self.is_disk_buffer(id)),
+                options: self.to_local_args(&config::WINDOW_OPTIONS, &config),
             }
-            (Inner::None, _) => unreachable!(),
         };
 
-        let mut new_evnt: Event = Event::default();
-        for evnt in evnt.into_iter() {
-            inner = match evnt {
-                Event::Appn(event::Appn::Less(wless)) => Inner::Less(Less {
-                    edit: inner.into_edit(),
-                    wless: *wless,
-                }),
-                Event::Esc => Inner::Edit(inner.into_edit()),
-                evnt => {
-                    new_evnt.push(evnt);
-                    inner
-                }
+        let sess = Session {
+            buffers,
+            marks,
+            tabs: vec![tab],
+        };
+        sess.save(&file, force)?;
+
+        let msg = format!("session saved to {:?}", file);
+        self.notify("code", pubsub::Notify::Status(vec![msg.into()]))
+    }
+
+    fn to_buffer_state(&self, buf: &Buffer) -> session::BufferState {
+        let xy = buf.to_xy_cursor(None);
+        let config = {
+            let num = buf.to_num();
+            match self.locals.iter().find(|(n, _)| *n == num) {
+                Some((_, local)) => self.config.clone().mixin(local.clone()),
+                None => self.config.clone(),
             }
+        };
+        let marks = {
+            let iter = buf.to_marks().into_iter();
+            iter.map(|mrk| session::MarkState {
+                index: mrk.to_index(),
+                file: None,
+                cursor: mrk.to_cursor(),
+            })
+            .collect()
+        };
+
+        session::BufferState {
+            file: buf.to_id(),
+            encoding: buf.to_location().to_encoding(),
+            format: buf.format.to_string(),
+            read_only: buf.is_read_only(),
+            row: xy.row,
+            col: xy.col,
+            options: self.to_local_args(&config::BUFFER_OPTIONS, &config),
+            marks,
         }
+    }
 
-        self.inner = inner;
-        Ok(new_evnt)
+    // return options `names` that differ from global configuration, as
+    // `:setlocal` arguments.
+    fn to_local_args(&self, names: &[&str], config: &Config) -> Vec<String> {
+        let iter = names.iter().filter(|name| {
+            let global = self.config.to_option(name);
+            config.to_option(name) != global
+        });
+        iter.filter_map(|name| config.to_option(name)).collect()
     }
 
-    fn on_refresh(&mut self) -> Result<()> {
-        let mut inner = mem::replace(&mut self.inner, Inner::default());
-        match &mut inner {
-            Inner::Edit(edit) => {
-                edit.wfile.on_refresh(self)?;
-                edit.wstat.on_refresh()?;
-                // TODO: edit.tbcline.on_refresh(self)?;
-            }
-            Inner::Prompt(prompt) => {
-                prompt.edit.wfile.on_refresh(self)?;
-                match prompt.prompts.first_mut() {
-                    Some(p) => p.on_refresh()?,
-                    None => (),
-                };
-            }
-            Inner::Command(cmd) => {
-                cmd.edit.wfile.on_refresh(self)?;
-                cmd.wcmd.on_refresh(self)?;
+    // open buffers, and set global marks, from session `file`. Return the
+    // session's active tab, along with prompts for buffers that failed to
+    // open. Files that are already opened are left as is.
+    fn restore_session(
+        &mut self,
+        file: &str,
+    ) -> Result<(Option<session::TabState>, Vec<WindowPrompt>)> {
+        let sess = Session::load(file)?;
+
+        let mut prompts = vec![];
+        for bs in sess.buffers.into_iter() {
+            if self.find_buffer(&bs.file).is_some() {
+                continue;
             }
-            Inner::Less(less) => {
-                less.edit.wfile.on_refresh(self)?;
-                less.wless.on_refresh()?;
+            let files = vec![(bs.file.clone(), Some(bs.encoding.clone()))];
+            let (bufs, ps) = self.open_cmd_files(files, None);
+            prompts.extend(ps);
+            for mut buf in bufs.into_iter() {
+                match self.restore_buffer(&mut buf, &bs) {
+                    Ok(()) => self.buffers.push(buf),
+                    Err(err) => {
+                        let msg = format!("error restoring {:?} : {}", bs.file, err.to_error());
+                        prompts.push(self.to_error_prompt(msg));
+                    }
+                }
             }
-            Inner::None => unreachable!(),
         }
-        self.inner = inner;
 
+        for ms in sess.marks.into_iter() {
+            let buf = ms.file.and_then(|f| self.find_buffer(&f));
+            let mrk = match buf.as_ref().and_then(|id| self.as_buffer(id)) {
+                Some(buf) => mark::Mark::new(ms.index, buf, ms.cursor),
+                None => continue,
+            };
+            mark::set_mark(&mut self.marks, mrk);
+        }
+
+        let tab = sess
+            .tabs
+            .into_iter()
+            .find(|tab| tab.app == "code" && tab.active);
+        Ok((tab, prompts))
+    }
+
+    fn restore_buffer(&mut self, buf: &mut Buffer, bs: &session::BufferState) -> Result<()> {
+        buf.format = bs.format.parse()?;
+        if bs.read_only {
+            buf.set_read_only(true);
+        }
+        buf.set_xy_cursor(bs.row, bs.col).clear_sticky_col();
+        for ms in bs.marks.iter() {
+            let mrk = mark::Mark::new(ms.index, buf, ms.cursor);
+            buf.set_mark(mrk);
+        }
+
+        if bs.options.len() > 0 {
+            let mut local = ConfigToml::default();
+            for arg in bs.options.iter() {
+                let (name, op) = SetOpt::parse(arg, |name| self.config.is_bool_option(name))?;
+                if !config::BUFFER_OPTIONS.contains(&name.as_str()) {
+                    err_at!(Invalid, msg: format!("{} is not a buffer option", name))?
+                }
+                let mut config = self.config.clone();
+                config.set_option(&name, &op)?;
+                local.copy_option(&name, &config)?;
+            }
+            self.locals.push((buf.to_num(), local));
+            self.config_buffer(buf);
+        }
         Ok(())
     }
 
-    fn to_tab_title(&self, wth: usize) -> state::TabTitle {
-        use crate::text;
-        use std::iter::FromIterator;
+    // show session's current, and alternate, buffer in edit window, and
+    // apply window-local options.
+    fn restore_tab(&mut self, edit: &mut Edit, tab: session::TabState) -> Result<()> {
+        let ids: Vec<String> = {
+            let iter = vec![tab.alternate, tab.current].into_iter();
+            iter.filter_map(|file| self.find_buffer(&file?)).collect()
+        };
+        for id in ids.iter() {
+            self.switch_buffer(edit, id)?;
+        }
+        self.on_set_cmd(edit, Scope::Local, tab.options)?;
+        Ok(())
+    }
 
-        let edit = match &self.inner {
+    // jump to the last cursor position of buffer's file.
+    fn restore_position(&self, buf: &mut Buffer) {
+        if self.config.remember_cursor {
+            if let Some((row, col)) = self.positions.to_position(&buf.to_id()) {
+                buf.set_xy_cursor(row, col).clear_sticky_col();
+            }
+        }
+    }
+
+    // remember cursor position of buffers, `ids`, that are backed by
+    // files, and persist the positions.
+    fn save_positions(&mut self, ids: Vec<String>) {
+        if !self.config.remember_cursor {
+            return;
+        }
+        for id in ids.into_iter() {
+            let xy = match self.as_buffer(&id) {
+                Some(buf) if self.is_disk_buffer(&id) => buf.to_xy_cursor(None),
+                _ => continue,
+            };
+            self.positions.add(&id, xy.row, xy.col);
+        }
+        if let Err(err) = self.positions.save() {
+            error!("fail saving positions: {}", err);
+        }
+    }
+}
+
+/// Directory browser, directory listing shown in edit window, refer
+/// [Location::new_dir].
+impl Code {
+    fn is_dir_buffer(&self, id: &str) -> bool {
+        match self.as_buffer(id) {
+            Some(buf) => buf.to_location().to_dir().is_some(),
+            None => false,
+        }
+    }
+
+    // in directory listing, `Enter` opens the entry under cursor and `-`
+    // opens the parent directory. Return the path to open.
+    fn to_browse_path(&self, edit: &Edit, evnt: &Event) -> Option<path::PathBuf> {
+        use crate::event::Event::{Char, Enter, Noop};
+
+        match edit.wfile.to_event_prefix() {
+            Noop => (),
+            _ => return None,
+        }
+        let buf = self.as_buffer(&edit.wfile.to_buffer_id())?;
+        let loc = buf.to_location();
+        match evnt {
+            _ if buf.to_mode() != "normal" => None,
+            Enter(m) if m.is_empty() => {
+                let row = buf.to_xy_cursor(None).row;
+                loc.to_dir_entry(&buf.to_text(Some((row, row))))
+            }
+            Char('-', m) if m.is_empty() => Some(loc.to_dir()?.parent()?.to_path_buf()),
+            _ => None,
+        }
+    }
+
+    // resolve file operation into paths, relative to the directory listed
+    // in edit window.
+    fn to_dir_op(&self, edit: &Edit, op: event::FileOp) -> Result<DirOp> {
+        use crate::event::FileOp;
+        use std::fs;
+
+        let id = edit.wfile.to_buffer_id();
+        let buf = match self.as_buffer(&id) {
+            Some(buf) => buf,
+            None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+        };
+        let loc = buf.to_location();
+        let dir = match loc.to_dir() {
+            Some(dir) => dir,
+            None => err_at!(Invalid, msg: format!("{:?} is not a directory listing", id))?,
+        };
+        // parent directory is not an entry of the listing.
+        let entry = {
+            let row = buf.to_xy_cursor(None).row;
+            let entry = loc.to_dir_entry(&buf.to_text(Some((row, row))));
+            entry.filter(|entry| entry.parent() == Some(dir.as_path()))
+        };
+
+        let op = match (op, entry) {
+            (FileOp::Create(file, is_dir), _) => DirOp::Create(dir.join(file), is_dir),
+            (FileOp::Rename(file), Some(entry)) => DirOp::Rename(entry, dir.join(file)),
+            (FileOp::Delete, Some(entry)) => {
+                // symbolic links are deleted, not their target.
+                let m = err_at!(IOError, fs::symlink_metadata(&entry))?;
+                DirOp::Delete(entry, m.is_dir())
+            }
+            (_, None) => err_at!(Invalid, msg: format!("no file under cursor"))?,
+        };
+        Ok(op)
+    }
+
+    fn to_confirm_inner(&self, edit: Edit, op: DirOp) -> Inner {
+        let line = match &op {
+            DirOp::Create(dir, true) => format!("create directory {:?}", dir),
+            DirOp::Create(file, false) => format!("create file {:?}", file),
+            DirOp::Rename(from, to) => format!("rename {:?} to {:?}", from, to),
+            DirOp::Delete(dir, true) => format!("delete directory {:?} and its content", dir),
+            DirOp::Delete(file, false) => format!("delete file {:?}", file),
+        };
+        let lines = vec![line, format!("[y]es / [n]o ? ")];
+        let mut prompt = {
+            let scheme = self.to_color_scheme(None);
+            WindowPrompt::new(self.to_coord_wprompt(), lines, scheme)
+        };
+        prompt.set_options(vec![
+            Regex::new(r"(?i)y$").unwrap(),
+            Regex::new(r"(?i)n$").unwrap(),
+        ]);
+        Inner::Confirm(Confirm { edit, op, prompt })
+    }
+
+    // apply the file operation if confirmed, and list the directory again.
+    fn on_confirm_answer(&mut self, mut edit: Edit, op: DirOp, answer: &str) -> Inner {
+        use std::fs;
+
+        let res = match answer.to_lowercase().chars().last() {
+            Some('y') => match op {
+                DirOp::Create(dir, true) => err_at!(IOError, fs::create_dir(dir)),
+                DirOp::Create(file, false) => {
+                    let mut oo = fs::OpenOptions::new();
+                    err_at!(IOError, oo.write(true).create_new(true).open(file)).map(|_| ())
+                }
+                DirOp::Rename(_, to) if to.exists() => {
+                    err_at!(Invalid, msg: format!("{:?} exists", to))
+                }
+                DirOp::Rename(from, to) => err_at!(IOError, fs::rename(from, to)),
+                DirOp::Delete(dir, true) => err_at!(IOError, fs::remove_dir_all(dir)),
+                DirOp::Delete(file, false) => err_at!(IOError, fs::remove_file(file)),
+            },
+            _ => return Inner::Edit(edit),
+        };
+
+        let id = edit.wfile.to_buffer_id();
+        let res = res
+            .and_then(|_| self.reload_buffer(&id, false))
+            .and_then(|_| self.switch_buffer(&mut edit, &id));
+        match res {
+            Ok(()) => Inner::Edit(edit),
+            Err(err) => self.to_error_inner(edit, err),
+        }
+    }
+}
+
+/// Buffer commands, operating on the buffer shown in edit window.
+impl Code {
+    fn on_buffer_cmd(&mut self, edit: &mut Edit, appn: event::Appn) -> Result<()> {
+        use crate::event::{Appn, DP};
+
+        let curr_id = edit.wfile.to_buffer_id();
+        match appn {
+            Appn::Edit(None, force) => {
+                self.reload_buffer(&curr_id, force)?;
+                self.switch_buffer(edit, &curr_id)
+            }
+            Appn::Edit(Some(file), _) => {
+                let file = self.expand_file_name(edit, &file)?;
+                self.edit_file(edit, &file)
+            }
+            Appn::Buffer(None) => match edit.wfile.to_altn_buffer_id() {
+                Some(id) => self.switch_buffer(edit, &id),
+                None => err_at!(Invalid, msg: format!("no alternate buffer")),
+            },
+            Appn::Buffer(Some(name)) => {
+                let name = self.expand_file_name(edit, &name)?;
+                let id = self.match_buffer(&name)?;
+                self.switch_buffer(edit, &id)
+            }
+            Appn::BufferRotate(n, dp) => {
+                let id = self.rotate_buffer(&curr_id, n, dp);
+                self.switch_buffer(edit, &id)
+            }
+            Appn::BufferDelete(name, force) => {
+                let id = match name {
+                    Some(name) => {
+                        let name = self.expand_file_name(edit, &name)?;
+                        self.match_buffer(&name)?
+                    }
+                    None => curr_id.clone(),
+                };
+                match self.as_buffer(&id) {
+                    Some(buf) if buf.is_modified() && !force => {
+                        let msg = format!(
+                            "buffer {} {:?} is modified, add ! to override",
+                            buf.to_num(),
+                            id
+                        );
+                        err_at!(Invalid, msg: msg)?
+                    }
+                    Some(_) => (),
+                    None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+                }
+
+                if id == curr_id {
+                    let next_id = match edit.wfile.to_altn_buffer_id() {
+                        Some(altn_id) if self.as_buffer(&altn_id).is_some() => altn_id,
+                        _ => self.rotate_buffer(&id, 1, DP::Right),
+                    };
+                    if next_id == id {
+                        let mut buf = Buffer::empty();
+                        self.config_buffer(&mut buf);
+                        edit.wfile.set_buffer(self, &buf);
+                        self.add_buffer(buf);
+                    } else {
+                        self.switch_buffer(edit, &next_id)?;
+                    }
+                }
+                self.save_positions(vec![id.clone()]);
+                self.take_buffer(&id);
+                edit.wfile.forget_buffer(&id);
+                Ok(())
+            }
+            Appn::Write {
+                range,
+                file,
+                append,
+                force,
+                update,
+            } => {
+                let id = curr_id;
+                let lines = match (self.as_buffer(&id), range) {
+                    (Some(buf), Some(range)) => Some(cmd::to_line_range(buf, &range)?),
+                    _ => None,
+                };
+                match file {
+                    Some(file) => {
+                        let file = self.expand_file_name(edit, &file)?;
+                        match self.find_buffer(&file) {
+                            Some(fid) if fid == id => {
+                                self.write_buffer(&id, lines, append, force, update)
+                            }
+                            _ if self.is_disk_buffer(&id) || lines.is_some() || append => {
+                                self.write_file(&id, lines, &file, append, force)
+                            }
+                            // buffer without a file, takes the file's name.
+                            _ => self.save_as(edit, &file, force),
+                        }
+                    }
+                    None => self.write_buffer(&id, lines, append, force, update),
+                }
+            }
+            Appn::SaveAs(file, force) => {
+                let file = self.expand_file_name(edit, &file)?;
+                self.save_as(edit, &file, force)
+            }
+            Appn::WriteAll(force) => {
+                let ids: Vec<String> = {
+                    let iter = self.buffers.iter().filter(|buf| buf.is_modified());
+                    iter.map(|buf| buf.to_id()).collect()
+                };
+                let mut errs = vec![];
+                for id in ids.into_iter() {
+                    if let Err(err) = self.write_buffer(&id, None, false, force, false) {
+                        errs.push(err.to_error());
+                    }
+                }
+                match errs.len() {
+                    0 => Ok(()),
+                    _ => err_at!(IOError, msg: errs.join("; ")),
+                }
+            }
+            Appn::Read { range, file, shell } => {
+                let id = curr_id;
+                let line_idx = match (self.as_buffer(&id), range) {
+                    (Some(buf), _) if buf.is_read_only() => {
+                        err_at!(Invalid, msg: format!("{:?} is read-only", id))?
+                    }
+                    (Some(_), Some(range)) if range.trim() == "0" => None,
+                    (Some(buf), Some(range)) => Some(cmd::to_line_range(buf, &range)?.1),
+                    (Some(buf), None) => Some(buf.to_xy_cursor(None).row),
+                    (None, _) => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+                };
+                let text = match (file, shell) {
+                    (Some(cmd), true) => {
+                        let cmd = self.expand_file_name(edit, &cmd)?;
+                        self.read_shell(&cmd)?
+                    }
+                    (Some(file), false) => {
+                        let file = self.expand_file_name(edit, &file)?;
+                        self.read_file(&file)?
+                    }
+                    (None, _) => match self.as_buffer(&id).map(|b| b.to_location()) {
+                        Some(loc @ Location::Disk { .. }) => loc.read()?,
+                        _ => {
+                            let msg = format!("no file name for buffer {:?}", id);
+                            err_at!(Invalid, msg: msg)?
+                        }
+                    },
+                };
+                let evnt = match self.as_mut_buffer(&id) {
+                    Some(buf) => buf.insert_lines(line_idx, &text)?.1,
+                    None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+                };
+                // update the syntax tree, like any other edit on buffer.
+                edit.wfile.on_event(self, evnt)?;
+                Ok(())
+            }
+            Appn::Substitute { range, args } => {
+                let id = curr_id;
+                let evnt = match self.as_mut_buffer(&id) {
+                    Some(buf) if buf.is_read_only() => {
+                        err_at!(Invalid, msg: format!("{:?} is read-only", id))?
+                    }
+                    Some(buf) => {
+                        let subst = {
+                            let last = buf.to_last_search().map(|s| s.patt);
+                            Substitute::parse(&args, last.as_deref())?
+                        };
+                        let (from, till) = match range {
+                            Some(range) => cmd::to_line_range(buf, &range)?,
+                            None => {
+                                let row = buf.to_xy_cursor(None).row;
+                                (row, row)
+                            }
+                        };
+                        match buf.substitute(from, till, &subst)? {
+                            (0, _) if subst.no_error => Event::Noop,
+                            (0, _) => {
+                                let msg = format!("pattern not found {}", subst.search.patt);
+                                err_at!(Invalid, msg: msg)?
+                            }
+                            (_, evnt) => evnt,
+                        }
+                    }
+                    None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+                };
+                // update the syntax tree, like any other edit on buffer.
+                edit.wfile.on_event(self, evnt)?;
+                Ok(())
+            }
+            appn => err_at!(Fatal, msg: format!("unexpected {}", appn)),
+        }
+    }
+
+    // edit `file`, or directory, switching to its buffer if it is already
+    // opened. Directory listings are refreshed when switched to.
+    fn edit_file(&mut self, edit: &mut Edit, file: &str) -> Result<()> {
+        let id = match self.find_buffer(file) {
+            Some(id) if self.is_dir_buffer(&id) => {
+                self.reload_buffer(&id, false)?;
+                id
+            }
+            Some(id) => id,
+            None => {
+                let f: ffi::OsString = file.to_string().into();
+                let buf = self.open_disk_buffer(Location::new_disk(&f, None)?)?;
+                let id = buf.to_id();
+                self.add_buffer(buf);
+                id
+            }
+        };
+        self.switch_buffer(edit, &id)
+    }
+
+    // read `file`, its encoding is detected from its content.
+    fn read_file(&self, file: &str) -> Result<String> {
+        let f: ffi::OsString = file.to_string().into();
+        Location::new_disk(&f, None)?.read()
+    }
+
+    // run `cmd` using the shell and return its standard output.
+    fn read_shell(&self, cmd: &str) -> Result<String> {
+        use std::process;
+
+        let shell = std::env::var("SHELL").unwrap_or("sh".to_string());
+        let out = {
+            let mut sh = process::Command::new(shell);
+            err_at!(IOError, sh.arg("-c").arg(cmd).output())?
+        };
+        if out.status.success() || out.stdout.len() > 0 {
+            Ok(text::Encoding::from_reader(out.stdout.as_slice(), "utf-8")?.into())
+        } else {
+            let msg = String::from_utf8_lossy(&out.stderr).trim().to_string();
+            err_at!(IOError, msg: format!("{:?} {}, {}", cmd, out.status, msg))
+        }
+    }
+
+    fn is_disk_buffer(&self, id: &str) -> bool {
+        match self.as_buffer(id).map(|buf| buf.to_location()) {
+            Some(Location::Disk { .. }) => true,
+            _ => false,
+        }
+    }
+
+    // write buffer, or lines from buffer, to its own file. Buffer is
+    // marked as un-modified only when its entire content is written.
+    fn write_buffer(
+        &mut self,
+        id: &str,
+        lines: Option<(usize, usize)>,
+        append: bool,
+        force: bool,
+        update: bool,
+    ) -> Result<()> {
+        let buf = match self.as_buffer(id) {
+            Some(buf) if update && !buf.is_modified() => return Ok(()),
+            Some(buf) if !self.is_disk_buffer(id) => {
+                let msg = format!("no file name for buffer {}", buf.to_num());
+                err_at!(Invalid, msg: msg)?
+            }
+            Some(buf) if buf.is_read_only() && !force => {
+                let msg = format!("{:?} is read-only, add ! to override", id);
+                err_at!(Invalid, msg: msg)?
+            }
+            Some(buf) if buf.to_location().is_changed() && !force => {
+                let msg = format!(
+                    "{:?} changed on disk since last read, add ! to override",
+                    id
+                );
+                err_at!(Invalid, msg: msg)?
+            }
+            Some(buf) => buf,
+            None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+        };
+
+        let text = buf.to_text(lines);
+        buf.to_location().save(&text, append)?;
+        self.notify_written(id, &text)?;
+
+        self.as_mut_buffer(id).map(|buf| {
+            let mut loc = buf.to_location();
+            buf.set_location(loc.refresh_stat().clone());
+            if lines.is_none() && !append {
+                buf.clear_modified();
+            }
+        });
+        Ok(())
+    }
+
+    // write buffer, or lines from buffer, to another file.
+    fn write_file(
+        &mut self,
+        id: &str,
+        lines: Option<(usize, usize)>,
+        file: &str,
+        append: bool,
+        force: bool,
+    ) -> Result<()> {
+        let loc = self.to_file_location(id, file, append || force)?;
+        let text = match self.as_buffer(id) {
+            Some(buf) => buf.to_text(lines),
+            None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+        };
+        loc.save(&text, append)?;
+        self.notify_written(file, &text)
+    }
+
+    // save buffer as `file`, which becomes the buffer's file.
+    fn save_as(&mut self, edit: &mut Edit, file: &str, force: bool) -> Result<()> {
+        let id = edit.wfile.to_buffer_id();
+        let f: ffi::OsString = file.to_string().into();
+
+        let loc = self.to_file_location(&id, file, force)?;
+        let mut buf = match self.take_buffer(&id) {
+            Some(buf) => buf,
+            None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+        };
+        let text = buf.to_text(None);
+        let res = loc.save(&text, false).and_then(|_| {
+            let loc = Location::new_disk(&f, Some(&loc.to_encoding()))?;
+            buf.set_location(loc).clear_modified();
+            Ok(())
+        });
+        let new_id = buf.to_id();
+        self.add_buffer(buf);
+        res?;
+
+        self.notify_written(&new_id, &text)?;
+        self.switch_buffer(edit, &new_id)?;
+        edit.wfile.forget_buffer(&id);
+        Ok(())
+    }
+
+    // disk location for writing buffer `id` to `file`, existing files are
+    // overwritten only when `force` is true.
+    fn to_file_location(&self, id: &str, file: &str, force: bool) -> Result<Location> {
+        let enc = match self.as_buffer(id).map(|buf| buf.to_location()) {
+            Some(loc) => loc.to_encoding(),
+            None => "utf-8".to_string(),
+        };
+        let f: ffi::OsString = file.to_string().into();
+        if !force && std::path::Path::new(&f).exists() {
+            let msg = format!("{:?} exists, add ! to override", file);
+            err_at!(Invalid, msg: msg)?
+        }
+        Location::new_disk_file(&f, Some(&enc))
+    }
+
+    fn notify_written(&self, name: &str, text: &str) -> Result<()> {
+        let n_lines = text.lines().count();
+        let msg = format!("{:?} {}L, {}B written", name, n_lines, text.len());
+        self.notify("code", pubsub::Notify::Status(vec![msg.into()]))
+    }
+
+    // on focus, offer to recover from a swap file left behind for the
+    // buffer in edit window, else check whether its file changed on disk.
+    fn on_focus(&mut self, edit: Edit) -> Inner {
+        let id = edit.wfile.to_buffer_id();
+        match self.as_buffer(&id).map(|buf| buf.to_location()) {
+            Some(Location::Disk { path_file, .. })
+                if self.config.swap_file && swap::is_foreign(&path_file) =>
+            {
+                self.to_recover_inner(edit, &path_file)
+            }
+            _ => self.check_changed(edit),
+        }
+    }
+
+    fn to_recover_inner(&self, edit: Edit, path_file: &ffi::OsStr) -> Inner {
+        let id = edit.wfile.to_buffer_id();
+        let swap_file = swap::to_swap_file(path_file).unwrap_or_default();
+
+        let mut lines = vec![format!("found swap file {:?}", swap_file)];
+        let journal = match swap::Journal::load(path_file) {
+            Ok(journal) => {
+                let running = if_else!(journal.is_running(), ", still running", "");
+                lines.push(format!("written by process {}{}", journal.pid, running));
+                lines.push(format!("[r]ecover / [d]iff / [D]elete ? "));
+                Some(journal)
+            }
+            Err(err) => {
+                lines.push(format!("unreadable swap file, {}", err.to_error()));
+                lines.push(format!("[D]elete ? "));
+                None
+            }
+        };
+        let mut prompt = {
+            let scheme = self.to_color_scheme(None);
+            WindowPrompt::new(self.to_coord_wprompt(), lines, scheme)
+        };
+        prompt.set_options(match journal {
+            Some(_) => vec![
+                Regex::new(r"r$").unwrap(),
+                Regex::new(r"d$").unwrap(),
+                Regex::new(r"D$").unwrap(),
+            ],
+            None => vec![Regex::new(r"D$").unwrap()],
+        });
+        Inner::Recover(Recover {
+            edit,
+            id,
+            journal,
+            prompt,
+        })
+    }
+
+    // replace content of buffer `id`, in edit window, with journal's text.
+    fn recover_journal(&mut self, edit: &mut Edit, id: &str, journal: swap::Journal) -> Result<()> {
+        let evnt = match self.as_mut_buffer(id) {
+            Some(buf) => {
+                let (cursor, format) = (journal.cursor, journal.format);
+                let evnt = buf.set_rope(journal.rope)?;
+                let cursor = cmp::min(cursor, buf.to_rope().len_chars());
+                buf.set_format(format).set_cursor(cursor);
+                evnt
+            }
+            None => return Ok(()),
+        };
+        // update the syntax tree and jump-list, like any other edit.
+        edit.wfile.on_event(self, evnt)?;
+        Ok(())
+    }
+
+    // `recover` replaces buffer's content with the journal's text, which
+    // can be undone, `diff` shows the journal's changes against the file
+    // and `Delete` removes the swap file.
+    fn on_recover_answer(
+        &mut self,
+        mut edit: Edit,
+        id: &str,
+        journal: Option<swap::Journal>,
+        answer: &str,
+    ) -> Inner {
+        let path_file = match self.as_buffer(id).map(|buf| buf.to_location()) {
+            Some(Location::Disk { path_file, .. }) => path_file,
+            _ => return Inner::Edit(edit),
+        };
+        let res = match (answer.chars().last(), journal) {
+            (Some('r'), Some(journal)) => {
+                let res = self.recover_journal(&mut edit, id, journal);
+                res.map(|_| None)
+            }
+            (Some('d'), Some(journal)) => {
+                let loc = self.as_buffer(id).map(|buf| buf.to_location());
+                let text = journal.rope.to_string();
+                match loc {
+                    Some(loc) => self.to_diff_less(&loc, &text, "swap").map(Some),
+                    None => Ok(None),
+                }
+            }
+            (_, _) => swap::delete(&path_file).map(|_| None),
+        };
+        // recovered buffer owns the swap file hereafter.
+        self.stage_journals();
+
+        match res.and_then(|wless| self.switch_buffer(&mut edit, id).map(|_| wless)) {
+            Ok(Some(wless)) => Inner::Less(Less { edit, wless }),
+            Ok(None) => Inner::Edit(edit),
+            Err(err) => self.to_error_inner(edit, err),
+        }
+    }
+
+    // stage journals for modified buffers, and write them to swap files
+    // once every `update_count` events.
+    fn stage_journals(&mut self) {
+        if !self.config.swap_file {
+            return;
+        }
+
+        let journals: Vec<swap::Journal> = {
+            let iter = self.buffers.iter().filter(|buf| buf.is_modified());
+            iter.filter_map(swap::Journal::from_buffer).collect()
+        };
+        let mut res = swap::stage(journals);
+
+        self.n_events += 1;
+        if self.n_events >= self.config.update_count {
+            self.n_events = 0;
+            res = res.and_then(|_| swap::flush());
+        }
+        if let Err(err) = res {
+            error!("fail writing swap files: {}", err);
+        }
+    }
+
+    // check whether file of the buffer in edit window was changed on disk,
+    // un-modified buffers are silently reloaded when `autoread` is set,
+    // otherwise prompt the user. Terminal focus events are not reported by
+    // crossterm 0.17, hence the check is done only when the edit window
+    // regains focus within the editor, say after a prompt or on switching
+    // buffers, and before writing; not when the terminal regains focus.
+    fn check_changed(&mut self, mut edit: Edit) -> Inner {
+        let id = edit.wfile.to_buffer_id();
+        match self.as_buffer(&id) {
+            Some(buf) if !buf.to_location().is_changed() => Inner::Edit(edit),
+            Some(buf) if self.config.autoread && !buf.is_modified() => {
+                match self.reload_edit_buffer(&mut edit, &id) {
+                    Ok(()) => {
+                        let msg = format!("{:?} reloaded, changed on disk", id);
+                        let notf = pubsub::Notify::Status(vec![msg.into()]);
+                        if let Err(err) = self.notify("code", notf) {
+                            debug!("notify autoread: {}", err);
+                        }
+                        Inner::Edit(edit)
+                    }
+                    Err(err) => self.to_error_inner(edit, err),
+                }
+            }
+            Some(_) => self.to_changed_inner(edit),
+            None => Inner::Edit(edit),
+        }
+    }
+
+    // reload buffer `id`, in edit window, from its file. Its swap journal
+    // is dropped, since the buffer is no more modified.
+    fn reload_edit_buffer(&mut self, edit: &mut Edit, id: &str) -> Result<()> {
+        let (evnt, loc) = match self.as_mut_buffer(id) {
+            Some(buf) => (buf.reload()?, buf.to_location()),
+            None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+        };
+        // update the syntax tree and jump-list, like any other edit.
+        edit.wfile.on_event(self, evnt)?;
+        match loc {
+            Location::Disk { path_file, .. } if !swap::is_foreign(&path_file) => {
+                swap::delete(&path_file)?
+            }
+            _ => (),
+        }
+        self.switch_buffer(edit, id)
+    }
+
+    // writing a buffer over its file, after the file was changed on disk,
+    // needs confirmation.
+    fn is_write_changed(&self, edit: &Edit, appn: &event::Appn) -> bool {
+        use crate::event::Appn;
+
+        let id = edit.wfile.to_buffer_id();
+        match (appn, self.as_buffer(&id)) {
+            (
+                Appn::Write {
+                    file: None,
+                    append: false,
+                    force: false,
+                    update,
+                    ..
+                },
+                Some(buf),
+            ) => (!update || buf.is_modified()) && buf.to_location().is_changed(),
+            _ => false,
+        }
+    }
+
+    fn to_changed_inner(&self, edit: Edit) -> Inner {
+        let id = edit.wfile.to_buffer_id();
+        let lines = vec![
+            format!("{:?} changed on disk since last read", id),
+            format!("[r]eload / [k]eep / [d]iff ? "),
+        ];
+        let mut prompt = {
+            let scheme = self.to_color_scheme(None);
+            WindowPrompt::new(self.to_coord_wprompt(), lines, scheme)
+        };
+        prompt.set_options(vec![
+            Regex::new(r"(?i)r$").unwrap(),
+            Regex::new(r"(?i)k$").unwrap(),
+            Regex::new(r"(?i)d$").unwrap(),
+        ]);
+        Inner::Changed(Changed { edit, id, prompt })
+    }
+
+    // `reload` discards buffer's modifications, `keep` retains buffer's
+    // content and `diff` shows the buffer's changes against its file.
+    fn on_changed_answer(&mut self, mut edit: Edit, id: &str, answer: &str) -> Inner {
+        let res = match answer.to_lowercase().chars().last() {
+            Some('r') => self.reload_edit_buffer(&mut edit, id).map(|_| None),
+            Some('k') => {
+                if let Some(buf) = self.as_mut_buffer(id) {
+                    let mut loc = buf.to_location();
+                    buf.set_location(loc.refresh_stat().clone());
+                }
+                Ok(None)
+            }
+            _ => match self.as_buffer(id) {
+                Some(buf) => {
+                    let loc = buf.to_location();
+                    self.to_diff_less(&loc, &buf.to_text(None), "buffer")
+                        .map(Some)
+                }
+                None => err_at!(Invalid, msg: format!("no buffer {:?}", id)),
+            },
+        };
+        match res.and_then(|wless| self.switch_buffer(&mut edit, id).map(|_| wless)) {
+            Ok(Some(wless)) => Inner::Less(Less { edit, wless }),
+            Ok(None) => Inner::Edit(edit),
+            Err(err) => self.to_error_inner(edit, err),
+        }
+    }
+
+    // unified diff of file's content on disk against `text`, using the
+    // `diff` command, `label` names the text. File's content is decoded,
+    // and de-compressed, the same way as it is read into the buffer, and
+    // staged in a temporary file for `diff`.
+    fn to_diff_less(&self, loc: &Location, text: &str, label: &str) -> Result<WindowLess> {
+        use std::fs;
+
+        let file = loc.to_string();
+        let disk_file = {
+            let name = format!("ted-diff-{}", process::id());
+            let disk_file = std::env::temp_dir().join(name);
+            err_at!(IOError, fs::write(&disk_file, loc.read()?))?;
+            disk_file
+        };
+
+        let labels = [format!("{} (disk)", file), format!("{} ({})", file, label)];
+        let res = diff_u(&disk_file, text, &labels);
+        if let Err(err) = fs::remove_file(&disk_file) {
+            debug!("remove {:?}: {}", disk_file, err);
+        }
+        let out = res?;
+        let content = match out.status.code() {
+            Some(0) => format!("no difference between {:?} and its {}", file, label),
+            Some(1) => String::from_utf8_lossy(&out.stdout).to_string(),
+            _ => {
+                let msg = String::from_utf8_lossy(&out.stderr).trim().to_string();
+                err_at!(IOError, msg: format!("diff {:?} {}, {}", file, out.status, msg))?
+            }
+        };
+
+        let scheme = self.to_color_scheme(None);
+        WindowLess::new("diff", &content, self.to_coord_wfile(), scheme)
+    }
+
+    fn switch_buffer(&self, edit: &mut Edit, id: &str) -> Result<()> {
+        match self.as_buffer(id) {
+            Some(buf) => {
+                edit.wfile.set_buffer(self, buf);
+                Ok(())
+            }
+            None => err_at!(Invalid, msg: format!("no buffer {:?}", id)),
+        }
+    }
+
+    // re-load buffer's content from disk.
+    fn reload_buffer(&mut self, id: &str, force: bool) -> Result<()> {
+        let buf = match self.as_buffer(id) {
+            Some(buf) if buf.is_modified() && !force => {
+                let msg = format!("buffer {:?} is modified, add ! to override", id);
+                err_at!(Invalid, msg: msg)?
+            }
+            Some(buf) => buf,
+            None => err_at!(Invalid, msg: format!("no buffer {:?}", id))?,
+        };
+        let loc = buf.to_location();
+        match (loc.to_dir(), loc) {
+            (None, loc @ Location::Disk { .. }) => {
+                let num = buf.to_num();
+                let mut buf = self.to_disk_buffer(loc)?;
+                buf.num = num;
+                self.take_buffer(id);
+                self.add_buffer(buf);
+            }
+            // directory is listed again, retaining the cursor's line.
+            (Some(dir), _) => {
+                let (num, row) = (buf.to_num(), buf.to_xy_cursor(None).row);
+                let mut buf = self.to_disk_buffer(Location::new_dir(dir.as_os_str())?)?;
+                buf.num = num;
+                buf.set_xy_cursor(row, 0);
+                self.take_buffer(id);
+                self.add_buffer(buf);
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    // return the id of n-th next/previous buffer, in buffer number order.
+    fn rotate_buffer(&self, id: &str, n: usize, dp: event::DP) -> String {
+        let mut bufs: Vec<&Buffer> = self.buffers.iter().collect();
+        bufs.sort_by_key(|buf| buf.to_num());
+
+        let m = bufs.len();
+        match bufs.iter().position(|buf| buf.to_id() == id) {
+            Some(off) if dp == event::DP::Left => bufs[(off + m - (n % m)) % m].to_id(),
+            Some(off) => bufs[(off + n) % m].to_id(),
+            None => id.to_string(),
+        }
+    }
+
+    // find buffer whose name is same as `name`.
+    fn find_buffer(&self, name: &str) -> Option<String> {
+        use std::fs;
+
+        let file = fs::canonicalize(name).ok();
+        for buf in self.buffers.iter() {
+            let id = buf.to_id();
+            if id == name {
+                return Some(id);
+            }
+            let ok = match &file {
+                Some(file) => {
+                    let dir = buf.to_location().to_dir();
+                    fs::canonicalize(&id).ok().as_ref() == Some(file) || dir.as_ref() == Some(file)
+                }
+                None => false,
+            };
+            if ok {
+                return Some(id);
+            }
+        }
+        None
+    }
+
+    // match buffer by its number, by its name, or by a sub-string of its
+    // name that is unique across all buffers.
+    fn match_buffer(&self, name: &str) -> Result<String> {
+        if let Ok(num) = name.parse::<usize>() {
+            let mut iter = self.buffers.iter().filter(|buf| buf.to_num() == num);
+            return match iter.next() {
+                Some(buf) => Ok(buf.to_id()),
+                None => err_at!(Invalid, msg: format!("no buffer {}", num)),
+            };
+        }
+        if let Some(id) = self.find_buffer(name) {
+            return Ok(id);
+        }
+
+        let ids: Vec<String> = {
+            let iter = self.buffers.iter().map(|buf| buf.to_id());
+            iter.filter(|id| id.contains(name)).collect()
+        };
+        match ids.len() {
+            0 => err_at!(Invalid, msg: format!("no matching buffer for {:?}", name)),
+            1 => Ok(ids[0].clone()),
+            _ => err_at!(Invalid, msg: format!("more than one match for {:?}", name)),
+        }
+    }
+
+    // expand `%` to current buffer's name, `#` to alternate buffer's name
+    // and `#n` to n-th buffer's name. `\%` and `\#` are kept literally.
+    fn expand_file_name(&self, edit: &Edit, text: &str) -> Result<String> {
+        let mut out = String::default();
+        let mut chars = text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match (ch, chars.peek()) {
+                ('\\', Some('%')) | ('\\', Some('#')) => out.push(chars.next().unwrap()),
+                ('%', _) => out.push_str(&edit.wfile.to_buffer_id()),
+                ('#', _) => {
+                    let mut num = String::default();
+                    while let Some(ch) = chars.peek().cloned().filter(char::is_ascii_digit) {
+                        num.push(ch);
+                        chars.next();
+                    }
+                    match num.as_str() {
+                        "" => match edit.wfile.to_altn_buffer_id() {
+                            Some(id) => out.push_str(&id),
+                            None => {
+                                let msg = format!("no alternate file name for #");
+                                err_at!(Invalid, msg: msg)?
+                            }
+                        },
+                        num => out.push_str(&self.match_buffer(num)?),
+                    }
+                }
+                (ch, _) => out.push(ch),
+            }
+        }
+        Ok(out)
+    }
+
+    // list buffers, ordered by buffer number, marking the current buffer
+    // with `%`, alternate buffer with `#`, read-only buffer with `=` and
+    // modified buffer with `+`.
+    fn to_buffers_less(&self, edit: &Edit) -> Result<WindowLess> {
+        let curr_id = edit.wfile.to_buffer_id();
+        let altn_id = edit.wfile.to_altn_buffer_id();
+
+        let mut bufs: Vec<&Buffer> = self.buffers.iter().collect();
+        bufs.sort_by_key(|buf| buf.to_num());
+
+        let mut lines = vec![];
+        for buf in bufs.into_iter() {
+            let id = buf.to_id();
+            let flag = if id == curr_id {
+                '%'
+            } else if Some(&id) == altn_id.as_ref() {
+                '#'
+            } else {
+                ' '
+            };
+            let stat = match buf {
+                buf if buf.is_read_only() => '=',
+                buf if buf.is_modified() => '+',
+                _ => ' ',
+            };
+            lines.push(format!("{:>3} {}{} {:?}", buf.to_num(), flag, stat, id));
+        }
+        let content = lines.join("\n");
+
+        let scheme = self.to_color_scheme(None);
+        WindowLess::new("buffers", &content, self.to_coord_wfile(), scheme)
+    }
+
+    fn to_error_prompt(&self, msg: String) -> WindowPrompt {
+        self.to_prompt(vec![msg])
+    }
+
+    fn to_error_inner(&self, edit: Edit, err: Error) -> Inner {
+        let prompt = self.to_error_prompt(err.to_error());
+        Inner::Prompt(Prompt {
+            edit,
+            prompts: vec![prompt],
+        })
+    }
+
+    // list of modified buffers, refusing to quit the application.
+    fn to_quit_prompt(&self) -> Option<WindowPrompt> {
+        let mut bufs: Vec<&Buffer> = {
+            let iter = self.buffers.iter().filter(|b| b.is_modified());
+            iter.collect()
+        };
+        bufs.sort_by_key(|b| b.to_num());
+
+        match bufs.len() {
+            0 => None,
+            _ => {
+                let mut lines = vec![format!("no write since last change, add ! to override")];
+                for buf in bufs.into_iter() {
+                    lines.push(format!("{:>3} {:?}", buf.to_num(), buf.to_id()));
+                }
+                Some(self.to_prompt(lines))
+            }
+        }
+    }
+
+    fn to_prompt(&self, mut lines: Vec<String>) -> WindowPrompt {
+        lines.push(format!("-press any key to continue-"));
+        let scheme = self.to_color_scheme(None);
+        WindowPrompt::new(self.to_coord_wprompt(), lines, scheme)
+    }
+}
+
+/// User mappings.
+impl Code {
+    // mode for user mappings, None when mappings does not apply, like
+    // when keymap is waiting for an argument.
+    fn to_map_mode(&self) -> Option<MapMode> {
+        match &self.inner {
+            Inner::Edit(edit) => {
+                let buf = self.as_buffer(&edit.wfile.to_buffer_id())?;
+                match (buf.to_mode(), edit.wfile.to_event_prefix()) {
+                    ("normal", Event::Noop) | ("normal", Event::N(_)) => Some(MapMode::Normal),
+                    ("insert", Event::Noop) => Some(MapMode::Insert),
+                    _ => None,
+                }
+            }
+            Inner::Command(cmd) => match cmd.wcmd.to_event_prefix() {
+                Event::Noop => Some(MapMode::Command),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn on_map_cmd(&mut self, appn: event::Appn) -> Result<Option<WindowLess>> {
+        use crate::event::Appn;
+
+        let leader = self.config.map_leader;
+        match appn {
+            Appn::Map {
+                mode,
+                noremap,
+                lhs: Some(lhs),
+                rhs: Some(rhs),
+            } => {
+                let mapping = keymap::Mapping::new(mode, &lhs, &rhs, noremap, leader)?;
+                self.keymaps.add(mapping);
+                Ok(None)
+            }
+            Appn::Map { mode, lhs, .. } => {
+                let prefix = keymap::parse_keys(&lhs.unwrap_or("".to_string()), leader)?;
+                let lines: Vec<String> = {
+                    let iter = self.keymaps.to_mappings(mode, &prefix).into_iter();
+                    iter.map(|m| m.to_string()).collect()
+                };
+                if lines.len() == 0 {
+                    err_at!(Invalid, msg: format!("no mapping found"))?
+                }
+                let scheme = self.to_color_scheme(None);
+                let coord = self.to_coord_wfile();
+                Ok(Some(WindowLess::new(
+                    "mappings",
+                    &lines.join("\n"),
+                    coord,
+                    scheme,
+                )?))
+            }
+            Appn::Unmap(mode, lhs) => {
+                let lhs = keymap::parse_keys(&lhs, leader)?;
+                self.keymaps.remove(mode, &lhs)?;
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Code {
+    // handle event, after resolving user mappings.
+    fn on_unmapped_event(&mut self, evnt: Event) -> Result<Event> {
+        let timeout = match evnt {
+            Event::Timeout if !self.keymaps.is_pending() => {
+                return self.on_mapped_event(evnt);
+            }
+            Event::Timeout => true,
+            evnt if evnt.is_key() => {
+                self.keymaps.push(evnt);
+                false
+            }
+            evnt => return self.on_mapped_event(evnt),
+        };
+
+        // resolve user mappings, one key at a time, so that keys are
+        // mapped for the mode they are applied in.
+        let mut new_evnt = Event::default();
+        loop {
+            let mode = self.to_map_mode();
+            let key = match self.keymaps.fold(mode, timeout) {
+                Ok(Some(key)) => key,
+                Ok(None) => break,
+                Err(err) => {
+                    let inner = mem::replace(&mut self.inner, Inner::default());
+                    self.inner = self.to_error_inner(inner.into_edit(), err);
+                    break;
+                }
+            };
+            match self.on_mapped_event(key) {
+                Ok(evnt) => new_evnt.push(evnt),
+                Err(err) => {
+                    self.keymaps.clear();
+                    return Err(err);
+                }
+            }
+        }
+        Ok(new_evnt)
+    }
+
+    // handle event, keys are already resolved for user mappings.
+    // handle event on the command-line, errors close the command-line and
+    // are reported on the prompt.
+    fn on_command_event(&mut self, mut cmd: Command, evnt: Event) -> (Inner, Event) {
+        let evnts = match cmd.wcmd.on_event(self, evnt) {
+            Ok(evnt) => evnt,
+            Err(err) => return (self.to_error_inner(cmd.edit, err), Event::Noop),
+        };
+        let mut new_evnt = Event::default();
+        // search-line resolves into motion events for the edit window.
+        for evnt in evnts.into_iter() {
+            match evnt {
+                evnt @ Event::Mt(_) => match cmd.edit.wfile.on_event(self, evnt) {
+                    Ok(evnt) => new_evnt.push(evnt),
+                    Err(err) => return (self.to_error_inner(cmd.edit, err), Event::Noop),
+                },
+                evnt => new_evnt.push(evnt),
+            }
+        }
+        (Inner::Command(cmd), new_evnt)
+    }
+
+    fn on_mapped_event(&mut self, evnt: Event) -> Result<Event> {
+        use crate::event::Mto;
+
+        let focus = match &self.inner {
+            Inner::Edit(edit) => Some(edit.wfile.to_buffer_id()),
+            _ => None,
+        };
+        let inner = mem::replace(&mut self.inner, Inner::default());
+        let (mut inner, evnt) = match (inner, evnt.clone()) {
+            (Inner::Edit(edit), Event::Mr(mrk)) => {
+                debug!("code event {}", evnt);
+                mark::set_mark(&mut self.marks, mrk);
+                (Inner::Edit(edit), Event::Noop)
+            }
+            (Inner::Edit(edit), Event::Mt(Mto::Jump('`', _mindex))) => {
+                debug!("code event {}", evnt);
+                // TODO: use `:buffer` command to load the buffer.
+                // TODO: set the cursor, clear-sticky-col
+                (Inner::Edit(edit), Event::Noop)
+            }
+            (Inner::Edit(edit), Event::Mt(Mto::Jump('\'', _mindex))) => {
+                debug!("code event {}", evnt);
+                // TODO: use `:buffer` command to load the buffer.
+                // TODO: set the cursor, clear-sticky-col
+                // TODO: mto_line_home
+                (Inner::Edit(edit), Event::Noop)
+            }
+            (Inner::Edit(edit), Event::Mt(Mto::Jump(typ, mindex))) => {
+                debug!("code event {}", evnt);
+                (Inner::Edit(edit), Event::Mt(Mto::Jump(typ, mindex)))
+            }
+            (Inner::Edit(edit), Event::Char(ch, m))
+                if m.is_empty() && self.is_cmd_leader(&edit, ch) =>
+            {
+                debug!("code event {}", evnt);
+                let prefix = edit.wfile.to_event_prefix();
+                let buf_id = edit.wfile.to_buffer_id();
+                let altn_id = edit.wfile.to_altn_buffer_id();
+                match WindowCmd::new(self.to_coord_wcmd(), self, ch, buf_id, altn_id) {
+                    Ok(wcmd) => {
+                        let val = Command { edit, wcmd };
+                        self.on_command_event(val, prefix)
+                    }
+                    Err(err) => (self.to_error_inner(edit, err), Event::Noop),
+                }
+            }
+            (Inner::Edit(mut edit), evnt) => match self.to_browse_path(&edit, &evnt) {
+                Some(file) => {
+                    debug!("code event {}", evnt);
+                    let file = file.to_string_lossy().to_string();
+                    match self.edit_file(&mut edit, &file) {
+                        Ok(()) => (Inner::Edit(edit), Event::Noop),
+                        Err(err) => (self.to_error_inner(edit, err), Event::Noop),
+                    }
+                }
+                None => {
+                    let evnt = edit.wfile.on_event(self, evnt)?;
+                    (Inner::Edit(edit), evnt)
+                }
+            },
+            (Inner::Recover(mut val), evnt) => {
+                let evnt = val.prompt.on_event(evnt)?;
+                match val.prompt.prompt_match() {
+                    Some(answer) => {
+                        let Recover {
+                            edit, id, journal, ..
+                        } = val;
+                        (self.on_recover_answer(edit, &id, journal, &answer), evnt)
+                    }
+                    None => (Inner::Recover(val), evnt),
+                }
+            }
+            (Inner::Confirm(mut val), evnt) => {
+                let evnt = val.prompt.on_event(evnt)?;
+                match val.prompt.prompt_match() {
+                    Some(answer) => {
+                        let inner = self.on_confirm_answer(val.edit, val.op, &answer);
+                        (inner, evnt)
+                    }
+                    None => (Inner::Confirm(val), evnt),
+                }
+            }
+            (Inner::Changed(mut val), evnt) => {
+                let evnt = val.prompt.on_event(evnt)?;
+                match val.prompt.prompt_match() {
+                    Some(answer) => {
+                        let inner = self.on_changed_answer(val.edit, &val.id, &answer);
+                        (inner, evnt)
+                    }
+                    None => (Inner::Changed(val), evnt),
+                }
+            }
+            (Inner::Prompt(mut prompt), evnt) => {
+                let evnt = prompt.prompts[0].on_event(evnt)?;
+                if let Some(_) = prompt.prompts[0].prompt_match() {
+                    prompt.prompts.remove(0);
+                }
+                match prompt.prompts.len() {
+                    0 => (Inner::Edit(prompt.edit), evnt),
+                    _ => (Inner::Prompt(prompt), evnt),
+                }
+            }
+            (Inner::Command(cmd), evnt) => self.on_command_event(cmd, evnt),
+            (Inner::Less(mut less), evnt) => {
+                let evnt = less.wless.on_event(evnt)?;
+                (Inner::Less(less), evnt)
+            }
+            (Inner::Hex(mut hex), evnt) => {
+                let evnt = hex.whex.on_event(evnt)?;
+                (Inner::Hex(hex), evnt)
+            }
+            (Inner::Finder(mut val), evnt) => match val.wfind.on_event(evnt)? {
+                Event::Enter(_) => match val.wfind.to_choice() {
+                    Some(file) => {
+                        let (mut edit, file) = (val.edit, file.to_string_lossy().to_string());
+                        edit.wfile.forget_screen();
+                        match self.edit_file(&mut edit, &file) {
+                            Ok(()) => (Inner::Edit(edit), Event::Noop),
+                            Err(err) => (self.to_error_inner(edit, err), Event::Noop),
+                        }
+                    }
+                    None => (Inner::Finder(val), Event::Noop),
+                },
+                evnt => (Inner::Finder(val), evnt),
+            },
+            (Inner::None, _) => unreachable!(),
+        };
+
+        let mut new_evnt: Event = Event::default();
+        for evnt in evnt.into_iter() {
+            inner = match evnt {
+                Event::Appn(event::Appn::Less(wless)) => Inner::Less(Less {
+                    edit: inner.into_edit(),
+                    wless: *wless,
+                }),
+                Event::Appn(event::Appn::Hex(whex)) => Inner::Hex(Hex {
+                    edit: inner.into_edit(),
+                    whex: *whex,
+                }),
+                Event::Appn(event::Appn::Prompt(prompt)) => Inner::Prompt(Prompt {
+                    edit: inner.into_edit(),
+                    prompts: vec![*prompt],
+                }),
+                Event::Appn(event::Appn::Set(scope, args)) => {
+                    let mut edit = inner.into_edit();
+                    match self.on_set_cmd(&mut edit, scope, args) {
+                        Ok(evnt) => {
+                            let mut inner = Inner::Edit(edit);
+                            for evnt in evnt.into_iter() {
+                                inner = match evnt {
+                                    Event::Appn(event::Appn::Less(wless)) => Inner::Less(Less {
+                                        edit: inner.into_edit(),
+                                        wless: *wless,
+                                    }),
+                                    Event::Appn(event::Appn::Hex(whex)) => Inner::Hex(Hex {
+                                        edit: inner.into_edit(),
+                                        whex: *whex,
+                                    }),
+                                    evnt => {
+                                        new_evnt.push(evnt);
+                                        inner
+                                    }
+                                }
+                            }
+                            inner
+                        }
+                        Err(err) => self.to_error_inner(edit, err),
+                    }
+                }
+                Event::Appn(appn @ event::Appn::Map { .. })
+                | Event::Appn(appn @ event::Appn::Unmap(_, _)) => {
+                    let edit = inner.into_edit();
+                    match self.on_map_cmd(appn) {
+                        Ok(Some(wless)) => Inner::Less(Less { edit, wless }),
+                        Ok(None) => Inner::Edit(edit),
+                        Err(err) => self.to_error_inner(edit, err),
+                    }
+                }
+                Event::Appn(event::Appn::Buffers) => {
+                    let edit = inner.into_edit();
+                    match self.to_buffers_less(&edit) {
+                        Ok(wless) => Inner::Less(Less { edit, wless }),
+                        Err(err) => self.to_error_inner(edit, err),
+                    }
+                }
+                Event::Appn(event::Appn::Finder) => {
+                    let edit = inner.into_edit();
+                    match err_at!(IOError, std::env::current_dir()) {
+                        Ok(dir) => {
+                            let coord = self.to_coord_wfinder();
+                            let scheme = self.to_color_scheme(None);
+                            let wfind = WindowFinder::new(coord, &dir, scheme);
+                            Inner::Finder(Finder { edit, wfind })
+                        }
+                        Err(err) => self.to_error_inner(edit, err),
+                    }
+                }
+                Event::Appn(appn @ event::Appn::Edit(_, _))
+                | Event::Appn(appn @ event::Appn::Buffer(_))
+                | Event::Appn(appn @ event::Appn::BufferRotate(_, _))
+                | Event::Appn(appn @ event::Appn::BufferDelete(_, _))
+                | Event::Appn(appn @ event::Appn::Write { .. })
+                | Event::Appn(appn @ event::Appn::SaveAs(_, _))
+                | Event::Appn(appn @ event::Appn::WriteAll(_))
+                | Event::Appn(appn @ event::Appn::Read { .. })
+                | Event::Appn(appn @ event::Appn::Substitute { .. }) => {
+                    let mut edit = inner.into_edit();
+                    if self.is_write_changed(&edit, &appn) {
+                        self.to_changed_inner(edit)
+                    } else {
+                        match self.on_buffer_cmd(&mut edit, appn) {
+                            Ok(()) => Inner::Edit(edit),
+                            Err(err) => self.to_error_inner(edit, err),
+                        }
+                    }
+                }
+                Event::Appn(event::Appn::FileOp(op)) => {
+                    let edit = inner.into_edit();
+                    match self.to_dir_op(&edit, op) {
+                        Ok(op) => self.to_confirm_inner(edit, op),
+                        Err(err) => self.to_error_inner(edit, err),
+                    }
+                }
+                Event::Appn(event::Appn::MkSession(file, force)) => {
+                    let edit = inner.into_edit();
+                    match self.mk_session(&edit, file, force) {
+                        Ok(()) => Inner::Edit(edit),
+                        Err(err) => self.to_error_inner(edit, err),
+                    }
+                }
+                // a failed write, like in `:wq`, shall not quit.
+                Event::Appn(event::Appn::Quit(_)) if inner.is_prompt() => inner,
+                Event::Appn(event::Appn::Quit(force)) => match self.to_quit_prompt() {
+                    Some(prompt) if !force => Inner::Prompt(Prompt {
+                        edit: inner.into_edit(),
+                        prompts: vec![prompt],
+                    }),
+                    _ => {
+                        let ids = self.buffers.iter().map(|buf| buf.to_id()).collect();
+                        self.save_positions(ids);
+                        if let Err(err) = self.history.save() {
+                            error!("fail saving history: {}", err);
+                        }
+                        if let Err(err) = swap::clear() {
+                            error!("fail removing swap files: {}", err);
+                        }
+                        new_evnt.push(Event::Quit);
+                        inner
+                    }
+                },
+                Event::Esc => match inner {
+                    Inner::Hex(hex) if hex.whex.is_written() => {
+                        let mut edit = hex.edit;
+                        match self.reload_hex_buffer(&mut edit) {
+                            Ok(()) => Inner::Edit(edit),
+                            Err(err) => self.to_error_inner(edit, err),
+                        }
+                    }
+                    // floating window leaves its content on the edit window.
+                    Inner::Finder(val) => {
+                        let mut edit = val.edit;
+                        edit.wfile.forget_screen();
+                        Inner::Edit(edit)
+                    }
+                    inner => Inner::Edit(inner.into_edit()),
+                },
+                evnt => {
+                    new_evnt.push(evnt);
+                    inner
+                }
+            }
+        }
+
+        // check for swap files and changes on disk, when edit window
+        // regains focus, or switches to another buffer.
+        self.inner = match inner {
+            Inner::Edit(edit) if Some(edit.wfile.to_buffer_id()) != focus => self.on_focus(edit),
+            inner => inner,
+        };
+        Ok(new_evnt)
+    }
+}
+
+impl Application for Code {
+    fn subscribe(&mut self, topic: &str, tx: mpsc::Sender<pubsub::Notify>) {
+        self.subscribers.subscribe(topic, tx);
+    }
+
+    fn notify(&self, topic: &str, msg: pubsub::Notify) -> Result<()> {
+        self.subscribers.notify(topic, msg)
+    }
+
+    fn to_cursor(&self) -> Option<Cursor> {
+        match &self.inner {
+            Inner::Edit(val) => val.wfile.to_cursor(),
+            Inner::Prompt(val) => val.prompts[0].to_cursor(),
+            Inner::Command(val) => val.wcmd.to_cursor(),
+            Inner::Less(val) => val.wless.to_cursor(),
+            Inner::Hex(val) => val.whex.to_cursor(),
+            Inner::Finder(val) => val.wfind.to_cursor(),
+            Inner::Changed(val) => val.prompt.to_cursor(),
+            Inner::Recover(val) => val.prompt.to_cursor(),
+            Inner::Confirm(val) => val.prompt.to_cursor(),
+            Inner::None => None,
+        }
+    }
+
+    fn on_event(&mut self, evnt: Event) -> Result<Event> {
+        let res = self.on_unmapped_event(evnt);
+        self.stage_journals();
+        res
+    }
+
+    fn to_timeout(&self) -> Option<time::Duration> {
+        match self.keymaps.is_pending() && self.config.timeout {
+            true => Some(time::Duration::from_millis(self.config.timeout_len)),
+            false => match &self.inner {
+                Inner::Less(val) => val.wless.to_timeout(),
+                Inner::Finder(val) => val.wfind.to_timeout(),
+                _ => None,
+            },
+        }
+    }
+
+    fn on_refresh(&mut self) -> Result<()> {
+        let mut inner = mem::replace(&mut self.inner, Inner::default());
+        match &mut inner {
+            Inner::Edit(edit) => {
+                edit.wfile.on_refresh(self)?;
+                edit.wstat.on_refresh()?;
+                // TODO: edit.tbcline.on_refresh(self)?;
+            }
+            Inner::Prompt(prompt) => {
+                prompt.edit.wfile.on_refresh(self)?;
+                match prompt.prompts.first_mut() {
+                    Some(p) => p.on_refresh()?,
+                    None => (),
+                };
+            }
+            Inner::Command(cmd) => {
+                cmd.edit.wfile.on_refresh(self)?;
+                cmd.wcmd.on_refresh(self)?;
+            }
+            Inner::Less(less) => {
+                less.edit.wfile.on_refresh(self)?;
+                less.wless.on_refresh()?;
+            }
+            Inner::Hex(hex) => {
+                hex.edit.wfile.on_refresh(self)?;
+                hex.whex.on_refresh()?;
+            }
+            Inner::Finder(val) => {
+                val.edit.wfile.on_refresh(self)?;
+                val.wfind.on_refresh()?;
+            }
+            Inner::Changed(val) => {
+                val.edit.wfile.on_refresh(self)?;
+                val.prompt.on_refresh()?;
+            }
+            Inner::Recover(val) => {
+                val.edit.wfile.on_refresh(self)?;
+                val.prompt.on_refresh()?;
+            }
+            Inner::Confirm(val) => {
+                val.edit.wfile.on_refresh(self)?;
+                val.prompt.on_refresh()?;
+            }
+            Inner::None => unreachable!(),
+        }
+        self.inner = inner;
+
+        Ok(())
+    }
+
+    fn to_tab_title(&self, wth: usize) -> state::TabTitle {
+        use crate::text;
+        use std::iter::FromIterator;
+
+        let edit = match &self.inner {
             Inner::Edit(edit) => edit,
             Inner::Prompt(val) => &val.edit,
             Inner::Command(val) => &val.edit,
             Inner::Less(val) => &val.edit,
+            Inner::Hex(val) => &val.edit,
+            Inner::Finder(val) => &val.edit,
+            Inner::Changed(val) => &val.edit,
+            Inner::Recover(val) => &val.edit,
+            Inner::Confirm(val) => &val.edit,
             Inner::None => unreachable!(),
         };
         let active = false;
@@ -488,3 +2522,23 @@ impl Application for Code {
         }
     }
 }
+
+// run `diff -u` on `file` against `text`, fed through stdin.
+fn diff_u(file: &path::Path, text: &str, labels: &[String; 2]) -> Result<process::Output> {
+    use std::io::Write;
+
+    let mut child = {
+        let mut cmd = process::Command::new("diff");
+        cmd.arg("-u").arg("--label").arg(&labels[0]);
+        cmd.arg("--label").arg(&labels[1]);
+        cmd.arg(file).arg("-");
+        cmd.stdin(process::Stdio::piped());
+        cmd.stdout(process::Stdio::piped());
+        cmd.stderr(process::Stdio::piped());
+        err_at!(IOError, cmd.spawn())?
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        err_at!(IOError, stdin.write_all(text.as_bytes()))?;
+    }
+    err_at!(IOError, child.wait_with_output())
+}
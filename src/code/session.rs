@@ -0,0 +1,174 @@
+//! Sessions and last cursor positions.
+//!
+//! A session is a snapshot of the editing state, buffers along with their
+//! cursor, marks and options, global marks and the layout of tabs, saved
+//! as TOML file by `:mksession` and restored with `--session` option.
+//! Content of the buffers is not part of the session, unsaved edits are
+//! left to swap files.
+
+#[allow(unused_imports)]
+use log::{debug, warn};
+use serde_derive::{Deserialize, Serialize};
+
+use std::{fs, path};
+
+use crate::{Error, Result};
+
+/// Default file for `:mksession`, in current directory.
+pub const SESSION_FILE: &'static str = "Session.toml";
+
+/// Maximum number of files to remember the cursor position.
+const POSITIONS_LIMIT: usize = 1000;
+
+/// Editing state saved by `:mksession`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Session {
+    /// Buffers in buffer number order.
+    #[serde(default)]
+    pub buffers: Vec<BufferState>,
+    /// Global marks, `[A-Z]`.
+    #[serde(default)]
+    pub marks: Vec<MarkState>,
+    /// Tabs, in the order they are shown.
+    #[serde(default)]
+    pub tabs: Vec<TabState>,
+}
+
+/// Buffer backed by a file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BufferState {
+    pub file: String,
+    pub encoding: String,
+    pub format: String,
+    pub read_only: bool,
+    pub row: usize,
+    pub col: usize,
+    /// Buffer-local options, as `:setlocal` arguments.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Buffer marks, `[a-z]`.
+    #[serde(default)]
+    pub marks: Vec<MarkState>,
+}
+
+/// Mark as a character offset into the buffer, `file` is None for
+/// buffer marks.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MarkState {
+    pub index: char,
+    pub file: Option<String>,
+    pub cursor: usize,
+}
+
+/// Tab and its window layout, the edit window shows `current` buffer.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TabState {
+    pub app: String,
+    pub active: bool,
+    pub current: Option<String>,
+    pub alternate: Option<String>,
+    /// Window-local options, as `:setlocal` arguments.
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+impl Session {
+    /// Load session from TOML `file`.
+    pub fn load(file: &str) -> Result<Session> {
+        let s = err_at!(IOError, fs::read_to_string(file))?;
+        err_at!(FailConvert, toml::from_str(&s))
+    }
+
+    /// Save session as TOML `file`, existing files are overwritten only
+    /// when `force` is true.
+    pub fn save(&self, file: &str, force: bool) -> Result<()> {
+        if !force && path::Path::new(file).exists() {
+            err_at!(Invalid, msg: format!("{:?} exists, add ! to override", file))?
+        }
+        let s = err_at!(FailConvert, toml::to_string(self))?;
+        err_at!(IOError, fs::write(file, s))
+    }
+}
+
+/// Last cursor position of files, persisted in `~/.ted/positions`. Each
+/// line in the file is an entry, `row col file`, ordered from oldest to
+/// newest.
+pub struct Positions {
+    entries: Vec<(String, usize, usize)>,
+}
+
+impl Positions {
+    /// Load positions from `~/.ted/positions`.
+    pub fn load() -> Positions {
+        let mut positions = Positions { entries: vec![] };
+
+        let file = match Self::to_file() {
+            Some(file) if file.exists() => file,
+            _ => return positions,
+        };
+        match fs::read_to_string(&file) {
+            Ok(s) => {
+                for line in s.lines() {
+                    let mut parts = line.splitn(3, ' ');
+                    let row = parts.next().and_then(|x| x.parse().ok());
+                    let col = parts.next().and_then(|x| x.parse().ok());
+                    match (row, col, parts.next()) {
+                        (Some(row), Some(col), Some(name)) => positions.add(name, row, col),
+                        _ => warn!("invalid position entry {:?}", line),
+                    }
+                }
+            }
+            Err(err) => warn!("fail reading positions {:?}: {}", file, err),
+        }
+        debug!("loaded positions from {:?}", file);
+
+        positions
+    }
+
+    /// Persist positions into `~/.ted/positions`.
+    pub fn save(&self) -> Result<()> {
+        let file = match Self::to_file() {
+            Some(file) => file,
+            None => err_at!(IOError, msg: format!("can't find home-directory"))?,
+        };
+        if let Some(dir) = file.parent() {
+            err_at!(IOError, fs::create_dir_all(dir))?;
+        }
+
+        let mut s = String::default();
+        for (name, row, col) in self.entries.iter() {
+            s.push_str(&format!("{} {} {}\n", row, col, name));
+        }
+        err_at!(IOError, fs::write(&file, s))
+    }
+
+    /// Remember cursor position for file `name`, older entry for the same
+    /// file is replaced.
+    pub fn add(&mut self, name: &str, row: usize, col: usize) {
+        if name.len() == 0 || name.contains('\n') {
+            return;
+        }
+
+        self.entries.retain(|(n, _, _)| n != name);
+        self.entries.push((name.to_string(), row, col));
+        if self.entries.len() > POSITIONS_LIMIT {
+            self.entries.drain(..(self.entries.len() - POSITIONS_LIMIT));
+        }
+    }
+
+    /// Return the last cursor position, `(row, col)`, for file `name`.
+    pub fn to_position(&self, name: &str) -> Option<(usize, usize)> {
+        let mut iter = self.entries.iter().rev();
+        iter.find(|(n, _, _)| n == name)
+            .map(|(_, row, col)| (*row, *col))
+    }
+
+    fn to_file() -> Option<path::PathBuf> {
+        let home = dirs::home_dir()?.into_os_string();
+        Some([home, ".ted".into(), "positions".into()].iter().collect())
+    }
+}
+
+#[cfg(test)]
+#[path = "session_test.rs"]
+mod session_test;
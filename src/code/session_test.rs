@@ -0,0 +1,74 @@
+use super::*;
+
+#[test]
+fn test_session_toml() {
+    let sess = Session {
+        buffers: vec![BufferState {
+            file: "/tmp/hello.txt".to_string(),
+            encoding: "utf-8".to_string(),
+            format: "dos".to_string(),
+            read_only: false,
+            row: 10,
+            col: 4,
+            options: vec!["shift_width=2".to_string(), "ignore_case".to_string()],
+            marks: vec![MarkState {
+                index: 'a',
+                file: None,
+                cursor: 100,
+            }],
+        }],
+        marks: vec![MarkState {
+            index: 'A',
+            file: Some("/tmp/hello.txt".to_string()),
+            cursor: 20,
+        }],
+        tabs: vec![TabState {
+            app: "code".to_string(),
+            active: true,
+            current: Some("/tmp/hello.txt".to_string()),
+            alternate: None,
+            options: vec!["nowrap".to_string()],
+        }],
+    };
+
+    let s = toml::to_string(&sess).unwrap();
+    let sess: Session = toml::from_str(&s).unwrap();
+
+    assert_eq!(sess.buffers.len(), 1);
+    let bs = &sess.buffers[0];
+    assert_eq!(bs.file, "/tmp/hello.txt");
+    assert_eq!(bs.format, "dos");
+    assert_eq!((bs.row, bs.col), (10, 4));
+    assert_eq!(bs.options, vec!["shift_width=2", "ignore_case"]);
+    assert_eq!((bs.marks[0].index, bs.marks[0].cursor), ('a', 100));
+    assert_eq!(bs.marks[0].file, None);
+
+    assert_eq!(sess.marks[0].file, Some("/tmp/hello.txt".to_string()));
+    assert_eq!(sess.tabs[0].current, Some("/tmp/hello.txt".to_string()));
+    assert_eq!(sess.tabs[0].alternate, None);
+    assert_eq!(sess.tabs[0].options, vec!["nowrap"]);
+
+    let sess: Session = toml::from_str("").unwrap();
+    assert_eq!(sess.buffers.len(), 0);
+}
+
+#[test]
+fn test_positions() {
+    let mut positions = Positions { entries: vec![] };
+    positions.add("/tmp/a.txt", 1, 2);
+    positions.add("/tmp/b.txt", 3, 4);
+    positions.add("/tmp/a.txt", 5, 6);
+    positions.add("", 7, 8);
+
+    assert_eq!(positions.entries.len(), 2);
+    assert_eq!(positions.to_position("/tmp/a.txt"), Some((5, 6)));
+    assert_eq!(positions.to_position("/tmp/b.txt"), Some((3, 4)));
+    assert_eq!(positions.to_position("/tmp/c.txt"), None);
+
+    for i in 0..(POSITIONS_LIMIT + 10) {
+        positions.add(&format!("/tmp/{}.txt", i), i, 0);
+    }
+    assert_eq!(positions.entries.len(), POSITIONS_LIMIT);
+    assert_eq!(positions.to_position("/tmp/a.txt"), None);
+    assert_eq!(positions.to_position("/tmp/10.txt"), Some((10, 0)));
+}
@@ -6,9 +6,9 @@ use std::{convert::TryInto, fmt, io, mem, result};
 
 use crate::{
     buffer::{self, Buffer},
-    code::{self, cmd},
+    code::{self, cmd, history},
     colors::ColorScheme,
-    event::Event,
+    event::{Appn, Cud, Event, Mto, DP},
     keymap::Keymap,
     location::Location,
     term::Spanline,
@@ -19,12 +19,23 @@ use crate::{
 
 pub struct WindowCmd {
     coord: Coord,
+    // one of `:` for commands, `/` and `?` for search.
+    leader: char,
+    // count prefix for search.
+    n: usize,
+    // (index, prefix), while recalling history entries.
+    hist: Option<(usize, String)>,
+    // buffer in the edit window, from which the command-line is opened,
+    // and the edit window's alternate buffer.
+    edit_buf_id: String,
+    altn_buf_id: Option<String>,
     cursor: Cursor,
     obc_xy: buffer::Cursor,
     buf: Buffer,
     scheme: ColorScheme,
     keymap: Keymap,
-    #[allow(dead_code)]
+    // span being completed, while cycling through suggestions.
+    tab_span: Option<String>,
     wsugg: WindowSuggest,
 }
 
@@ -39,29 +50,131 @@ impl fmt::Display for WindowCmd {
 }
 
 impl WindowCmd {
-    pub fn new(coord: Coord, app: &code::Code) -> Result<WindowCmd> {
-        let mut buf = {
-            let read_only = false;
-            let loc = Location::new_ted("code-cmd", io::empty(), read_only)?;
-            Buffer::from_reader(loc)?
-        };
-        buf.set_insert_mode();
-        buf.cud_char(None, ':').unwrap();
+    /// Create a command-line window, `leader` can be `:` for ex-commands,
+    /// `/` for forward search and `?` for backward search. `edit_buf_id`
+    /// is the buffer being edited when command-line is opened, and
+    /// `altn_buf_id` is the edit window's alternate buffer.
+    pub fn new(
+        coord: Coord,
+        app: &code::Code,
+        leader: char,
+        edit_buf_id: String,
+        altn_buf_id: Option<String>,
+    ) -> Result<WindowCmd> {
+        let buf = new_buffer(&leader.to_string())?;
 
         let cursor = view::NoWrap::initial_cursor(false /*line_number*/);
         let obc_xy = (0, 0).into();
         Ok(WindowCmd {
             coord,
+            leader,
+            n: 1,
+            hist: None,
+            edit_buf_id,
+            altn_buf_id,
             cursor,
             obc_xy,
             buf,
             scheme: app.to_color_scheme(None),
             keymap: Keymap::new_cmd(),
+            tab_span: None,
             wsugg: app.to_wsugg(),
         })
     }
 }
 
+impl WindowCmd {
+    pub fn to_event_prefix(&self) -> Event {
+        self.keymap.to_event_prefix()
+    }
+
+    fn to_history_kind(&self, text: &str) -> history::Kind {
+        match self.leader {
+            ':' => {
+                let range = |ch: char| ch.is_ascii_digit() || ".,;%$+-' ".contains(ch);
+                let text = text.trim_start_matches(range);
+                if_else!(
+                    text.starts_with('!'),
+                    history::Kind::Filter,
+                    history::Kind::Cmd
+                )
+            }
+            _ => history::Kind::Search,
+        }
+    }
+
+    // recall older/newer entry from history, matching the text typed
+    // before starting the recall.
+    fn recall(&mut self, app: &code::Code, buf: &Buffer, dp: DP) -> Result<Buffer> {
+        let (off, prefix) = match self.hist.take() {
+            Some((off, prefix)) => (Some(off), prefix),
+            None => (None, buf.to_string().chars().skip(1).collect()),
+        };
+        let kind = self.to_history_kind(&prefix);
+        let history = app.as_history();
+
+        let off = match dp {
+            DP::Left => history.to_older(kind, off, &prefix).or(off),
+            _ => off.and_then(|off| history.to_newer(kind, off, &prefix)),
+        };
+        let text = match off {
+            Some(off) => {
+                self.hist = Some((off, prefix));
+                history.as_entries(kind)[off].clone()
+            }
+            None => prefix,
+        };
+        new_buffer(&format!("{}{}", self.leader, text))
+    }
+
+    // complete the span before cursor, cycle through the choices on
+    // subsequent Tab and BackTab.
+    fn complete(&mut self, app: &code::Code, buf: &mut Buffer, evnt: Event) -> Result<Event> {
+        if self.tab_span.is_none() && self.leader == ':' {
+            let line = buf.slice(1..buf.to_char_cursor());
+            let (span, choices) = cmd::to_completions(app, &line);
+            if choices.len() > 0 {
+                self.wsugg = app.to_wsugg().into_suggestions(choices);
+                self.tab_span = Some(span);
+            }
+        }
+
+        let n = match self.tab_span.as_ref() {
+            Some(span) => span.chars().count(),
+            None => return Ok(Event::Noop),
+        };
+        match self.wsugg.on_event(evnt)? {
+            Event::TabInsert(choice) => {
+                let text: String = choice.chars().skip(n).collect();
+                buf.on_event(Event::TabInsert(text))
+            }
+            Event::TabClear => buf.on_event(Event::TabClear),
+            _ => Ok(Event::Noop),
+        }
+    }
+
+    // parse and apply the command-line `content`.
+    fn to_command_event(&self, app: &mut code::Code, content: String) -> Result<Event> {
+        use crate::code::cmd::Command;
+
+        let mut val: cmd::Cmd = (content, self.scheme.clone()).try_into()?;
+        val.on_command(app)
+    }
+
+    // only read-only registers are supported, ted does not keep yanked
+    // text in registers.
+    fn to_register(&self, app: &code::Code, reg: char) -> Option<String> {
+        let history = app.as_history();
+        match reg {
+            ':' => history.as_entries(history::Kind::Cmd).last().cloned(),
+            '/' => history.as_entries(history::Kind::Search).last().cloned(),
+            '%' => app.as_buffer(&self.edit_buf_id).map(|buf| buf.to_id()),
+            '#' => self.altn_buf_id.clone(),
+            _ => None,
+        }
+    }
+}
+
 impl Window for WindowCmd {
     type App = code::Code;
 
@@ -96,31 +209,86 @@ impl Window for WindowCmd {
     }
 
     fn on_event(&mut self, app: &mut code::Code, mut evnt: Event) -> Result<Event> {
-        use crate::code::cmd::Command;
-
         let mut buf = mem::replace(&mut self.buf, Buffer::empty());
         evnt = match self.keymap.fold(&mut buf, evnt)? {
-            Event::N(n) => {
+            Event::N(n) if self.leader == ':' => {
                 let s = format!(".,.+{}", n.saturating_sub(1));
                 buf.cud_str(Some(0), &s)?;
                 Event::Noop
             }
-            Event::Enter(_) => {
-                let mut val: cmd::Cmd = {
-                    let content = buf.to_string();
-                    (content, self.scheme.clone()).try_into()?
+            Event::N(n) => {
+                self.n = n;
+                Event::Noop
+            }
+            Event::Up(_) => {
+                buf = self.recall(app, &buf, DP::Left)?;
+                Event::Noop
+            }
+            Event::Down(_) => {
+                buf = self.recall(app, &buf, DP::Right)?;
+                Event::Noop
+            }
+            Event::Wr(Cud::Register(reg)) => {
+                if let Some(text) = self.to_register(app, reg) {
+                    let cursor = buf.cud_str(None, &text)?;
+                    buf.set_cursor(cursor);
+                }
+                Event::Noop
+            }
+            Event::Wr(Cud::CursorWord) => {
+                let word = match app.as_mut_buffer(&self.edit_buf_id) {
+                    Some(buf) => buf.to_cursor_word()?,
+                    None => None,
                 };
-                let mut evnt = val.on_command(app)?;
+                if let Some(word) = word {
+                    let cursor = buf.cud_str(None, &word)?;
+                    buf.set_cursor(cursor);
+                }
+                Event::Noop
+            }
+            Event::Enter(_) if self.leader != ':' => {
+                let patt: String = buf.to_string().chars().skip(1).collect();
+                app.add_history(history::Kind::Search, &patt);
+                let dp = if_else!(self.leader == '/', DP::Right, DP::Left);
+                let mut evnt = Event::Mt(Mto::Pattern(self.n, Some(patt), dp));
                 evnt.push(Event::Esc);
                 evnt
             }
-            evnt => buf.on_event(evnt)?,
+            Event::Enter(_) => {
+                let content = buf.to_string();
+                let text: String = content.chars().skip(1).collect();
+                app.add_history(self.to_history_kind(&text), &text);
+                // close the command-line before applying command's events,
+                // a failed command is reported on the prompt.
+                let mut evnt = Event::Esc;
+                match self.to_command_event(app, content) {
+                    Ok(cmd_evnt) => evnt.push(cmd_evnt),
+                    Err(err) => {
+                        let prompt = app.to_error_prompt(err.to_error());
+                        evnt.push(Event::Appn(Appn::Prompt(Box::new(prompt))))
+                    }
+                }
+                evnt
+            }
+            evnt @ Event::Tab(_) | evnt @ Event::BackTab => {
+                self.hist = None;
+                self.complete(app, &mut buf, evnt)?
+            }
+            evnt => {
+                self.hist = None;
+                if self.tab_span.take().is_some() {
+                    self.wsugg = app.to_wsugg();
+                }
+                buf.on_event(evnt)?
+            }
         };
         self.buf = buf;
         Ok(evnt)
     }
 
     fn on_refresh(&mut self, _app: &mut code::Code) -> Result<()> {
+        self.wsugg.on_refresh()?;
+
         let (col, row) = self.coord.to_origin_cursor();
         err_at!(Fatal, termqu!(term_cursor::MoveTo(col, row)))?;
 
@@ -133,6 +301,19 @@ impl Window for WindowCmd {
     }
 }
 
+// command-line buffer, in insert mode, with cursor at the end of `text`.
+fn new_buffer(text: &str) -> Result<Buffer> {
+    let mut buf = {
+        let read_only = false;
+        let loc = Location::new_ted("code-cmd", io::empty(), read_only)?;
+        Buffer::from_reader(loc)?
+    };
+    buf.set_insert_mode();
+    let cursor = buf.cud_str(None, text)?;
+    buf.set_cursor(cursor);
+    Ok(buf)
+}
+
 impl Render for WindowCmd {
     type Buf = Buffer;
 
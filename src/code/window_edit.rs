@@ -1,12 +1,15 @@
 #[allow(unused_imports)]
 use log::{debug, trace};
 
-use std::{cmp, convert::TryInto, fmt, result};
+use std::{cmp, convert::TryInto, fmt, mem, result};
 
 use crate::{
     app::Application,
     buffer::{self, Buffer},
-    code::{self},
+    code::{
+        self,
+        config::{Config, ConfigToml},
+    },
     colors::ColorScheme,
     event::{self, Event, Scroll, DP},
     keymap::Keymap,
@@ -14,7 +17,7 @@ use crate::{
     syntax::{self, Syntax},
     term::Spanline,
     view,
-    window::{Coord, Cursor, Render, WinBuffer, Window},
+    window::{Coord, Cursor, Jump, JumpList, Render, WinBuffer, Window},
     Error, Result,
 };
 
@@ -23,13 +26,14 @@ pub struct WindowEdit {
     cursor: Cursor,
     obc_xy: buffer::Cursor,
     curr_buf_id: String,
-    #[allow(dead_code)]
     altn_buf_id: Option<String>,
     syn: syntax::Syn,
     scheme: ColorScheme,
     keymap: Keymap,
+    jumps: JumpList,
     old_screen: Option<Vec<view::ScrLine>>,
-    // configuration.
+    // configuration, window-local options override buffer's options.
+    local: ConfigToml,
     wrap: bool,
     scroll_off: u16,
     line_number: bool,
@@ -48,10 +52,11 @@ impl fmt::Display for WindowEdit {
 
 impl<'a, 'b> From<(&'a code::Code, &'b Buffer, Coord)> for WindowEdit {
     fn from((app, buf, coord): (&'a code::Code, &'b Buffer, Coord)) -> Self {
-        let cursor = if app.config.wrap {
-            view::Wrap::initial_cursor(app.config.line_number)
+        let config = app.to_buffer_config(buf);
+        let cursor = if config.wrap {
+            view::Wrap::initial_cursor(config.line_number)
         } else {
-            view::NoWrap::initial_cursor(app.config.line_number)
+            view::NoWrap::initial_cursor(config.line_number)
         };
 
         let scheme = app.to_color_scheme(None);
@@ -61,14 +66,16 @@ impl<'a, 'b> From<(&'a code::Code, &'b Buffer, Coord)> for WindowEdit {
             obc_xy: (0, 0).into(),
             curr_buf_id: buf.to_id(),
             altn_buf_id: None,
-            syn: syntax::detect(buf, &scheme).unwrap(),
+            syn: syntax::detect(buf, &scheme, app.state_config.large_file).unwrap(),
             scheme,
             keymap: Keymap::new_edit(),
+            jumps: JumpList::default(),
             old_screen: None,
             // configuration
-            wrap: app.as_ref().wrap,
-            scroll_off: app.as_ref().scroll_off,
-            line_number: app.as_ref().line_number,
+            local: ConfigToml::default(),
+            wrap: config.wrap,
+            scroll_off: config.scroll_off,
+            line_number: config.line_number,
             scroll: None,
         };
         debug!("{} {} {}", w, w.scroll_off, w.line_number);
@@ -77,29 +84,111 @@ impl<'a, 'b> From<(&'a code::Code, &'b Buffer, Coord)> for WindowEdit {
 }
 
 impl WindowEdit {
-    #[allow(dead_code)]
-    pub fn set_buffer(&mut self, buf: &Buffer) -> &mut Self {
-        self.altn_buf_id = Some(self.curr_buf_id.clone());
-        self.curr_buf_id = buf.to_id();
+    /// Switch to buffer `buf`, current buffer becomes the alternate
+    /// buffer. Switching to current buffer will re-build the window for
+    /// the buffer, say after reloading its content.
+    pub fn set_buffer(&mut self, app: &code::Code, buf: &Buffer) -> &mut Self {
+        let altn_buf_id = match buf.to_id() {
+            id if id == self.curr_buf_id => self.altn_buf_id.take(),
+            _ => Some(self.curr_buf_id.clone()),
+        };
+        let jumps = mem::replace(&mut self.jumps, JumpList::default());
+
+        let coord = self.coord;
+        *self = (app, buf, coord).into();
+        self.altn_buf_id = altn_buf_id;
+        self.jumps = jumps;
         self
     }
 
-    #[allow(dead_code)]
-    pub fn flip_buffer(&mut self) -> &mut Self {
-        match self.altn_buf_id.take() {
-            Some(altn_buf_id) => {
-                let curr_buf_id = self.curr_buf_id.clone();
-                self.curr_buf_id = altn_buf_id;
-                self.altn_buf_id = Some(curr_buf_id);
-            }
-            None => (),
+    /// Re-apply configuration, after it is changed using `:set`, on this
+    /// window. Window-local options override options from the buffer's
+    /// configuration.
+    pub fn set_config(&mut self, app: &code::Code) -> &mut Self {
+        let config = match app.as_buffer(&self.curr_buf_id) {
+            Some(buf) => app.to_buffer_config(buf).mixin(self.local.clone()),
+            None => return self,
+        };
+        if self.wrap != config.wrap || self.line_number != config.line_number {
+            // line-number width and wrapping changes the screen column,
+            // re-align the cursor with buffer's cursor on the next refresh.
+            let row = self.cursor.row;
+            self.cursor = if config.wrap {
+                view::Wrap::initial_cursor(config.line_number)
+            } else {
+                view::NoWrap::initial_cursor(config.line_number)
+            };
+            self.cursor.row = row;
+            self.obc_xy.col = 0;
         }
+        self.wrap = config.wrap;
+        self.scroll_off = config.scroll_off;
+        self.line_number = config.line_number;
+        self.old_screen = None;
+        self
+    }
+
+    /// Set window-local option `name`, from `config`. Window-local
+    /// options are reset when the window switches to another buffer.
+    pub fn set_local_option(&mut self, name: &str, config: &Config) -> Result<&mut Self> {
+        self.local.copy_option(name, config)?;
+        Ok(self)
+    }
+
+    /// Forget buffer `id`, if it is the alternate buffer.
+    pub fn forget_buffer(&mut self, id: &str) -> &mut Self {
+        if self.altn_buf_id.as_ref().map(|x| x.as_str()) == Some(id) {
+            self.altn_buf_id = None;
+        }
+        self
+    }
+
+    /// Forget the rendered screen, so that all the lines are rendered on
+    /// next refresh, say after closing a window floating over this one.
+    pub fn forget_screen(&mut self) -> &mut Self {
+        self.old_screen = None;
         self
     }
 
     pub fn to_event_prefix(&self) -> Event {
         self.keymap.to_event_prefix()
     }
+
+    // record jumps, made within the buffer, in this window's jump-list,
+    // and adjust the remembered jumps for edits.
+    fn remember_jumps(&mut self, buf: &Buffer, evnt: Event) -> Event {
+        let mut new_evnt = Event::default();
+        for evnt in evnt.into_iter() {
+            match evnt {
+                Event::JumpFrom(cursor) => self.jumps.remember(Jump::new(cursor, buf)),
+                Event::Edit(edit) => {
+                    self.jumps.update(&edit);
+                    new_evnt.push(Event::Edit(edit))
+                }
+                evnt => new_evnt.push(evnt),
+            }
+        }
+        new_evnt
+    }
+
+    // move `n` positions older, [DP::Left], or newer, [DP::Right], in the
+    // jump-list. Jumps made in other buffers are not followed.
+    fn mto_jumps(&mut self, buf: &Buffer, n: usize, dp: DP) -> Result<usize> {
+        let cursor = buf.to_char_cursor();
+        match (dp, self.jumps.to_current()) {
+            // remember the current position, to come back with Ctrl-I.
+            (DP::Left, None) => self.jumps.older(n + 1, Some(Jump::new(cursor, buf))),
+            (DP::Left, Some(_)) => self.jumps.older(n, None),
+            (DP::Right, _) => self.jumps.newer(n),
+            (dp, _) => err_at!(Fatal, msg: format!("invalid direction {}", dp))?,
+        }
+        match self.jumps.to_current() {
+            Some(jmp) if jmp.to_buffer_id() == buf.to_id() => {
+                Ok(cmp::min(jmp.to_cursor(), buf.last_char_idx()))
+            }
+            _ => Ok(cursor),
+        }
+    }
 }
 
 impl WindowEdit {
@@ -108,6 +197,16 @@ impl WindowEdit {
         self.curr_buf_id.clone()
     }
 
+    #[inline]
+    pub fn to_altn_buffer_id(&self) -> Option<String> {
+        self.altn_buf_id.clone()
+    }
+
+    #[inline]
+    pub fn to_local_options(&self) -> ConfigToml {
+        self.local.clone()
+    }
+
     #[inline]
     pub fn to_text_type(&self) -> String {
         self.syn.as_name().to_string()
@@ -567,6 +666,11 @@ impl Window for WindowEdit {
                     buf.set_cursor(nbc).clear_sticky_col();
                     (Event::Noop, Some(buf))
                 }
+                Event::Mt(Mto::Jumps(n, dp)) => {
+                    let nbc = self.mto_jumps(&buf, n, dp)?;
+                    buf.set_cursor(nbc).clear_sticky_col();
+                    (Event::Noop, Some(buf))
+                }
                 Event::Appn(event::Appn::StatusCursor) => {
                     let msg = vec![self.syn.to_status_cursor()?];
                     app.notify("code", Notify::Status(msg))?;
@@ -574,6 +678,7 @@ impl Window for WindowEdit {
                 }
                 evnt => {
                     let evnt = buf.on_event(evnt)?;
+                    let evnt = self.remember_jumps(&buf, evnt);
                     let evnt = self.syn.on_edit(&mut buf, evnt)?;
                     (evnt, Some(buf))
                 }
@@ -6,6 +6,7 @@ use crate::{
     app::Application,
     buffer::Buffer,
     code,
+    code::config::{Config, ConfigToml},
     code::window_edit::WindowEdit,
     event::{self, Event},
     term::Span,
@@ -51,6 +52,41 @@ impl WindowFile {
     pub fn to_buffer_id(&self) -> String {
         self.we.to_buffer_id()
     }
+
+    #[inline]
+    pub fn to_altn_buffer_id(&self) -> Option<String> {
+        self.we.to_altn_buffer_id()
+    }
+
+    pub fn set_buffer(&mut self, app: &code::Code, buf: &Buffer) -> &mut Self {
+        self.we.set_buffer(app, buf);
+        self
+    }
+
+    pub fn forget_buffer(&mut self, id: &str) -> &mut Self {
+        self.we.forget_buffer(id);
+        self
+    }
+
+    pub fn forget_screen(&mut self) -> &mut Self {
+        self.we.forget_screen();
+        self
+    }
+
+    pub fn set_config(&mut self, app: &code::Code) -> &mut Self {
+        self.we.set_config(app);
+        self
+    }
+
+    pub fn set_local_option(&mut self, name: &str, config: &Config) -> Result<&mut Self> {
+        self.we.set_local_option(name, config)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn to_local_options(&self) -> ConfigToml {
+        self.we.to_local_options()
+    }
 }
 
 impl WindowFile {
@@ -94,9 +130,12 @@ impl WindowFile {
         let fstt = {
             let mut ss = vec![];
             if b.is_read_only() {
-                ss.push("read-only")
+                ss.push("read-only".to_string())
             } else if b.is_modified() {
-                ss.push("modified")
+                ss.push("modified".to_string())
+            }
+            if let Some(compress) = b.to_location().to_compress() {
+                ss.push(compress.to_string())
             }
             ss.join(", ")
         };
@@ -0,0 +1,120 @@
+//! Module `compress` implement transparent decompression, and
+//! re-compression, of gzip, bzip2 and xz files.
+
+use std::{
+    fmt, fs,
+    io::{self, Read, Write},
+    path, result,
+};
+
+use crate::{Error, Result};
+
+/// Number of leading bytes to read for detecting compression.
+const MAGIC_SIZE: u64 = 16;
+
+/// Compression algorithm, along with its level, for a file. Files are
+/// re-compressed with the same algorithm and level they were read with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compress {
+    /// Level is taken from the header's extra-flags, 9 for best, 1 for
+    /// fastest, else 6.
+    Gzip(u32),
+    /// Level is the block size from the header, `1` to `9`.
+    Bzip2(u32),
+    /// Level is not recorded in the stream, always 6.
+    Xz(u32),
+}
+
+impl fmt::Display for Compress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        match self {
+            Compress::Gzip(level) => write!(f, "gzip-{}", level),
+            Compress::Bzip2(level) => write!(f, "bzip2-{}", level),
+            Compress::Xz(level) => write!(f, "xz-{}", level),
+        }
+    }
+}
+
+impl Compress {
+    /// Detect compression from the leading bytes of content, return None
+    /// if content is not compressed.
+    pub fn from_magic(bytes: &[u8]) -> Option<Compress> {
+        match bytes {
+            [0x1f, 0x8b, 8, _, _, _, _, _, 2, ..] => Some(Compress::Gzip(9)),
+            [0x1f, 0x8b, 8, _, _, _, _, _, 4, ..] => Some(Compress::Gzip(1)),
+            [0x1f, 0x8b, ..] => Some(Compress::Gzip(6)),
+            [b'B', b'Z', b'h', n @ b'1'..=b'9', ..] => Some(Compress::Bzip2((n - b'0') as u32)),
+            [0xFD, b'7', b'z', b'X', b'Z', 0, ..] => Some(Compress::Xz(6)),
+            _ => None,
+        }
+    }
+
+    /// Detect compression from file's extension, `.gz`, `.bz2` or `.xz`,
+    /// using the default level of each algorithm.
+    pub fn from_extension(file: &path::Path) -> Option<Compress> {
+        match file.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => Some(Compress::Gzip(6)),
+            Some("bz2") => Some(Compress::Bzip2(9)),
+            Some("xz") => Some(Compress::Xz(6)),
+            _ => None,
+        }
+    }
+
+    /// Detect compression for `file` from its leading bytes. Missing, or
+    /// empty, files are detected by their extension.
+    pub fn detect(file: &path::Path) -> Result<Option<Compress>> {
+        let mut bytes = vec![];
+        match fs::File::open(file) {
+            Ok(fd) => {
+                err_at!(IOError, fd.take(MAGIC_SIZE).read_to_end(&mut bytes))?;
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => err_at!(IOError, Err(err))?,
+        }
+        match bytes.len() {
+            0 => Ok(Self::from_extension(file)),
+            _ => Ok(Self::from_magic(&bytes)),
+        }
+    }
+
+    /// Wrap `r` to read decompressed content. Concatenated streams, like
+    /// the ones created by appending to a compressed file, are read as a
+    /// single content.
+    pub fn to_reader<'a, R>(&self, r: R) -> Box<dyn io::Read + 'a>
+    where
+        R: 'a + io::Read,
+    {
+        match self {
+            Compress::Gzip(_) => Box::new(flate2::read::MultiGzDecoder::new(r)),
+            Compress::Bzip2(_) => Box::new(bzip2::read::MultiBzDecoder::new(r)),
+            Compress::Xz(_) => Box::new(xz2::read::XzDecoder::new_multi_decoder(r)),
+        }
+    }
+
+    /// Compress `bytes` into a single stream.
+    pub fn encode(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Compress::Gzip(level) => {
+                let level = flate2::Compression::new(*level);
+                let mut w = flate2::write::GzEncoder::new(vec![], level);
+                err_at!(IOError, w.write_all(bytes))?;
+                err_at!(IOError, w.finish())
+            }
+            Compress::Bzip2(level) => {
+                let level = bzip2::Compression::new(*level);
+                let mut w = bzip2::write::BzEncoder::new(vec![], level);
+                err_at!(IOError, w.write_all(bytes))?;
+                err_at!(IOError, w.finish())
+            }
+            Compress::Xz(level) => {
+                let mut w = xz2::write::XzEncoder::new(vec![], *level);
+                err_at!(IOError, w.write_all(bytes))?;
+                err_at!(IOError, w.finish())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "compress_test.rs"]
+mod compress_test;
@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn test_compress() {
+    let text = "hello world\nhow are you\n".repeat(100);
+    let compresses = vec![
+        Compress::Gzip(1),
+        Compress::Gzip(6),
+        Compress::Gzip(9),
+        Compress::Bzip2(1),
+        Compress::Bzip2(9),
+        Compress::Xz(6),
+    ];
+    for compress in compresses.into_iter() {
+        let bytes = compress.encode(text.as_bytes()).unwrap();
+        assert_eq!(Compress::from_magic(&bytes), Some(compress), "{}", compress);
+
+        // appended streams are read as one content.
+        let mut bytes = bytes;
+        bytes.extend(compress.encode(b"appended\n").unwrap());
+        let mut out = String::default();
+        compress
+            .to_reader(bytes.as_slice())
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, text.clone() + "appended\n", "{}", compress);
+    }
+
+    assert_eq!(Compress::from_magic(text.as_bytes()), None);
+    assert_eq!(Compress::from_magic(&[]), None);
+}
+
+#[test]
+fn test_detect() {
+    let ext = |f: &str| Compress::from_extension(path::Path::new(f));
+    assert_eq!(ext("config.toml.gz"), Some(Compress::Gzip(6)));
+    assert_eq!(ext("app.log.bz2"), Some(Compress::Bzip2(9)));
+    assert_eq!(ext("app.log.xz"), Some(Compress::Xz(6)));
+    assert_eq!(ext("app.log"), None);
+
+    let dir = std::env::temp_dir();
+
+    // content takes precedence over extension.
+    let file = dir.join("ted-test-detect.gz");
+    fs::write(&file, Compress::Bzip2(4).encode(b"hello").unwrap()).unwrap();
+    assert_eq!(Compress::detect(&file).unwrap(), Some(Compress::Bzip2(4)));
+    fs::write(&file, "hello").unwrap();
+    assert_eq!(Compress::detect(&file).unwrap(), None);
+    fs::write(&file, "").unwrap();
+    assert_eq!(Compress::detect(&file).unwrap(), Some(Compress::Gzip(6)));
+    fs::remove_file(&file).unwrap();
+
+    let file = dir.join("ted-test-detect-missing.xz");
+    assert_eq!(Compress::detect(&file).unwrap(), Some(Compress::Xz(6)));
+}
@@ -19,6 +19,8 @@ use crate::{colors::ColorScheme, Error, Result};
 /// c. Implement `FromStr`, TryFrom<ffi::OsString>, TryFrom<&str>, TryFrom<&[u8]>
 ///    TryFrom<toml::Value> traits for `ConfigToml` that make deserialization
 ///    of config from common types like - string, file, bytes and toml.
+/// e. Option table for `Config` type, to query and update configuration
+///    fields by name, refer [OptValue] and [SetOpt].
 ///
 macro_rules! config {
     ($(($field:ident, $t:ty, $val:expr)),*) => (
@@ -35,7 +37,7 @@ macro_rules! config {
 
         /// Generated using config![] macro. Shadow type for [Config] type.
         /// Refer [crate::config] macros for more detail.
-        #[derive(Clone, Debug, Deserialize)]
+        #[derive(Clone, Debug, Default, Deserialize)]
         pub struct ConfigToml {
             $(pub $field: Option<$t>,)*
         }
@@ -59,6 +61,65 @@ macro_rules! config {
             }
         }
 
+        impl Config {
+            /// Return the list of option names, in the order of declaration.
+            pub fn to_option_names() -> Vec<&'static str> {
+                vec![$(stringify!($field),)*]
+            }
+
+            /// Return whether option `name` is boolean, None if there is
+            /// no such option.
+            pub fn is_bool_option(&self, name: &str) -> Option<bool> {
+                use $crate::config::OptValue;
+
+                match name {
+                    $(stringify!($field) => Some(self.$field.is_bool()),)*
+                    _ => None,
+                }
+            }
+
+            /// Return option `name` formatted as `:set` argument.
+            pub fn to_option(&self, name: &str) -> Option<String> {
+                use $crate::config::OptValue;
+
+                match name {
+                    $(stringify!($field) => Some(self.$field.to_opt_arg(name)),)*
+                    _ => None,
+                }
+            }
+
+            /// Apply `op` on option `name`.
+            pub fn set_option(&mut self, name: &str, op: &$crate::config::SetOpt) -> Result<()> {
+                use $crate::config::OptValue;
+
+                match name {
+                    $(stringify!($field) => self.$field.set_opt(name, op),)*
+                    name => err_at!(Invalid, msg: format!("unknown option {}", name)),
+                }
+            }
+
+            /// Copy option `name` from `other` configuration.
+            pub fn copy_option(&mut self, name: &str, other: &Config) -> Result<()> {
+                match name {
+                    $(stringify!($field) => self.$field = other.$field.clone(),)*
+                    name => err_at!(Invalid, msg: format!("unknown option {}", name))?,
+                };
+                Ok(())
+            }
+        }
+
+        impl ConfigToml {
+            /// Copy option `name` from `other` configuration into this
+            /// layer, so that it overrides the layers beneath.
+            pub fn copy_option(&mut self, name: &str, other: &Config) -> Result<()> {
+                match name {
+                    $(stringify!($field) => self.$field = Some(other.$field.clone()),)*
+                    name => err_at!(Invalid, msg: format!("unknown option {}", name))?,
+                };
+                Ok(())
+            }
+        }
+
         impl FromStr for ConfigToml {
             type Err = Error;
 
@@ -98,6 +159,206 @@ macro_rules! config {
     );
 }
 
+/// Scope of `:set` operation, `:set` apply on both global and local
+/// values, `:setlocal` only on local values and `:setglobal` only on
+/// global values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scope {
+    Both,
+    Local,
+    Global,
+}
+
+/// Operation on a configuration option, parsed from `:set` argument.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SetOpt {
+    /// `opt?`, or `opt` for non-boolean options.
+    Show,
+    /// `opt`, for boolean options.
+    On,
+    /// `noopt`, for boolean options.
+    Off,
+    /// `opt!` or `invopt`, for boolean options.
+    Toggle,
+    /// `opt=val`
+    Assign(String),
+    /// `opt+=val`, add to number or append to string.
+    Add(String),
+    /// `opt-=val`, subtract from number or remove from string.
+    Sub(String),
+}
+
+impl SetOpt {
+    /// Parse `:set` argument into option name and its operation.
+    /// `is_bool` shall return whether the named option is boolean, or
+    /// None if there is no such option. Special argument `all` shall be
+    /// handled by the caller.
+    pub fn parse<F>(arg: &str, is_bool: F) -> Result<(String, SetOpt)>
+    where
+        F: Fn(&str) -> Option<bool>,
+    {
+        let (name, op) = match arg.find('=') {
+            Some(off) => {
+                let val = unescape(&arg[off + 1..]);
+                match &arg[..off] {
+                    name if name.ends_with('+') => (&name[..off - 1], SetOpt::Add(val)),
+                    name if name.ends_with('-') => (&name[..off - 1], SetOpt::Sub(val)),
+                    name => (name, SetOpt::Assign(val)),
+                }
+            }
+            None if arg.ends_with('?') => (&arg[..arg.len() - 1], SetOpt::Show),
+            None if arg.ends_with('!') => (&arg[..arg.len() - 1], SetOpt::Toggle),
+            None => match is_bool(arg) {
+                Some(true) => (arg, SetOpt::On),
+                Some(false) => (arg, SetOpt::Show),
+                None if arg.starts_with("no") => (&arg[2..], SetOpt::Off),
+                None if arg.starts_with("inv") => (&arg[3..], SetOpt::Toggle),
+                None => (arg, SetOpt::Show),
+            },
+        };
+
+        match (is_bool(name), &op) {
+            (Some(false), SetOpt::Off) | (Some(false), SetOpt::Toggle) => {
+                err_at!(Invalid, msg: format!("invalid argument {}", arg))
+            }
+            (Some(_), _) => Ok((name.to_string(), op)),
+            (None, _) => err_at!(Invalid, msg: format!("unknown option {}", arg)),
+        }
+    }
+
+    fn to_arg(&self, name: &str) -> String {
+        match self {
+            SetOpt::Show => format!("{}?", name),
+            SetOpt::On => name.to_string(),
+            SetOpt::Off => format!("no{}", name),
+            SetOpt::Toggle => format!("{}!", name),
+            SetOpt::Assign(val) => format!("{}={}", name, val),
+            SetOpt::Add(val) => format!("{}+={}", name, val),
+            SetOpt::Sub(val) => format!("{}-={}", name, val),
+        }
+    }
+}
+
+// `\ ` is a space within the value, `\\` is a backslash.
+fn unescape(val: &str) -> String {
+    let mut s = String::default();
+    let mut chars = val.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => s.push(chars.next().unwrap_or('\\')),
+            ch => s.push(ch),
+        }
+    }
+    s
+}
+
+/// Types that can be used as fields in [config] macro, so that they can
+/// be queried and updated by `:set` command.
+pub trait OptValue {
+    /// Return whether this is a boolean option.
+    fn is_bool(&self) -> bool {
+        false
+    }
+
+    /// Return option value formatted as `:set` argument.
+    fn to_opt_arg(&self, name: &str) -> String;
+
+    /// Apply `op` on option value, type-checking its argument.
+    fn set_opt(&mut self, name: &str, op: &SetOpt) -> Result<()>;
+}
+
+impl OptValue for bool {
+    fn is_bool(&self) -> bool {
+        true
+    }
+
+    fn to_opt_arg(&self, name: &str) -> String {
+        if_else!(*self, name.to_string(), format!("no{}", name))
+    }
+
+    fn set_opt(&mut self, name: &str, op: &SetOpt) -> Result<()> {
+        *self = match op {
+            SetOpt::Show => *self,
+            SetOpt::On => true,
+            SetOpt::Off => false,
+            SetOpt::Toggle => !*self,
+            op => err_at!(Invalid, msg: format!("invalid argument {}", op.to_arg(name)))?,
+        };
+        Ok(())
+    }
+}
+
+macro_rules! opt_number {
+    ($($t:ty),*) => (
+        $(
+            impl OptValue for $t {
+                fn to_opt_arg(&self, name: &str) -> String {
+                    format!("{}={}", name, self)
+                }
+
+                fn set_opt(&mut self, name: &str, op: &SetOpt) -> Result<()> {
+                    let to_num = |val: &str| -> Result<$t> {
+                        let msg = format!("number required {}", op.to_arg(name));
+                        err_at!(Invalid, val.trim().parse::<$t>().map_err(|_| msg))
+                    };
+                    let val = match op {
+                        SetOpt::Show => Some(*self),
+                        SetOpt::Assign(val) => Some(to_num(val)?),
+                        SetOpt::Add(val) => self.checked_add(to_num(val)?),
+                        SetOpt::Sub(val) => self.checked_sub(to_num(val)?),
+                        op => {
+                            let msg = format!("invalid argument {}", op.to_arg(name));
+                            err_at!(Invalid, msg: msg)?
+                        }
+                    };
+                    *self = match val {
+                        Some(val) => val,
+                        None => {
+                            let msg = format!("out of range {}", op.to_arg(name));
+                            err_at!(Invalid, msg: msg)?
+                        }
+                    };
+                    Ok(())
+                }
+            }
+        )*
+    );
+}
+
+opt_number![u8, u16, u32, u64, usize];
+
+impl OptValue for char {
+    fn to_opt_arg(&self, name: &str) -> String {
+        format!("{}={}", name, self)
+    }
+
+    fn set_opt(&mut self, name: &str, op: &SetOpt) -> Result<()> {
+        *self = match op {
+            SetOpt::Show => *self,
+            SetOpt::Assign(val) if val.chars().count() == 1 => val.chars().next().unwrap(),
+            op => err_at!(Invalid, msg: format!("invalid argument {}", op.to_arg(name)))?,
+        };
+        Ok(())
+    }
+}
+
+impl OptValue for String {
+    fn to_opt_arg(&self, name: &str) -> String {
+        format!("{}={}", name, self)
+    }
+
+    fn set_opt(&mut self, name: &str, op: &SetOpt) -> Result<()> {
+        match op {
+            SetOpt::Show => (),
+            SetOpt::Assign(val) => *self = val.clone(),
+            SetOpt::Add(val) => self.push_str(val),
+            SetOpt::Sub(val) => *self = self.replacen(val.as_str(), "", 1),
+            op => err_at!(Invalid, msg: format!("invalid argument {}", op.to_arg(name)))?,
+        };
+        Ok(())
+    }
+}
+
 struct ConfigFile(ffi::OsString);
 
 impl From<String> for ConfigFile {
@@ -176,10 +437,7 @@ lazy_static! {
 pub fn read_config(toml_file: Option<String>, ftype: Option<String>) -> Result<toml::Value> {
     let mut files: Vec<path::PathBuf> = TOML_FILES.clone();
     if let Some(ftype) = ftype {
-        for ftypes_dir in FTYPES_DIRS.clone().into_iter() {
-            let cf: ConfigFile = ftype.clone().into();
-            files.push([ftypes_dir, cf.into()].iter().collect());
-        }
+        files.extend(to_ftype_files(&ftype));
     }
 
     if let Some(toml_file) = toml_file {
@@ -187,6 +445,29 @@ pub fn read_config(toml_file: Option<String>, ftype: Option<String>) -> Result<t
         files.push(err_at!(IOError, fs::canonicalize(&toml_file))?);
     }
 
+    read_files(files)
+}
+
+/// Read file-type specific configuration, only, from:
+///
+/// * ~/.ted/<ftypes>/<ftype>.toml
+///
+pub fn read_ftype_config(ftype: &str) -> Result<toml::Value> {
+    let files = to_ftype_files(ftype);
+    let iter = files.into_iter().filter(|fl| path::Path::new(fl).exists());
+    read_files(iter.collect())
+}
+
+fn to_ftype_files(ftype: &str) -> Vec<path::PathBuf> {
+    let mut files = vec![];
+    for ftypes_dir in FTYPES_DIRS.clone().into_iter() {
+        let cf: ConfigFile = ftype.to_string().into();
+        files.push([ftypes_dir, cf.into()].iter().collect());
+    }
+    files
+}
+
+fn read_files(files: Vec<path::PathBuf>) -> Result<toml::Value> {
     let mut config: toml::map::Map<String, toml::Value> = Default::default();
     for fl in files.into_iter() {
         if !path::Path::new(&fl).exists() {
@@ -260,3 +541,7 @@ pub fn to_section(mut config: toml::Value, section: &str) -> toml::Value {
     }
     config
 }
+
+#[cfg(test)]
+#[path = "config_test.rs"]
+mod config_test;
@@ -0,0 +1,118 @@
+use std::{convert::TryInto, str::FromStr};
+
+use super::*;
+
+config![
+    (wrap, bool, true),
+    (scroll_off, u16, 0),
+    (margin_char, char, '|'),
+    (color_scheme, String, "default".to_string())
+];
+
+#[test]
+fn test_set_opt_parse() {
+    let config = Config::default();
+    let parse = |arg: &str| SetOpt::parse(arg, |name| config.is_bool_option(name));
+
+    let name = "wrap".to_string();
+    assert_eq!(parse("wrap").unwrap(), (name.clone(), SetOpt::On));
+    assert_eq!(parse("nowrap").unwrap(), (name.clone(), SetOpt::Off));
+    assert_eq!(parse("invwrap").unwrap(), (name.clone(), SetOpt::Toggle));
+    assert_eq!(parse("wrap!").unwrap(), (name.clone(), SetOpt::Toggle));
+    assert_eq!(parse("wrap?").unwrap(), (name.clone(), SetOpt::Show));
+
+    let name = "scroll_off".to_string();
+    assert_eq!(parse("scroll_off").unwrap(), (name.clone(), SetOpt::Show));
+    let op = SetOpt::Assign("4".to_string());
+    assert_eq!(parse("scroll_off=4").unwrap(), (name.clone(), op));
+    let op = SetOpt::Add("2".to_string());
+    assert_eq!(parse("scroll_off+=2").unwrap(), (name.clone(), op));
+    let op = SetOpt::Sub("1".to_string());
+    assert_eq!(parse("scroll_off-=1").unwrap(), (name.clone(), op));
+    let op = SetOpt::Assign("a b\\".to_string());
+    assert_eq!(
+        parse("color_scheme=a\\ b\\\\").unwrap(),
+        ("color_scheme".to_string(), op)
+    );
+
+    assert!(parse("noscroll_off").is_err());
+    assert!(parse("scroll_off!").is_err());
+    assert!(parse("nosuch").is_err());
+    assert!(parse("nosuch=1").is_err());
+}
+
+#[test]
+fn test_set_option() {
+    let mut config = Config::default();
+
+    config.set_option("wrap", &SetOpt::Toggle).unwrap();
+    assert_eq!(config.wrap, false);
+    assert_eq!(config.to_option("wrap").unwrap(), "nowrap");
+    config.set_option("wrap", &SetOpt::On).unwrap();
+    assert_eq!(config.to_option("wrap").unwrap(), "wrap");
+    assert!(config
+        .set_option("wrap", &SetOpt::Assign("1".to_string()))
+        .is_err());
+
+    config
+        .set_option("scroll_off", &SetOpt::Assign("4".to_string()))
+        .unwrap();
+    config
+        .set_option("scroll_off", &SetOpt::Add("2".to_string()))
+        .unwrap();
+    config
+        .set_option("scroll_off", &SetOpt::Sub("1".to_string()))
+        .unwrap();
+    assert_eq!(config.to_option("scroll_off").unwrap(), "scroll_off=5");
+    assert!(config
+        .set_option("scroll_off", &SetOpt::Assign("x".to_string()))
+        .is_err());
+    assert!(config
+        .set_option("scroll_off", &SetOpt::Sub("6".to_string()))
+        .is_err());
+    assert_eq!(config.scroll_off, 5);
+
+    config
+        .set_option("margin_char", &SetOpt::Assign(":".to_string()))
+        .unwrap();
+    assert_eq!(config.margin_char, ':');
+    assert!(config
+        .set_option("margin_char", &SetOpt::Assign("ab".to_string()))
+        .is_err());
+
+    let (add, sub) = (
+        SetOpt::Add("-dark".to_string()),
+        SetOpt::Sub("de".to_string()),
+    );
+    config.set_option("color_scheme", &add).unwrap();
+    config.set_option("color_scheme", &sub).unwrap();
+    assert_eq!(config.color_scheme, "fault-dark");
+
+    assert!(config.set_option("nosuch", &SetOpt::On).is_err());
+    assert_eq!(
+        Config::to_option_names(),
+        vec!["wrap", "scroll_off", "margin_char", "color_scheme"]
+    );
+}
+
+#[test]
+fn test_copy_option() {
+    let mut local = Config::default();
+    local.set_option("wrap", &SetOpt::Off).unwrap();
+    local
+        .set_option("scroll_off", &SetOpt::Assign("3".to_string()))
+        .unwrap();
+
+    let mut layer = ConfigToml::default();
+    layer.copy_option("wrap", &local).unwrap();
+    assert!(layer.copy_option("nosuch", &local).is_err());
+
+    let config = Config::default().mixin(layer);
+    assert_eq!(config.wrap, false);
+    assert_eq!(config.scroll_off, 0);
+
+    let mut config = Config::default();
+    config.copy_option("scroll_off", &local).unwrap();
+    assert_eq!(config.scroll_off, 3);
+    assert_eq!(config.wrap, true);
+}
@@ -13,9 +13,11 @@ use std::{fmt, iter::FromIterator, mem, result};
 
 use crate::{
     buffer::{self, Buffer},
+    config::{Scope, SetOpt},
+    keymap::MapMode,
     mark,
     pubsub::Notify,
-    window::{WinBuffer, WindowLess, WindowPrompt},
+    window::{WinBuffer, WindowHex, WindowLess, WindowPrompt},
     Error, Result,
 };
 
@@ -49,6 +51,8 @@ pub enum Event {
     J(char),      // jump prefix (['`],)
     Z(usize),     // scroll prefix (n,)
     M,            // mark prefix
+    R,            // register prefix
+    Q,            // quit prefix ('Z')
     Op(Opr),      // Operation  (op-event)
     // folded events for buffer management.
     Mt(Mto),        // Motion     (n, motion-event)
@@ -60,6 +64,8 @@ pub enum Event {
     // other events
     Appn(Appn),
     JumpFrom(usize), // (cursor,)
+    Timeout,         // no key within timeout, while resolving a mapping
+    Quit,            // quit the editor
     // local events
     Edit(Edit),
     List(Vec<Event>),
@@ -82,7 +88,7 @@ impl Event {
             Home(m) | End(m) | PageUp(m) | PageDown(m) => m,
             BackTab | Esc => empty,
             // prefix events
-            N(_) | G(_) | B(_, _) | F(_, _) | T(_, _) | M | J(_) | Z(_) => empty,
+            N(_) | G(_) | B(_, _) | F(_, _) | T(_, _) | M | R | Q | J(_) | Z(_) => empty,
             Op(op) => op.to_modifiers(),
             // folded events for buffer management.
             Mr(_) => empty,
@@ -91,7 +97,7 @@ impl Event {
             Wr(cud) => cud.to_modifiers(),
             TabInsert(_) | TabClear => empty,
             // other events
-            Appn(_) | JumpFrom(_) => empty,
+            Appn(_) | JumpFrom(_) | Timeout | Quit => empty,
             // local events
             Edit(_) | List(_) | Notify(_) | Noop => empty,
         }
@@ -106,6 +112,20 @@ impl Event {
         }
     }
 
+    /// Return whether the event is a keystroke, as received from terminal.
+    pub fn is_key(&self) -> bool {
+        use Event::*;
+
+        match self {
+            Backspace(_) | Enter(_) | Tab(_) | Delete(_) => true,
+            Char(_, _) | FKey(_, _) | InsKey(_) => true,
+            Left(_) | Right(_) | Up(_) | Down(_) => true,
+            Home(_) | End(_) | PageUp(_) | PageDown(_) => true,
+            BackTab | Esc => true,
+            _ => false,
+        }
+    }
+
     /// Return whether the event is to enter insert/append/open/replace mode.
     /// In short, whether shift to insert-mode.
     pub fn is_insert(&self) -> bool {
@@ -252,6 +272,8 @@ impl fmt::Display for Event {
             F(n, dp) => write!(f, "f({},{})", n, dp),
             T(n, dp) => write!(f, "t({},{})", n, dp),
             M => write!(f, "m"),
+            R => write!(f, "r"),
+            Q => write!(f, "q"),
             J(ch) => write!(f, "j({})", ch),
             Z(n) => write!(f, "z({})", n),
             Op(opr) => write!(f, "op({})", opr),
@@ -265,6 +287,8 @@ impl fmt::Display for Event {
             // other events
             Appn(cd) => write!(f, "Appn({})", cd),
             JumpFrom(cursor) => write!(f, "jump-from({})", cursor),
+            Timeout => write!(f, "timeout"),
+            Quit => write!(f, "quit"),
             // local events
             Edit(val) => write!(f, "edit({})", val),
             List(es) => write!(f, "list({})", es.len()),
@@ -274,6 +298,12 @@ impl fmt::Display for Event {
     }
 }
 
+impl fmt::Debug for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
 impl From<TermEvent> for Event {
     fn from(evnt: TermEvent) -> Event {
         match evnt {
@@ -602,8 +632,10 @@ pub enum Cud {
     RemoveLine,
     NextWord,
     PrevWord,
-    RShift(usize), // (n,)
-    LShift(usize), // (n,)
+    RShift(usize),  // (n,)
+    LShift(usize),  // (n,)
+    Register(char), // insert register's content (register,)
+    CursorWord,     // insert the word under the cursor
 }
 
 impl fmt::Display for Cud {
@@ -621,6 +653,8 @@ impl fmt::Display for Cud {
             Cud::PrevWord => write!(f, "prev-line"),
             Cud::RShift(n) => write!(f, "rshift({})", n),
             Cud::LShift(n) => write!(f, "lshift({})", n),
+            Cud::Register(reg) => write!(f, "register({})", reg),
+            Cud::CursorWord => write!(f, "cursor-word"),
         }
     }
 }
@@ -641,6 +675,7 @@ pub enum Scroll {
     TextUp,
     TextCenter,
     TextBottom,
+    Follow,
     // horizontal scrolls
     Chars,
     Slide,
@@ -657,6 +692,7 @@ impl fmt::Display for Scroll {
             Scroll::TextUp => write!(f, "text-up"),
             Scroll::TextCenter => write!(f, "text-center"),
             Scroll::TextBottom => write!(f, "text-bottom"),
+            Scroll::Follow => write!(f, "follow"),
             Scroll::Chars => write!(f, "chars"),
             Scroll::Slide => write!(f, "slide"),
             Scroll::Align => write!(f, "align"),
@@ -703,10 +739,12 @@ pub enum Mto {
     UnmatchPair(usize, char, DP), // (n, marker, Left/Right)
     // jumps and marks
     Jump(char, char), // (['`], [a-zA-Z0-9])
+    Jumps(usize, DP), // jump-list (n, Left/Right)
 
     Bracket(usize, char, char, DP),     // (n, yin, yan, Left/Right)
     Pattern(usize, Option<String>, DP), // (n, pattern, Left/Right)
     PatternR(usize, DP),                // repeat pattern (n, Left/Right)
+    PatternW(usize, bool, DP),          // word under cursor (n, whole, Left/Right)
     None,
 }
 
@@ -753,6 +791,7 @@ impl fmt::Display for Mto {
                 write!(f, "unmatch-pair({},{},{})", n, ch, dir /* for exprs */)
             }
             Mto::Jump(typ, ch) => write!(f, "mark-jump({},{})", typ, ch),
+            Mto::Jumps(n, dp) => write!(f, "jumps({},{})", n, dp),
 
             Mto::Bracket(n, ch1, ch2, dp) => {
                 //
@@ -760,6 +799,7 @@ impl fmt::Display for Mto {
             }
             Mto::Pattern(n, _, dp) => write!(f, "pattern({},{})", n, dp),
             Mto::PatternR(n, dp) => write!(f, "patternr({},{})", n, dp),
+            Mto::PatternW(n, w, dp) => write!(f, "patternw({},{},{})", n, w, dp),
             Mto::None => write!(f, "none"),
         }
     }
@@ -789,20 +829,129 @@ impl Mto {
 #[derive(Clone, Eq, PartialEq)]
 pub enum Appn {
     Less(Box<WindowLess>),
+    Hex(Box<WindowHex>),
     Prompt(Box<WindowPrompt>),
     StatusFile,
     StatusCursor,
+    /// Edit file, `:edit [file]`, None to re-edit the current buffer.
+    /// Flag is true to discard modifications.
+    Edit(Option<String>, bool),
+    /// Switch to buffer by its number or name, None for alternate buffer.
+    Buffer(Option<String>),
+    /// Switch to n-th next buffer, DP::Right, or previous, DP::Left.
+    BufferRotate(usize, DP),
+    /// Delete buffer by its number or name, None for the current buffer.
+    /// Flag is true to discard modifications.
+    BufferDelete(Option<String>, bool),
+    /// List all buffers.
+    Buffers,
+    /// Pick a file, using fuzzy finder, to edit.
+    Finder,
+    /// Write current buffer, `:[range]write[!] [>>] [file]`. If `update`
+    /// is true, write only when the buffer is modified.
+    Write {
+        range: Option<String>,
+        file: Option<String>,
+        append: bool,
+        force: bool,
+        update: bool,
+    },
+    /// Save current buffer as file, and edit that file.
+    SaveAs(String, bool),
+    /// Write all modified buffers.
+    WriteAll(bool),
+    /// Insert file, or shell command's output if `shell` is true, below
+    /// line, `:[line]read [file]` or `:[line]read !{cmd}`.
+    Read {
+        range: Option<String>,
+        file: Option<String>,
+        shell: bool,
+    },
+    /// Substitute pattern in lines, `:[range]s/{pattern}/{string}/[flags]`,
+    /// refer [crate::search::Substitute] for `args`.
+    Substitute {
+        range: Option<String>,
+        args: String,
+    },
+    /// Quit application, flag is true to discard modified buffers.
+    Quit(bool),
+    /// Save editing state to session file, `:mksession[!] [file]`. Flag
+    /// is true to overwrite an existing file.
+    MkSession(Option<String>, bool),
+    /// File operation in directory listing.
+    FileOp(FileOp),
+    /// Set options, `:set [arg...]`, `:setlocal [arg...]` or
+    /// `:setglobal [arg...]`.
+    Set(Scope, Vec<String>),
+    /// Apply `:set` operation on application-state's option.
+    StateOption(String, SetOpt),
+    /// Add mapping, `:nmap {lhs} {rhs}` and its variants. Without `rhs`
+    /// list mappings starting with `lhs`, without `lhs` list all mappings.
+    Map {
+        mode: MapMode,
+        noremap: bool,
+        lhs: Option<String>,
+        rhs: Option<String>,
+    },
+    /// Remove mapping, `:unmap {lhs}` and its variants.
+    Unmap(MapMode, String),
 }
 
 impl fmt::Display for Appn {
     fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
-        use Appn::{Less, Prompt, StatusCursor, StatusFile};
+        use Appn::{Buffer, BufferDelete, BufferRotate, Buffers, Edit, Finder};
+        use Appn::{FileOp, Map, MkSession, Unmap, Write, WriteAll};
+        use Appn::{Hex, Less, Prompt, Quit, Read, SaveAs, Set, StateOption};
+        use Appn::{StatusCursor, StatusFile, Substitute};
 
         match self {
             Less(_) => write!(f, "less"),
+            Hex(_) => write!(f, "hex"),
             Prompt(_) => write!(f, "prompt"),
             StatusFile => write!(f, "status_file"),
             StatusCursor => write!(f, "status_cursor"),
+            Edit(file, force) => write!(f, "edit({:?},{})", file, force),
+            Buffer(name) => write!(f, "buffer({:?})", name),
+            BufferRotate(n, dp) => write!(f, "buffer_rotate({},{})", n, dp),
+            BufferDelete(name, force) => write!(f, "buffer_delete({:?},{})", name, force),
+            Buffers => write!(f, "buffers"),
+            Finder => write!(f, "finder"),
+            Write { file, force, .. } => write!(f, "write({:?},{})", file, force),
+            SaveAs(file, force) => write!(f, "saveas({:?},{})", file, force),
+            WriteAll(force) => write!(f, "write_all({})", force),
+            Read { file, shell, .. } => write!(f, "read({:?},{})", file, shell),
+            Substitute { range, args } => write!(f, "substitute({:?},{})", range, args),
+            Quit(force) => write!(f, "quit({})", force),
+            MkSession(file, force) => write!(f, "mksession({:?},{})", file, force),
+            FileOp(op) => write!(f, "file_op({})", op),
+            Set(scope, args) => write!(f, "set({:?},{})", scope, args.join(" ")),
+            StateOption(name, op) => write!(f, "state_option({},{:?})", name, op),
+            Map { mode, lhs, rhs, .. } => write!(f, "map({:?},{:?},{:?})", mode, lhs, rhs),
+            Unmap(mode, lhs) => write!(f, "unmap({:?},{})", mode, lhs),
+        }
+    }
+}
+
+/// File operation in directory listing, file names are relative to the
+/// listed directory. `Rename` and `Delete` apply on the entry under cursor.
+#[derive(Clone, Eq, PartialEq)]
+pub enum FileOp {
+    /// Create file, or directory if flag is true, `:mkfile {file}` and
+    /// `:mkdir {dir}`.
+    Create(String, bool),
+    /// Rename entry, `:rename {file}`.
+    Rename(String),
+    /// Delete entry, `:remove`, directories are deleted along with their
+    /// content.
+    Delete,
+}
+
+impl fmt::Display for FileOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        match self {
+            FileOp::Create(file, dir) => write!(f, "create({:?},{})", file, dir),
+            FileOp::Rename(file) => write!(f, "rename({:?})", file),
+            FileOp::Delete => write!(f, "delete"),
         }
     }
 }
@@ -0,0 +1,169 @@
+//! Module implement fuzzy matching, used for picking files by typing a
+//! few characters from their path.
+//!
+//! Pattern characters should appear in the same order in the text, not
+//! necessarily next to each other. Among all such alignments the one with
+//! the best score is picked, scoring favours:
+//!
+//! * consecutive characters, `fmod` matches `fuzzy/mod.rs` better than
+//!   `f/u/m/o/d.rs`.
+//! * characters at the start of a word, that is, after `/`, `_`, `-`, `.`,
+//!   white-space, or an upper-case letter following a lower-case letter.
+//! * fewer, and shorter, gaps between matching characters.
+//!
+//! Matching ignores case, unless the pattern contains an upper-case
+//! character.
+
+use std::cmp;
+
+/// Score for every matching character.
+const SCORE_MATCH: isize = 16;
+/// Bonus for a character matching next to the previous match.
+const BONUS_CONSECUTIVE: isize = 16;
+/// Bonus for a character matching at the start of a word.
+const BONUS_BOUNDARY: isize = 12;
+/// Penalty for every character skipped between two matches.
+const PENALTY_GAP: isize = 2;
+/// Penalty for every character skipped before the first match, limited to
+/// `PENALTY_LEADING_MAX`.
+const PENALTY_LEADING: isize = 1;
+const PENALTY_LEADING_MAX: isize = 8;
+
+/// Result of matching a pattern with text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match {
+    /// Higher the score better the match.
+    pub score: isize,
+    /// Character index, into the text, of every matching character.
+    pub positions: Vec<usize>,
+}
+
+/// Match `pattern` with `text`, return None if `text` does not contain all
+/// the characters of `pattern` in the same order. Empty pattern matches
+/// any text.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<Match> {
+    let smart_case = pattern.chars().any(char::is_uppercase);
+    let fold = |ch: char| -> char {
+        match smart_case {
+            true => ch,
+            false => ch.to_lowercase().next().unwrap_or(ch),
+        }
+    };
+
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    let chars: Vec<char> = text.chars().collect();
+    let folded: Vec<char> = chars.iter().map(|ch| fold(*ch)).collect();
+
+    if pattern.len() == 0 {
+        return Some(Match {
+            score: 0,
+            positions: vec![],
+        });
+    }
+
+    // quick check, most of the texts won't match.
+    {
+        let mut iter = folded.iter();
+        if !pattern.iter().all(|p| iter.any(|ch| ch == p)) {
+            return None;
+        }
+    }
+
+    let (n, m) = (pattern.len(), chars.len());
+    let bonus: Vec<isize> = (0..m).map(|j| to_bonus(&chars, j)).collect();
+
+    // scores[i][j] is the best score with pattern[..=i] aligned such that
+    // pattern[i] matches text[j], froms[i][j] is the position of
+    // pattern[i-1] in that alignment.
+    let mut scores: Vec<Vec<Option<isize>>> = vec![vec![None; m]; n];
+    let mut froms: Vec<Vec<usize>> = vec![vec![0; m]; n];
+
+    for j in 0..m {
+        if folded[j] == pattern[0] {
+            let leading = cmp::min(j as isize * PENALTY_LEADING, PENALTY_LEADING_MAX);
+            scores[0][j] = Some(SCORE_MATCH + bonus[j] - leading);
+        }
+    }
+
+    for i in 1..n {
+        // best (score, position) for pattern[i-1], before text[j-1],
+        // including the penalty for the gap till text[j].
+        let mut gapped: Option<(isize, usize)> = None;
+        for j in i..m {
+            if j >= 2 {
+                gapped = match (gapped, scores[i - 1][j - 2]) {
+                    (Some((a, k)), Some(b)) if a >= b => Some((a - PENALTY_GAP, k)),
+                    (_, Some(b)) => Some((b - PENALTY_GAP, j - 2)),
+                    (Some((a, k)), None) => Some((a - PENALTY_GAP, k)),
+                    (None, None) => None,
+                };
+            }
+            if folded[j] != pattern[i] {
+                continue;
+            }
+            let consecutive = scores[i - 1][j - 1].map(|s| (s + BONUS_CONSECUTIVE, j - 1));
+            let best = match (consecutive, gapped) {
+                (Some(a), Some(b)) if a.0 >= b.0 => Some(a),
+                (Some(_), Some(b)) => Some(b),
+                (a, None) => a,
+                (None, b) => b,
+            };
+            if let Some((score, k)) = best {
+                scores[i][j] = Some(score + SCORE_MATCH + bonus[j]);
+                froms[i][j] = k;
+            }
+        }
+    }
+
+    // pick the best alignment for the last pattern character, and trace
+    // back the positions of the previous ones.
+    let (score, mut j) = {
+        let iter = scores[n - 1].iter().enumerate();
+        let iter = iter.filter_map(|(j, s)| s.map(|s| (s, j)));
+        iter.max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))?
+    };
+    let mut positions = vec![j];
+    for i in (1..n).rev() {
+        j = froms[i][j];
+        positions.push(j);
+    }
+    positions.reverse();
+
+    Some(Match { score, positions })
+}
+
+/// Match `pattern` with every item in `items`, return index of matching
+/// items along with the match, best match first. Items with same score
+/// are ordered by their length, and then by their index.
+pub fn rank<S>(pattern: &str, items: &[S]) -> Vec<(usize, Match)>
+where
+    S: AsRef<str>,
+{
+    let mut matches: Vec<(usize, Match)> = {
+        let iter = items.iter().enumerate();
+        iter.filter_map(|(i, item)| Some((i, fuzzy_match(pattern, item.as_ref())?)))
+            .collect()
+    };
+    matches.sort_by(|(a, x), (b, y)| {
+        let (p, q) = (items[*a].as_ref().len(), items[*b].as_ref().len());
+        y.score.cmp(&x.score).then(p.cmp(&q)).then(a.cmp(b))
+    });
+    matches
+}
+
+fn to_bonus(chars: &[char], j: usize) -> isize {
+    match j {
+        0 => BONUS_BOUNDARY,
+        j => match (chars[j - 1], chars[j]) {
+            ('/', _) | ('\\', _) => BONUS_BOUNDARY,
+            ('_', _) | ('-', _) | ('.', _) => BONUS_BOUNDARY,
+            (prev, _) if prev.is_whitespace() => BONUS_BOUNDARY,
+            (prev, ch) if prev.is_lowercase() && ch.is_uppercase() => BONUS_BOUNDARY,
+            _ => 0,
+        },
+    }
+}
+
+#[cfg(test)]
+#[path = "fuzzy_test.rs"]
+mod fuzzy_test;
@@ -0,0 +1,53 @@
+use super::*;
+
+#[test]
+fn test_fuzzy_match() {
+    let m = fuzzy_match("", "src/lib.rs").unwrap();
+    assert_eq!(m.score, 0);
+    assert_eq!(m.positions, Vec::<usize>::new());
+
+    assert_eq!(fuzzy_match("xyz", "src/lib.rs"), None);
+    assert_eq!(fuzzy_match("bil", "src/lib.rs"), None);
+
+    let m = fuzzy_match("lib", "src/lib.rs").unwrap();
+    assert_eq!(m.positions, vec![4, 5, 6]);
+
+    // consecutive match is preferred over the earlier scattered match.
+    let m = fuzzy_match("mod", "src/main_old/mod.rs").unwrap();
+    assert_eq!(m.positions, vec![13, 14, 15]);
+
+    // word boundaries are preferred.
+    let m = fuzzy_match("wp", "src/window_prompt.rs").unwrap();
+    assert_eq!(m.positions, vec![4, 11]);
+    let m = fuzzy_match("cc", "src/CodeCmd.rs").unwrap();
+    assert_eq!(m.positions, vec![4, 8]);
+
+    // smart case.
+    assert!(fuzzy_match("readme", "README.md").is_some());
+    assert!(fuzzy_match("README", "README.md").is_some());
+    assert_eq!(fuzzy_match("Readme", "README.md"), None);
+
+    // positions are character indices.
+    let m = fuzzy_match("ab", "ñ/ab").unwrap();
+    assert_eq!(m.positions, vec![2, 3]);
+}
+
+#[test]
+fn test_rank() {
+    let items = vec![
+        "src/code/window_file.rs",
+        "src/window_hex.rs",
+        "src/window.rs",
+        "README.md",
+        "src/code/mod.rs",
+    ];
+
+    let ranks: Vec<usize> = rank("win", &items).into_iter().map(|(i, _)| i).collect();
+    assert_eq!(ranks, vec![2, 1, 0]);
+
+    let ranks: Vec<usize> = rank("wfile", &items).into_iter().map(|(i, _)| i).collect();
+    assert_eq!(ranks, vec![0]);
+
+    let ranks: Vec<usize> = rank("", &items).into_iter().map(|(i, _)| i).collect();
+    assert_eq!(ranks, vec![3, 2, 4, 1, 0]);
+}
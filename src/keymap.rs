@@ -2,7 +2,9 @@ use crate::{buffer::Buffer, event::Event, Error, Result};
 
 pub use crate::keymap_cmd::KeyCmd;
 pub use crate::keymap_edit::KeyEdit;
+pub use crate::keymap_hex::KeyHex;
 pub use crate::keymap_less::KeyLess;
+pub use crate::keymap_map::{parse_keys, to_key_notation, MapMode, Mapping, Mappings};
 
 trait Keymapper {
     fn fold(&mut self, buf: &Buffer, evnt: Event) -> Result<Event>;
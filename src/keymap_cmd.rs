@@ -1,17 +1,154 @@
 #[allow(unused_imports)]
 use log::trace;
 
-use crate::{buffer::Buffer, event::Event, Result};
+use std::mem;
 
+use crate::{
+    buffer::{self, Buffer},
+    event::{Cud, Event, Mto, DP},
+    window::WinBuffer,
+    Result,
+};
+
+/// Keymap for command-line. The first character in the command-line
+/// buffer is the leader, like `:` or `/`, which is never edited or moved
+/// over by the following keys:
+///
+/// * `Ctrl-B`, `Home` move to the beginning of command-line.
+/// * `Ctrl-E`, `End` move to the end of command-line.
+/// * `Ctrl-Left`, `Ctrl-Right` move one WORD left and right.
+/// * `Ctrl-W` delete the word before the cursor.
+/// * `Ctrl-U` delete all characters before the cursor.
+/// * `Ctrl-R {reg}` insert the content of a read-only register, `:` last
+///   command, `/` last search pattern, `%` current file name and `#`
+///   alternate file name. Yanked text is not kept in registers.
+/// * `Ctrl-R Ctrl-W` insert the word under the cursor in edit window.
+/// * `Backspace` on an empty command-line cancels it.
 #[derive(Clone, Default)]
-pub struct KeyCmd;
+pub struct KeyCmd {
+    prefix: Event,
+}
 
 impl KeyCmd {
-    pub fn fold(&mut self, _: &Buffer, evnt: Event) -> Result<Event> {
+    pub fn fold(&mut self, buf: &Buffer, evnt: Event) -> Result<Event> {
+        use crate::event::Event::*;
+
+        let prefix = mem::replace(&mut self.prefix, Event::default());
+        let (empty, ctrl, shift) = {
+            use crossterm::event::KeyModifiers as KM;
+            let m = evnt.to_modifiers();
+            (m.is_empty(), m.contains(KM::CONTROL), m.contains(KM::SHIFT))
+        };
+
+        let cursor = buf.to_char_cursor();
+        let chars: Vec<char> = buf.to_string().chars().collect();
+
+        let (prefix, evnt) = match prefix {
+            Event::Noop if empty | shift => match evnt {
+                Char(ch, m) if ch.is_control() => (Noop, Char(ch, m)),
+                Char(ch, _) => (Noop, Wr(Cud::Char(ch))),
+                Backspace(_) if chars.len() <= 1 => (Noop, Esc),
+                Backspace(_) if cursor > 1 => (Noop, Wr(Cud::Backspace(1))),
+                Backspace(_) => (Noop, Noop),
+                Delete(_) => (Noop, Wr(Cud::Delete(1))),
+                Left(_) if cursor > 1 => (Noop, Mt(Mto::Left(1, DP::None))),
+                Left(_) => (Noop, Noop),
+                Right(_) => (Noop, Mt(Mto::Right(1, DP::None))),
+                Home(_) => (Noop, to_home(cursor)),
+                End(_) => (Noop, to_end(cursor, chars.len())),
+                evnt => (Noop, evnt),
+            },
+            Event::Noop if ctrl => match evnt {
+                Char('h', _) if cursor > 1 => (Noop, Wr(Cud::Backspace(1))),
+                Char('b', _) => (Noop, to_home(cursor)),
+                Char('e', _) => (Noop, to_end(cursor, chars.len())),
+                Char('w', _) => {
+                    let n = cursor.saturating_sub(word_left(&chars, cursor, true));
+                    (Noop, if_else!(n > 0, Wr(Cud::Backspace(n)), Noop))
+                }
+                Char('u', _) if cursor > 1 => (Noop, Wr(Cud::Backspace(cursor - 1))),
+                Char('r', _) => (R, Noop),
+                Left(_) => {
+                    let n = cursor.saturating_sub(word_left(&chars, cursor, false));
+                    (Noop, if_else!(n > 0, Mt(Mto::Left(n, DP::None)), Noop))
+                }
+                Right(_) => {
+                    let n = word_right(&chars, cursor).saturating_sub(cursor);
+                    (Noop, Mt(Mto::Right(n, DP::None)))
+                }
+                Char(_, _) => (Noop, Noop),
+                evnt => (Noop, evnt),
+            },
+            R if ctrl => match evnt {
+                Char('w', _) => (Noop, Wr(Cud::CursorWord)),
+                _ => (Noop, Noop),
+            },
+            R => match evnt {
+                Char(reg, _) => (Noop, Wr(Cud::Register(reg))),
+                _ => (Noop, Noop),
+            },
+            prefix => (prefix, evnt),
+        };
+
+        trace!("cmd prefix:{} event:{}", prefix, evnt);
+
+        self.prefix = prefix;
         Ok(evnt)
     }
 
     pub fn to_event_prefix(&self) -> Event {
-        Event::Noop
+        self.prefix.clone()
+    }
+}
+
+fn to_home(cursor: usize) -> Event {
+    Event::Mt(Mto::Left(cursor.saturating_sub(1), DP::None))
+}
+
+fn to_end(cursor: usize, n_chars: usize) -> Event {
+    Event::Mt(Mto::Right(n_chars.saturating_sub(cursor), DP::None))
+}
+
+// Start of the word before cursor. If `keyword` is true, keywords and
+// other non-blank characters form separate words, else all non-blank
+// characters form a WORD. Leader is never part of the word.
+fn word_left(chars: &[char], cursor: usize, keyword: bool) -> usize {
+    let mut off = cursor;
+    while off > 1 && chars[off - 1].is_whitespace() {
+        off -= 1;
+    }
+    match off {
+        off if off <= 1 => 1,
+        _ if keyword && buffer::is_keyword(chars[off - 1]) => {
+            while off > 1 && buffer::is_keyword(chars[off - 1]) {
+                off -= 1;
+            }
+            off
+        }
+        _ if keyword => {
+            let word = |ch: char| !ch.is_whitespace() && !buffer::is_keyword(ch);
+            while off > 1 && word(chars[off - 1]) {
+                off -= 1;
+            }
+            off
+        }
+        _ => {
+            while off > 1 && !chars[off - 1].is_whitespace() {
+                off -= 1;
+            }
+            off
+        }
+    }
+}
+
+// Start of the WORD after cursor, or end of the command-line.
+fn word_right(chars: &[char], cursor: usize) -> usize {
+    let mut off = cursor;
+    while off < chars.len() && !chars[off].is_whitespace() {
+        off += 1;
+    }
+    while off < chars.len() && chars[off].is_whitespace() {
+        off += 1;
     }
+    off
 }
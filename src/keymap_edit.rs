@@ -165,12 +165,17 @@ impl KeyEdit {
                 Char('`', _) => (J('`'), noop),
                 Char('n', _) => (noop, Mt(Mto::PatternR(1, DP::Right))),
                 Char('N', _) => (noop, Mt(Mto::PatternR(1, DP::Left))),
+                Char('*', _) => (noop, Mt(Mto::PatternW(1, true, DP::Right))),
+                Char('#', _) => (noop, Mt(Mto::PatternW(1, true, DP::Left))),
+                // terminals report Ctrl-I as Tab.
+                Tab(_) => (noop, Mt(Mto::Jumps(1, DP::Right))),
                 // prefix commands
                 Char(ch @ '0'..='9', _) => (N(parse_n!(0, ch)), noop),
                 Char('[', _) => (B(1, DP::Left), noop),
                 Char(']', _) => (B(1, DP::Right), noop),
                 Char('g', _) => (G(1), noop),
                 Char('z', _) => (Z(0), noop),
+                Char('Z', _) => (Q, noop),
                 // operation prefix
                 Char('c', _) => (Op(event::Opr::Change(1, Mto::None)), noop),
                 Char('d', _) => (Op(event::Opr::Delete(1, Mto::None)), noop),
@@ -191,6 +196,14 @@ impl KeyEdit {
                 evnt => (noop, evnt),
             },
             Event::Noop if ctrl => match evnt {
+                // alternate buffer, terminals report Ctrl-^ as Ctrl-6.
+                Char('^', _) | Char('6', _) => {
+                    let evnt = Event::Appn(event::Appn::Buffer(None));
+                    (noop, evnt)
+                }
+                // jump-list, older and newer positions.
+                Char('o', _) => (noop, Mt(Mto::Jumps(1, DP::Left))),
+                Char('i', _) => (noop, Mt(Mto::Jumps(1, DP::Right))),
                 // motion commands, window scroll.
                 Char('g', _) => {
                     let evnt = Event::Appn(event::Appn::StatusFile);
@@ -272,6 +285,10 @@ impl KeyEdit {
                 Char('`', _) => (J('`'), noop),
                 Char('n', _) => (noop, Mt(Mto::PatternR(n, DP::Right))),
                 Char('N', _) => (noop, Mt(Mto::PatternR(n, DP::Left))),
+                Char('*', _) => (noop, Mt(Mto::PatternW(n, true, DP::Right))),
+                Char('#', _) => (noop, Mt(Mto::PatternW(n, true, DP::Left))),
+                // terminals report Ctrl-I as Tab.
+                Tab(_) => (noop, Mt(Mto::Jumps(n, DP::Right))),
                 // prefix commands
                 Char(ch @ '0'..='9', _) => (N(parse_n!(n, ch)), noop),
                 Char('[', _) => (B(n, DP::Left), noop),
@@ -297,6 +314,14 @@ impl KeyEdit {
                 evnt => (noop, evnt),
             },
             N(n) if ctrl => match evnt {
+                // edit n-th buffer.
+                Char('^', _) | Char('6', _) => {
+                    let evnt = Event::Appn(event::Appn::Buffer(Some(n.to_string())));
+                    (noop, evnt)
+                }
+                // jump-list, older and newer positions.
+                Char('o', _) => (noop, Mt(Mto::Jumps(n, DP::Left))),
+                Char('i', _) => (noop, Mt(Mto::Jumps(n, DP::Right))),
                 // motion commands, window scroll.
                 Char('g', _) => {
                     let evnt = Event::Appn(event::Appn::StatusFile);
@@ -347,6 +372,9 @@ impl KeyEdit {
                 // motion command - wordwise
                 Char('e', _) => (noop, Mt(Mto::Word(n, DP::Left, DP::Start))),
                 Char('E', _) => (noop, Mt(Mto::WWord(n, DP::Left, DP::Start))),
+                // motion command - searches
+                Char('*', _) => (noop, Mt(Mto::PatternW(n, false, DP::Right))),
+                Char('#', _) => (noop, Mt(Mto::PatternW(n, false, DP::Left))),
 
                 Char('o', _) => (noop, Mt(Mto::Cursor(n))),
                 Char('I', _) => (noop, Md(Mod::Insert(n, DP::TextCol))),
@@ -442,6 +470,23 @@ impl KeyEdit {
                 }
                 evnt => (noop, evnt),
             },
+            Q if empty | shift => match evnt {
+                // write current buffer if modified and quit, or quit
+                // discarding all modifications.
+                Char('Z', _) => {
+                    let mut evnt = Event::Appn(event::Appn::Write {
+                        range: None,
+                        file: None,
+                        append: false,
+                        force: false,
+                        update: true,
+                    });
+                    evnt.push(Event::Appn(event::Appn::Quit(false)));
+                    (noop, evnt)
+                }
+                Char('Q', _) => (noop, Event::Appn(event::Appn::Quit(true))),
+                evnt => (noop, evnt),
+            },
             M if empty => match evnt {
                 Char(ch, _) => match ch {
                     'a'..='z' | 'A'..='Z' | '\'' | '`' => (noop, Mr(ch.into())),
@@ -0,0 +1,123 @@
+#[allow(unused_imports)]
+use log::{debug, trace};
+
+use std::mem;
+
+use crate::{
+    event::{Event, Mod, Mto, Scroll, DP},
+    Result,
+};
+
+macro_rules! parse_n {
+    ($n:expr, $ch:expr) => {{
+        let m = $ch.to_digit(10).unwrap_or(1) as usize;
+        ($n * 10) + m
+    }};
+}
+
+/// Keymap for hex windows. Motions are nibble-wise, hex-digits typed in
+/// replace mode, or after `r`, overwrite the nibble under the cursor and
+/// are passed on as [Event::Char].
+#[derive(Clone, Default)]
+pub struct KeyHex {
+    prefix: Event,
+}
+
+impl KeyHex {
+    pub fn fold(&mut self, replace: bool, evnt: Event) -> Result<Event> {
+        match replace {
+            true => Ok(self.replace_fold(evnt)),
+            false => Ok(self.normal_fold(evnt)),
+        }
+    }
+
+    pub fn to_event_prefix(&self) -> Event {
+        self.prefix.clone()
+    }
+}
+
+impl KeyHex {
+    fn normal_fold(&mut self, evnt: Event) -> Event {
+        use crate::event::Event::*;
+
+        let noop = Event::Noop;
+
+        let prefix = mem::replace(&mut self.prefix, Event::default());
+        let (empty, ctrl, shift) = {
+            use crossterm::event::KeyModifiers as KM;
+            let m = evnt.to_modifiers();
+            (m.is_empty(), m.contains(KM::CONTROL), m.contains(KM::SHIFT))
+        };
+
+        let (n, count) = match &prefix {
+            N(n) => (*n, Some(*n)),
+            _ => (1, None),
+        };
+
+        let (prefix, evnt) = match prefix {
+            Md(Mod::Replace(_, _)) => match evnt {
+                Char(ch, m) if ch.is_digit(16) => (noop, Char(ch, m)),
+                _ => (noop.clone(), noop),
+            },
+            Event::Noop | N(_) if empty | shift => match evnt {
+                // count prefix
+                Char('0', _) if count.is_none() => (noop, Mt(Mto::LineHome(DP::None))),
+                Char(ch @ '0'..='9', _) => (N(parse_n!(count.unwrap_or(0), ch)), noop),
+                // nibble-wise and byte-wise motion
+                Char('h', _) | Left(_) | Backspace(_) => (noop, Mt(Mto::Left(n, DP::None))),
+                Char('l', _) | Char(' ', _) | Right(_) => (noop, Mt(Mto::Right(n, DP::None))),
+                Char('b', _) => (noop, Mt(Mto::Word(n, DP::Left, DP::Start))),
+                Char('w', _) => (noop, Mt(Mto::Word(n, DP::Right, DP::Start))),
+                // row-wise motion
+                Char('k', _) | Up(_) => (noop, Mt(Mto::Up(n, DP::None))),
+                Char('j', _) | Down(_) | Enter(_) => (noop, Mt(Mto::Down(n, DP::None))),
+                Home(_) => (noop, Mt(Mto::LineHome(DP::None))),
+                Char('$', _) | End(_) => (noop, Mt(Mto::LineEnd(n, DP::None))),
+                Char('g', _) => (noop, Mt(Mto::Row(n, DP::None))),
+                Char('G', _) => {
+                    let n = count.unwrap_or(std::usize::MAX);
+                    (noop, Mt(Mto::Row(n, DP::None)))
+                }
+                PageDown(_) => (noop, Mt(Mto::WinScroll(n, Scroll::Pages, DP::Right))),
+                PageUp(_) => (noop, Mt(Mto::WinScroll(n, Scroll::Pages, DP::Left))),
+                // searches
+                Char('/', _) => (noop, Mt(Mto::Pattern(n, None, DP::Right))),
+                Char('?', _) => (noop, Mt(Mto::Pattern(n, None, DP::Left))),
+                Char('n', _) => (noop, Mt(Mto::PatternR(n, DP::Right))),
+                Char('N', _) => (noop, Mt(Mto::PatternR(n, DP::Left))),
+                // editing
+                Char('r', _) => (Md(Mod::Replace(1, DP::None)), noop),
+                Char('R', _) => (noop, Md(Mod::Replace(1, DP::None))),
+                Char(':', m) => (noop, Char(':', m)),
+                _ => (noop.clone(), noop),
+            },
+            Event::Noop | N(_) if ctrl => match evnt {
+                Char('f', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Pages, DP::Right))),
+                Char('b', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Pages, DP::Left))),
+                Char('d', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Lines, DP::Right))),
+                Char('u', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Lines, DP::Left))),
+                _ => (noop.clone(), noop),
+            },
+            prefix => (prefix, evnt),
+        };
+
+        debug!("hex prefix:{} event:{}", prefix, evnt);
+
+        self.prefix = prefix;
+        evnt
+    }
+
+    fn replace_fold(&mut self, evnt: Event) -> Event {
+        use crate::event::Event::*;
+
+        match evnt {
+            Char(ch, m) if ch.is_digit(16) => Char(ch, m),
+            Left(_) | Backspace(_) => Mt(Mto::Left(1, DP::None)),
+            Right(_) => Mt(Mto::Right(1, DP::None)),
+            Up(_) => Mt(Mto::Up(1, DP::None)),
+            Down(_) => Mt(Mto::Down(1, DP::None)),
+            Esc => Md(Mod::Esc),
+            _ => Event::Noop,
+        }
+    }
+}
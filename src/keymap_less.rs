@@ -1,10 +1,26 @@
 #[allow(unused_imports)]
-use log::trace;
+use log::{debug, trace};
 
-use crate::{buffer::Buffer, event::Event, Error, Result};
+use std::mem;
 
+use crate::{
+    buffer::Buffer,
+    event::{Event, Mto, Scroll, DP},
+    Error, Result,
+};
+
+macro_rules! parse_n {
+    ($n:expr, $ch:expr) => {{
+        let m = $ch.to_digit(10).unwrap_or(1) as usize;
+        ($n * 10) + m
+    }};
+}
+
+/// Keymap for pager windows, modelled after the `less` command.
 #[derive(Clone, Default)]
-pub struct KeyLess;
+pub struct KeyLess {
+    prefix: Event,
+}
 
 impl KeyLess {
     pub fn fold(&mut self, buf: &Buffer, evnt: Event) -> Result<Event> {
@@ -16,12 +32,83 @@ impl KeyLess {
     }
 
     pub fn to_event_prefix(&self) -> Event {
-        Event::Noop
+        self.prefix.clone()
     }
 }
 
 impl KeyLess {
     fn normal_fold(&mut self, evnt: Event) -> Result<Event> {
+        use crate::event::Event::*;
+
+        let noop = Event::Noop;
+
+        let prefix = mem::replace(&mut self.prefix, Event::default());
+        let (empty, ctrl, shift) = {
+            use crossterm::event::KeyModifiers as KM;
+            let m = evnt.to_modifiers();
+            (m.is_empty(), m.contains(KM::CONTROL), m.contains(KM::SHIFT))
+        };
+
+        let (n, count) = match &prefix {
+            N(n) => (*n, Some(*n)),
+            _ => (1, None),
+        };
+
+        let (prefix, evnt) = match prefix {
+            Event::Noop | N(_) if empty | shift => match evnt {
+                // count prefix
+                Char(ch @ '0'..='9', _) => (N(parse_n!(count.unwrap_or(0), ch)), noop),
+                // line-wise scrolling
+                Char('j', _) | Char('e', _) | Down(_) | Enter(_) => {
+                    (noop, Mt(Mto::WinScroll(n, Scroll::Ones, DP::Right)))
+                }
+                Char('k', _) | Char('y', _) | Up(_) => {
+                    (noop, Mt(Mto::WinScroll(n, Scroll::Ones, DP::Left)))
+                }
+                // page-wise and half-page scrolling
+                Char(' ', _) | Char('f', _) | PageDown(_) => {
+                    (noop, Mt(Mto::WinScroll(n, Scroll::Pages, DP::Right)))
+                }
+                Char('b', _) | PageUp(_) => (noop, Mt(Mto::WinScroll(n, Scroll::Pages, DP::Left))),
+                Char('d', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Lines, DP::Right))),
+                Char('u', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Lines, DP::Left))),
+                // jumps
+                Char('g', _) | Char('<', _) | Home(_) => (noop, Mt(Mto::Row(n, DP::None))),
+                Char('G', _) | Char('>', _) | End(_) => {
+                    let n = count.unwrap_or(std::usize::MAX);
+                    (noop, Mt(Mto::Row(n, DP::None)))
+                }
+                // searches
+                Char('/', _) => (noop, Mt(Mto::Pattern(n, None, DP::Right))),
+                Char('?', _) => (noop, Mt(Mto::Pattern(n, None, DP::Left))),
+                Char('n', _) => (noop, Mt(Mto::PatternR(n, DP::Right))),
+                Char('N', _) => (noop, Mt(Mto::PatternR(n, DP::Left))),
+                // follow and quit
+                Char('F', _) => (noop, Mt(Mto::WinScroll(1, Scroll::Follow, DP::Right))),
+                Char('q', _) | Char('Q', _) | Esc => (noop, Esc),
+                _ => (noop.clone(), noop),
+            },
+            Event::Noop | N(_) if ctrl => match evnt {
+                Char('e', _) | Char('n', _) | Char('j', _) => {
+                    (noop, Mt(Mto::WinScroll(n, Scroll::Ones, DP::Right)))
+                }
+                Char('y', _) | Char('p', _) | Char('k', _) => {
+                    (noop, Mt(Mto::WinScroll(n, Scroll::Ones, DP::Left)))
+                }
+                Char('f', _) | Char('v', _) => {
+                    (noop, Mt(Mto::WinScroll(n, Scroll::Pages, DP::Right)))
+                }
+                Char('b', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Pages, DP::Left))),
+                Char('d', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Lines, DP::Right))),
+                Char('u', _) => (noop, Mt(Mto::WinScroll(n, Scroll::Lines, DP::Left))),
+                _ => (noop.clone(), noop),
+            },
+            prefix => (prefix, evnt),
+        };
+
+        trace!("less prefix:{} event:{}", prefix, evnt);
+
+        self.prefix = prefix;
         Ok(evnt)
     }
 }
@@ -0,0 +1,401 @@
+use crossterm::event::KeyModifiers as KM;
+#[allow(unused_imports)]
+use log::{debug, trace};
+use toml;
+
+use std::fmt;
+
+use crate::{event::Event, Error, Result};
+
+/// Maximum number of times a mapping can expand into another mapping,
+/// before the key sequence is treated as a recursive mapping.
+const MAX_DEPTH: usize = 1000;
+
+/// Editor modes to which user mappings can apply.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MapMode {
+    Normal,
+    Insert,
+    Command,
+}
+
+impl MapMode {
+    /// Resolve map command, or `[code.keymap]` section name, into mode
+    /// and whether the mapping is non-recursive, like `inoremap` resolves
+    /// into `(Insert, true)`.
+    pub fn from_command(name: &str) -> Option<(MapMode, bool)> {
+        match name {
+            "map" | "nmap" => Some((MapMode::Normal, false)),
+            "imap" => Some((MapMode::Insert, false)),
+            "cmap" => Some((MapMode::Command, false)),
+            "noremap" | "nnoremap" => Some((MapMode::Normal, true)),
+            "inoremap" => Some((MapMode::Insert, true)),
+            "cnoremap" => Some((MapMode::Command, true)),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            MapMode::Normal => 'n',
+            MapMode::Insert => 'i',
+            MapMode::Command => 'c',
+        }
+    }
+}
+
+/// Single user mapping, from `lhs` key sequence to `rhs` key sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mapping {
+    pub mode: MapMode,
+    pub lhs: Vec<Event>,
+    pub rhs: Vec<Event>,
+    pub noremap: bool,
+}
+
+impl Mapping {
+    /// Create a new mapping from key-notation, refer [parse_keys].
+    pub fn new(mode: MapMode, lhs: &str, rhs: &str, noremap: bool, leader: char) -> Result<Self> {
+        let lhs: Vec<Event> = {
+            let keys = parse_keys(lhs, leader)?;
+            keys.iter().map(to_normal_key).collect()
+        };
+        if lhs.len() == 0 {
+            err_at!(Invalid, msg: format!("empty mapping"))?
+        }
+        let rhs = parse_keys(rhs, leader)?;
+
+        Ok(Mapping {
+            mode,
+            lhs,
+            rhs,
+            noremap,
+        })
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lhs = to_key_notation(&self.lhs);
+        let rhs = to_key_notation(&self.rhs);
+        let star = if_else!(self.noremap, '*', ' ');
+        write!(f, "{}  {:<16} {}{}", self.mode.to_char(), lhs, star, rhs)
+    }
+}
+
+/// User mappings, sits in front of [Keymap][crate::keymap::Keymap] and
+/// translate key sequences typed by user before they are folded by the
+/// keymap. Keys are fed into a typeahead buffer, when the typeahead is a
+/// prefix for one or more mappings, it waits for more keys or for a
+/// timeout. Resolved keys are taken out one at a time using `fold()`,
+/// so that mode switches made by earlier keys apply to later keys.
+#[derive(Clone, Default)]
+pub struct Mappings {
+    maps: Vec<Mapping>,
+    // (key, whether key can be remapped)
+    typeahead: Vec<(Event, bool)>,
+    depth: usize,
+}
+
+impl Mappings {
+    /// Load mappings from `[code.keymap]` section, where each sub-table is
+    /// named after the map command and contains `lhs = rhs` entries.
+    ///
+    /// ```toml
+    /// [code.keymap]
+    /// nnoremap = { "<leader>w" = ":w<CR>" }
+    /// inoremap = { "jk" = "<Esc>" }
+    /// ```
+    pub fn from_toml(value: &toml::Value, leader: char) -> Result<Mappings> {
+        let mut maps = Mappings::default();
+        let table = match value.as_table() {
+            Some(table) => table,
+            None => err_at!(Invalid, msg: format!("keymap must be a table"))?,
+        };
+        for (name, value) in table.iter() {
+            let (mode, noremap) = match MapMode::from_command(name) {
+                Some(val) => val,
+                None => err_at!(Invalid, msg: format!("keymap {}", name))?,
+            };
+            let entries = match value.as_table() {
+                Some(entries) => entries,
+                None => err_at!(Invalid, msg: format!("keymap {} must be a table", name))?,
+            };
+            for (lhs, rhs) in entries.iter() {
+                let rhs = match rhs.as_str() {
+                    Some(rhs) => rhs,
+                    None => err_at!(Invalid, msg: format!("keymap {} {:?}", name, lhs))?,
+                };
+                maps.add(Mapping::new(mode, lhs, rhs, noremap, leader)?);
+            }
+        }
+        Ok(maps)
+    }
+
+    /// Add a new mapping, replacing the older one for same mode and lhs.
+    pub fn add(&mut self, mapping: Mapping) {
+        self.maps
+            .retain(|m| !(m.mode == mapping.mode && m.lhs == mapping.lhs));
+        self.maps.push(mapping)
+    }
+
+    /// Remove mapping for `lhs` in `mode`.
+    pub fn remove(&mut self, mode: MapMode, lhs: &[Event]) -> Result<Mapping> {
+        let lhs: Vec<Event> = lhs.iter().map(to_normal_key).collect();
+        match self
+            .maps
+            .iter()
+            .position(|m| m.mode == mode && m.lhs == lhs)
+        {
+            Some(off) => Ok(self.maps.remove(off)),
+            None => err_at!(Invalid, msg: format!("no such mapping")),
+        }
+    }
+
+    /// Return mappings in `mode` whose lhs starts with `prefix`.
+    pub fn to_mappings(&self, mode: MapMode, prefix: &[Event]) -> Vec<Mapping> {
+        let prefix: Vec<Event> = prefix.iter().map(to_normal_key).collect();
+        let iter = self.maps.iter().filter(|m| m.mode == mode);
+        iter.filter(|m| m.lhs.starts_with(&prefix))
+            .cloned()
+            .collect()
+    }
+
+    /// Feed a key typed by user into typeahead.
+    pub fn push(&mut self, key: Event) {
+        self.typeahead.push((key, true))
+    }
+
+    /// Return whether typeahead is waiting for more keys to resolve an
+    /// ambiguous mapping.
+    pub fn is_pending(&self) -> bool {
+        self.typeahead.len() > 0
+    }
+
+    /// Discard typeahead, typically after an error.
+    pub fn clear(&mut self) {
+        self.typeahead.drain(..);
+        self.depth = 0;
+    }
+
+    /// Return the next resolved key from typeahead. `mode` is the current
+    /// editor mode, None if mappings does not apply, like when a keymap
+    /// is waiting for a character argument. Return None if typeahead is
+    /// empty or waiting for more keys. With `timeout` waiting keys are
+    /// resolved with the longest matching mapping, if any.
+    pub fn fold(&mut self, mode: Option<MapMode>, timeout: bool) -> Result<Option<Event>> {
+        loop {
+            let mode = match (mode, self.typeahead.first()) {
+                (_, None) => {
+                    self.depth = 0;
+                    break Ok(None);
+                }
+                (None, Some(_)) | (_, Some((_, false))) => {
+                    break Ok(Some(self.typeahead.remove(0).0));
+                }
+                (Some(mode), Some(_)) => mode,
+            };
+
+            let keys: Vec<Event> = {
+                let iter = self.typeahead.iter().take_while(|(_, remap)| *remap);
+                iter.map(|(key, _)| to_normal_key(key)).collect()
+            };
+
+            let (mut wait, mut found): (bool, Option<&Mapping>) = (false, None);
+            for m in self.maps.iter().filter(|m| m.mode == mode) {
+                if m.lhs.len() > keys.len() {
+                    wait = wait || m.lhs.starts_with(&keys);
+                } else if keys.starts_with(&m.lhs) {
+                    found = match found {
+                        Some(f) if f.lhs.len() >= m.lhs.len() => Some(f),
+                        _ => Some(m),
+                    };
+                }
+            }
+
+            if wait && !timeout {
+                break Ok(None);
+            }
+
+            let (n, rhs) = match found {
+                Some(m) => {
+                    let remap = !m.noremap;
+                    let mut rhs: Vec<(Event, bool)> = {
+                        let iter = m.rhs.iter();
+                        iter.map(|key| (key.clone(), remap)).collect()
+                    };
+                    // a recursive mapping, whose rhs starts with its lhs,
+                    // shall not remap its lhs.
+                    if remap && m.rhs.starts_with(&m.lhs) {
+                        rhs[..m.lhs.len()].iter_mut().for_each(|k| k.1 = false);
+                    }
+                    (m.lhs.len(), rhs)
+                }
+                None => break Ok(Some(self.typeahead.remove(0).0)),
+            };
+
+            self.depth += 1;
+            if self.depth > MAX_DEPTH {
+                self.clear();
+                err_at!(Invalid, msg: format!("recursive mapping"))?
+            }
+            debug!("mapping {} keys into {} keys", n, rhs.len());
+            self.typeahead.splice(0..n, rhs);
+        }
+    }
+}
+
+/// Parse key-notation into key events. Special keys are specified within
+/// angle brackets, like `<Esc>`, `<CR>`, `<Tab>`, `<BS>`, `<Del>`,
+/// `<Insert>`, `<Up>`, `<Down>`, `<Left>`, `<Right>`, `<Home>`, `<End>`,
+/// `<PageUp>`, `<PageDown>`, `<F1>` .. `<F12>`, `<Space>`, `<lt>`, `<Bar>`,
+/// `<Bslash>`, `<Nop>` and `<leader>`. Special keys and characters can be
+/// prefixed with modifiers `C-`, `S-`, `M-` or `A-`, like `<C-w>`.
+/// Key names are case insensitive, and text within angle brackets that
+/// is not a key name is treated as literal characters.
+pub fn parse_keys(text: &str, leader: char) -> Result<Vec<Event>> {
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut keys = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '<' {
+            let name = match chars[i + 1..].iter().position(|ch| *ch == '>') {
+                Some(n) if n > 0 => Some(chars[i + 1..i + 1 + n].iter().collect::<String>()),
+                _ => None,
+            };
+            let name_keys = name.as_ref().and_then(|name| parse_key_name(name, leader));
+            if let (Some(name), Some(name_keys)) = (name, name_keys) {
+                keys.extend(name_keys);
+                i += name.chars().count() + 2;
+                continue;
+            }
+        }
+        keys.push(Event::Char(chars[i], KM::empty()));
+        i += 1;
+    }
+
+    Ok(keys)
+}
+
+fn parse_key_name(name: &str, leader: char) -> Option<Vec<Event>> {
+    use Event::*;
+
+    let mut m = KM::empty();
+    let mut name = name;
+    while name.len() > 2 && name.as_bytes()[1] == b'-' {
+        m.insert(match name.as_bytes()[0].to_ascii_lowercase() {
+            b'c' => KM::CONTROL,
+            b's' => KM::SHIFT,
+            b'm' | b'a' => KM::ALT,
+            _ => return None,
+        });
+        name = &name[2..];
+    }
+
+    let mut chars = name.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(_), None) if m.is_empty() => return None,
+        (Some(ch), None) if m.contains(KM::SHIFT) => Char(ch.to_ascii_uppercase(), m),
+        (Some(ch), None) => Char(ch, m),
+        _ => match name.to_lowercase().as_str() {
+            "esc" => Esc,
+            "cr" | "enter" | "return" => Enter(m),
+            "tab" if m.contains(KM::SHIFT) => BackTab,
+            "tab" => Tab(m),
+            "bs" | "backspace" => Backspace(m),
+            "del" | "delete" => Delete(m),
+            "insert" | "ins" => InsKey(m),
+            "up" => Up(m),
+            "down" => Down(m),
+            "left" => Left(m),
+            "right" => Right(m),
+            "home" => Home(m),
+            "end" => End(m),
+            "pageup" => PageUp(m),
+            "pagedown" => PageDown(m),
+            "space" => Char(' ', m),
+            "lt" => Char('<', m),
+            "bar" => Char('|', m),
+            "bslash" => Char('\\', m),
+            "leader" if m.is_empty() => Char(leader, m),
+            "nop" if m.is_empty() => return Some(vec![]),
+            name if name.starts_with('f') => match name[1..].parse::<u8>() {
+                Ok(n) if n > 0 && n <= 12 => FKey(n, m),
+                _ => return None,
+            },
+            _ => return None,
+        },
+    };
+
+    Some(vec![to_normal_key(&key)])
+}
+
+/// Convert key events into key-notation, refer [parse_keys].
+pub fn to_key_notation(keys: &[Event]) -> String {
+    use Event::*;
+
+    let mut s = String::default();
+    for key in keys.iter() {
+        let m = key.to_modifiers();
+        let name = match key {
+            Char(' ', _) => "Space".to_string(),
+            Char('<', _) => "lt".to_string(),
+            Char(ch, m) if m.is_empty() => {
+                s.push(*ch);
+                continue;
+            }
+            Char(ch, _) => ch.to_string(),
+            Esc => "Esc".to_string(),
+            Enter(_) => "CR".to_string(),
+            BackTab => "S-Tab".to_string(),
+            Tab(_) => "Tab".to_string(),
+            Backspace(_) => "BS".to_string(),
+            Delete(_) => "Del".to_string(),
+            InsKey(_) => "Insert".to_string(),
+            Up(_) => "Up".to_string(),
+            Down(_) => "Down".to_string(),
+            Left(_) => "Left".to_string(),
+            Right(_) => "Right".to_string(),
+            Home(_) => "Home".to_string(),
+            End(_) => "End".to_string(),
+            PageUp(_) => "PageUp".to_string(),
+            PageDown(_) => "PageDown".to_string(),
+            FKey(n, _) => format!("F{}", n),
+            key => key.to_string(),
+        };
+        s.push('<');
+        if m.contains(KM::CONTROL) {
+            s.push_str("C-")
+        }
+        if m.contains(KM::SHIFT) {
+            s.push_str("S-")
+        }
+        if m.contains(KM::ALT) {
+            s.push_str("M-")
+        }
+        s.push_str(&name);
+        s.push('>');
+    }
+    s
+}
+
+// Shift modifier is implied by character keys, and control characters
+// are case insensitive.
+fn to_normal_key(key: &Event) -> Event {
+    match key {
+        Event::Char(ch, m) => {
+            let mut m = m.clone();
+            m.remove(KM::SHIFT);
+            match m.contains(KM::CONTROL) {
+                true => Event::Char(ch.to_ascii_lowercase(), m),
+                false => Event::Char(*ch, m),
+            }
+        }
+        key => key.clone(),
+    }
+}
+
+#[cfg(test)]
+#[path = "keymap_map_test.rs"]
+mod keymap_map_test;
@@ -0,0 +1,134 @@
+use super::*;
+
+fn to_keys(text: &str) -> Vec<Event> {
+    text.chars()
+        .map(|ch| Event::Char(ch, KM::empty()))
+        .collect()
+}
+
+fn fold_all(maps: &mut Mappings, mode: MapMode, timeout: bool) -> Vec<Event> {
+    let mut keys = vec![];
+    while let Some(key) = maps.fold(Some(mode), timeout).unwrap() {
+        keys.push(key)
+    }
+    keys
+}
+
+#[test]
+fn test_parse_keys() {
+    let keys = parse_keys("<C-W>x<Esc><CR><S-Tab><F5><Space><lt><leader>", ',').unwrap();
+    let refs = vec![
+        Event::Char('w', KM::CONTROL),
+        Event::Char('x', KM::empty()),
+        Event::Esc,
+        Event::Enter(KM::empty()),
+        Event::BackTab,
+        Event::FKey(5, KM::empty()),
+        Event::Char(' ', KM::empty()),
+        Event::Char('<', KM::empty()),
+        Event::Char(',', KM::empty()),
+    ];
+    assert_eq!(keys, refs);
+
+    assert_eq!(parse_keys("<Nop>", ',').unwrap(), vec![]);
+    assert_eq!(parse_keys("<S-a>", ',').unwrap(), to_keys("A"));
+    assert_eq!(parse_keys("<x>", ',').unwrap(), to_keys("<x>"));
+    assert_eq!(parse_keys("<foo", ',').unwrap(), to_keys("<foo"));
+    assert_eq!(parse_keys("<>", ',').unwrap(), to_keys("<>"));
+
+    let text = "<C-w>x<Esc><CR><S-Tab><F5><Space><lt><M-Left>";
+    let keys = parse_keys(text, ',').unwrap();
+    assert_eq!(to_key_notation(&keys), text);
+}
+
+#[test]
+fn test_mappings_fold() {
+    let mut maps = Mappings::default();
+    let mapping = Mapping::new(MapMode::Insert, "jk", "<Esc>", true, '\\').unwrap();
+    maps.add(mapping);
+    maps.add(Mapping::new(MapMode::Normal, "<leader>w", ":w<CR>", false, '\\').unwrap());
+
+    // ambiguous prefix waits for more keys.
+    maps.push(Event::Char('j', KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Insert, false), vec![]);
+    assert!(maps.is_pending());
+    maps.push(Event::Char('k', KM::empty()));
+    assert_eq!(
+        fold_all(&mut maps, MapMode::Insert, false),
+        vec![Event::Esc]
+    );
+    assert!(!maps.is_pending());
+
+    // timeout resolves the waiting keys.
+    maps.push(Event::Char('j', KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Insert, false), vec![]);
+    assert_eq!(fold_all(&mut maps, MapMode::Insert, true), to_keys("j"));
+
+    // a mismatch releases keys.
+    maps.push(Event::Char('j', KM::empty()));
+    maps.push(Event::Char('x', KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Insert, false), to_keys("jx"));
+
+    // mappings are per mode.
+    maps.push(Event::Char('\\', KM::empty()));
+    maps.push(Event::Char('w', KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Insert, false), to_keys("\\w"));
+    maps.push(Event::Char('\\', KM::empty()));
+    maps.push(Event::Char('w', KM::empty()));
+    let mut refs = to_keys(":w");
+    refs.push(Event::Enter(KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Normal, false), refs);
+
+    let lhs = parse_keys("jk", '\\').unwrap();
+    assert!(maps.remove(MapMode::Normal, &lhs).is_err());
+    assert!(maps.remove(MapMode::Insert, &lhs).is_ok());
+    maps.push(Event::Char('j', KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Insert, false), to_keys("j"));
+}
+
+#[test]
+fn test_mappings_remap() {
+    let mut maps = Mappings::default();
+    maps.add(Mapping::new(MapMode::Normal, "a", "b", false, '\\').unwrap());
+    maps.add(Mapping::new(MapMode::Normal, "b", "c", false, '\\').unwrap());
+    maps.add(Mapping::new(MapMode::Normal, "x", "b", true, '\\').unwrap());
+    maps.add(Mapping::new(MapMode::Normal, "y", "yb", false, '\\').unwrap());
+
+    maps.push(Event::Char('a', KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Normal, false), to_keys("c"));
+    maps.push(Event::Char('x', KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Normal, false), to_keys("b"));
+    maps.push(Event::Char('y', KM::empty()));
+    assert_eq!(fold_all(&mut maps, MapMode::Normal, false), to_keys("yc"));
+
+    maps.add(Mapping::new(MapMode::Normal, "c", "a", false, '\\').unwrap());
+    maps.push(Event::Char('a', KM::empty()));
+    assert!(maps.fold(Some(MapMode::Normal), false).is_err());
+    assert!(!maps.is_pending());
+
+    // mappings does not apply without mode.
+    maps.push(Event::Char('a', KM::empty()));
+    assert_eq!(
+        maps.fold(None, false).unwrap(),
+        Some(to_keys("a")[0].clone())
+    );
+}
+
+#[test]
+fn test_mappings_toml() {
+    let text = r#"
+        nnoremap = { "<leader>w" = ":w<CR>" }
+        inoremap = { "jk" = "<Esc>" }
+    "#;
+    let value: toml::Value = text.parse().unwrap();
+    let maps = Mappings::from_toml(&value, ' ').unwrap();
+
+    let ms = maps.to_mappings(MapMode::Normal, &[]);
+    assert_eq!(ms.len(), 1);
+    assert_eq!(ms[0].to_string(), "n  <Space>w         *:w<CR>");
+    assert_eq!(maps.to_mappings(MapMode::Insert, &[]).len(), 1);
+    assert_eq!(maps.to_mappings(MapMode::Command, &[]).len(), 0);
+
+    let value: toml::Value = "xmap = { a = \"b\" }".parse().unwrap();
+    assert!(Mappings::from_toml(&value, ' ').is_err());
+}
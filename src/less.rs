@@ -0,0 +1,129 @@
+//! Module `less` implement the pager application, similar to the `less`
+//! command. Files, or piped standard input, are paged in read-only mode.
+//! When more than one file is supplied, files are paged one after the
+//! other, quitting a file moves on to the next one.
+
+#[allow(unused_imports)]
+use log::{debug, trace};
+
+use std::{convert::TryFrom, ffi, sync::mpsc, time};
+
+use crate::{
+    app::Application,
+    buffer::Buffer,
+    event::Event,
+    location::Location,
+    pubsub::{self, PubSub},
+    state::{self, State},
+    window::{Coord, Cursor, WindowLess},
+    Error, Result,
+};
+
+/// Pager application.
+pub struct Less {
+    subscribers: PubSub,
+    wlesses: Vec<WindowLess>,
+}
+
+impl<'a> TryFrom<(&'a State, Coord)> for Less {
+    type Error = Error;
+
+    fn try_from((state, coord): (&'a State, Coord)) -> Result<Less> {
+        debug!("starting app `less` coord:{}", coord);
+
+        // piped input is paged from an empty buffer, as the content arrives.
+        let mut locs = vec![];
+        for file in state.opts.files.iter() {
+            match (file.as_str(), &state.pipe) {
+                ("-", Some(pipe)) => locs.push((to_pipe_location()?, Some(pipe))),
+                (file, _) => {
+                    let enc = state.opts.encoding.as_deref();
+                    let mut loc = Location::new_disk(&ffi::OsString::from(file), enc)?;
+                    loc.set_read_only(true);
+                    locs.push((loc, None));
+                }
+            }
+        }
+        match &state.pipe {
+            Some(pipe) if locs.len() == 0 => locs.push((to_pipe_location()?, Some(pipe))),
+            _ => (),
+        }
+        if locs.len() == 0 {
+            err_at!(Invalid, msg: format!("missing filename"))?
+        }
+
+        let scheme = state.to_color_scheme(None);
+        let mut wlesses = vec![];
+        for (loc, pipe) in locs.into_iter() {
+            let buf = Buffer::from_reader(loc)?;
+            let large_file = state.config.large_file;
+            let mut wless = WindowLess::from_buffer(buf, coord, scheme.clone(), large_file)?;
+            wless.set_wrap(true);
+            if let Some(pipe) = pipe {
+                wless.set_pipe(pipe.clone());
+            }
+            wlesses.push(wless);
+        }
+
+        Ok(Less {
+            subscribers: state.subscribers.clone(),
+            wlesses,
+        })
+    }
+}
+
+impl Application for Less {
+    fn subscribe(&mut self, topic: &str, tx: mpsc::Sender<pubsub::Notify>) {
+        self.subscribers.subscribe(topic, tx);
+    }
+
+    fn notify(&self, topic: &str, msg: pubsub::Notify) -> Result<()> {
+        self.subscribers.notify(topic, msg)
+    }
+
+    fn on_event(&mut self, evnt: Event) -> Result<Event> {
+        let evnt = match self.wlesses.first_mut() {
+            Some(wless) => wless.on_event(evnt)?,
+            None => return Ok(Event::Quit),
+        };
+        match evnt {
+            Event::Esc => {
+                self.wlesses.remove(0);
+                Ok(if_else!(self.wlesses.len() == 0, Event::Quit, Event::Noop))
+            }
+            evnt => Ok(evnt),
+        }
+    }
+
+    fn on_refresh(&mut self) -> Result<()> {
+        match self.wlesses.first_mut() {
+            Some(wless) => wless.on_refresh(),
+            None => Ok(()),
+        }
+    }
+
+    fn to_cursor(&self) -> Option<Cursor> {
+        self.wlesses.first().and_then(|wless| wless.to_cursor())
+    }
+
+    fn to_timeout(&self) -> Option<time::Duration> {
+        self.wlesses.first().and_then(|wless| wless.to_timeout())
+    }
+
+    fn to_tab_title(&self, wth: usize) -> state::TabTitle {
+        let text = match self.wlesses.first() {
+            Some(wless) => wless.to_location().to_title(wth).ok(),
+            None => None,
+        };
+        state::TabTitle {
+            text: text.unwrap_or("∞".to_string()),
+            modified: false,
+            active: false,
+        }
+    }
+}
+
+fn to_pipe_location() -> Result<Location> {
+    let read_only = true;
+    Location::new_memory(&b""[..], "utf-8", read_only)
+}
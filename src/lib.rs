@@ -15,6 +15,8 @@ pub mod term;
 
 #[macro_use]
 pub mod window;
+mod window_finder;
+mod window_hex;
 mod window_less;
 mod window_prompt;
 mod window_status;
@@ -25,23 +27,30 @@ pub mod state;
 pub mod buffer;
 mod col_nu;
 pub mod colors;
+pub mod compress;
 pub mod event;
+mod fuzzy;
 pub mod location;
 mod mark;
 mod match_pair;
 pub mod pubsub;
 mod scroll;
+mod search;
+pub mod swap;
 pub mod tabc;
 pub mod tss;
 mod view;
 
 pub mod app;
 mod code;
+mod less;
 
 pub mod keymap;
 mod keymap_cmd;
 mod keymap_edit;
+mod keymap_hex;
 mod keymap_less;
+mod keymap_map;
 
 mod syntax;
 pub mod text;
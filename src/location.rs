@@ -2,51 +2,66 @@
 
 use dirs;
 use lazy_static::lazy_static;
+use ropey::{Rope, RopeBuilder};
 
-use std::{ffi, fmt, io, path, result, sync::Mutex};
+use std::{
+    ffi, fmt, io, path, result,
+    sync::{Arc, Mutex},
+    time,
+};
 
-use crate::{text, Error, Result};
+use crate::{compress::Compress, text, Error, Result};
 
 lazy_static! {
     static ref MEM_BUFFER_N: Mutex<usize> = Mutex::new(0);
 }
 
+/// Number of bytes sampled from the start of a file, to detect its
+/// file-encoding.
+const DETECT_SIZE: u64 = 1024 * 1024;
+
 /// Location of buffer's content, typically persistent medium. Each
 /// variant denotes the location type.
 #[derive(Clone)]
 pub enum Location {
-    /// Memory location, transient aka buffer is not persisted.
+    /// Memory location, transient aka buffer is not persisted. Text is
+    /// shared between clones of the location.
     Memory {
         name: String,
-        text: String,
+        text: Arc<String>,
         read_only: bool,
     },
-    /// Disk location, durable.
+    /// Disk location, durable. `stat` is the file's modified-time and
+    /// size, when it was last read or written, None for new files.
+    /// Compressed files are decompressed on read, and re-compressed on
+    /// write, using `compress`.
     Disk {
         loc: ffi::OsString,
         path_file: ffi::OsString,
         enc: String,
         read_only: bool,
+        stat: Option<(time::SystemTime, u64)>,
+        compress: Option<Compress>,
     },
     /// Ted application buffers, similar to `Memory`. Mostly configured
-    /// as read-only.
+    /// as read-only. Directory listings are ted buffers named after the
+    /// directory's path, refer [Location::new_dir].
     Ted {
         name: String,
-        text: String,
+        text: Arc<String>,
         read_only: bool,
     },
 }
 
-// TODO: new_disk() to accept `enc` argument as Option<&str>.
-// if None, we need to figure out the file-encoding  using other
-// means.
-
 impl Location {
     /// Create a new Disk location for buffer. `loc` can be absolute path,
     /// relative path to current-directory, or start with `~` relative to
-    /// home-directory.
-    pub fn new_disk(loc: &ffi::OsStr, enc: &str) -> Result<Location> {
-        use std::fs;
+    /// home-directory. If `enc` is None, file-encoding is detected from
+    /// the first [DETECT_SIZE] bytes of file, refer [text::Encoding::detect].
+    /// Compressed files are detected by their content, refer
+    /// [Compress::detect], and encoding is detected after decompression.
+    pub fn new_disk(loc: &ffi::OsStr, enc: Option<&str>) -> Result<Location> {
+        use std::{fs, io::Read};
 
         let fp = {
             let res = loc.to_os_string().into_string();
@@ -54,14 +69,105 @@ impl Location {
         };
         let path_file = Self::canonicalize(fp).into_os_string();
         let m = err_at!(IOError, fs::metadata(&path_file))?;
+        if m.is_dir() {
+            return Self::new_dir(&path_file);
+        }
+        let compress = Compress::detect(path::Path::new(&path_file))?;
+        let enc = match enc {
+            Some(enc) => text::Encoding::to_canonical(enc)?,
+            None => {
+                let mut bytes = vec![];
+                let r = open_reader(&path_file, &compress)?;
+                err_at!(IOError, r.take(DETECT_SIZE).read_to_end(&mut bytes))?;
+                // size of decompressed content is not known upfront.
+                let prefix = match compress {
+                    Some(_) => (bytes.len() as u64) == DETECT_SIZE,
+                    None => (bytes.len() as u64) < m.len(),
+                };
+                if prefix {
+                    text::Encoding::detect_prefix(&bytes)
+                } else {
+                    text::Encoding::detect(&bytes)
+                }
+            }
+        };
         Ok(Location::Disk {
             loc: loc.to_os_string(),
             path_file,
             enc: enc.to_string(),
             read_only: m.permissions().readonly(),
+            stat: to_stat(&m),
+            compress,
         })
     }
 
+    /// Create a new Disk location for a file that may not exist yet,
+    /// like the target of `:write {file}`. New files default to `utf-8`,
+    /// and are compressed if their name ends with `.gz`, `.bz2` or `.xz`.
+    pub fn new_disk_file(loc: &ffi::OsStr, enc: Option<&str>) -> Result<Location> {
+        let fp = {
+            let res = loc.to_os_string().into_string();
+            err_at!(IOError, res.map_err(|e| format!("{:?}", e)))?
+        };
+        let path_file = Self::canonicalize(fp).into_os_string();
+        if path::Path::new(&path_file).exists() {
+            Self::new_disk(loc, enc)
+        } else {
+            let enc = text::Encoding::to_canonical(enc.unwrap_or("utf-8"))?;
+            let compress = Compress::from_extension(path::Path::new(&path_file));
+            Ok(Location::Disk {
+                loc: loc.to_os_string(),
+                path_file,
+                enc: enc.to_string(),
+                read_only: false,
+                stat: None,
+                compress,
+            })
+        }
+    }
+
+    /// Create a read-only listing of directory `loc`, its entries along
+    /// with their size and modified-time, directories listed first. First
+    /// line is the directory's path followed by the parent directory.
+    pub fn new_dir(loc: &ffi::OsStr) -> Result<Location> {
+        use std::fs;
+
+        let dir = err_at!(IOError, fs::canonicalize(loc))?;
+        let mut entries = vec![];
+        for entry in err_at!(IOError, fs::read_dir(&dir))? {
+            let entry = err_at!(IOError, entry)?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.contains('\n') {
+                continue;
+            }
+            // broken symbolic links are listed as is.
+            let m = match fs::metadata(entry.path()) {
+                Ok(m) => m,
+                Err(_) => err_at!(IOError, entry.metadata())?,
+            };
+            entries.push((!m.is_dir(), name, m));
+        }
+        entries.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+        let name = to_dir_name(&dir);
+        let mut lines = vec![name.clone()];
+        if dir.parent().is_some() {
+            lines.push(format!("..{}", path::MAIN_SEPARATOR));
+        }
+        for (_, name, m) in entries.into_iter() {
+            let mtime = m.modified().map(to_mtime).unwrap_or("-".to_string());
+            let line = if m.is_dir() {
+                format!("{:>12} {} {}{}", "-", mtime, name, path::MAIN_SEPARATOR)
+            } else {
+                format!("{:>12} {} {}", m.len(), mtime, name)
+            };
+            lines.push(line);
+        }
+        lines.push("".to_string());
+
+        Self::new_ted(&name, lines.join("\n").as_bytes(), true)
+    }
+
     /// Create a memory-only buffer.
     pub fn new_memory<R>(r: R, enc: &str, read_only: bool) -> Result<Location>
     where
@@ -75,7 +181,7 @@ impl Location {
         let text: String = text::Encoding::from_reader(r, enc)?.into();
         Ok(Location::Memory {
             name,
-            text,
+            text: Arc::new(text),
             read_only,
         })
     }
@@ -89,11 +195,23 @@ impl Location {
         let name = name.to_string();
         Ok(Location::Ted {
             name: format!("[{}]", name),
-            text,
+            text: Arc::new(text),
             read_only,
         })
     }
 
+    /// Set file-encoding for disk location, content shall be saved
+    /// using this encoding.
+    pub fn set_encoding(&mut self, encoding: &str) -> Result<&mut Self> {
+        match self {
+            Location::Disk { enc, .. } => {
+                *enc = text::Encoding::to_canonical(encoding)?.to_string();
+                Ok(self)
+            }
+            loc => err_at!(Invalid, msg: format!("{} is not a file", loc)),
+        }
+    }
+
     pub fn set_read_only(&mut self, ro: bool) -> &mut Self {
         match self {
             Location::Memory { read_only, .. } => *read_only = ro,
@@ -102,24 +220,203 @@ impl Location {
         }
         self
     }
+
+    /// Record the file's current modified-time and size, typically after
+    /// the file is read or written.
+    pub fn refresh_stat(&mut self) -> &mut Self {
+        use std::fs;
+
+        match self {
+            Location::Disk {
+                path_file, stat, ..
+            } => {
+                *stat = fs::metadata(path_file).ok().and_then(|m| to_stat(&m));
+            }
+            _ => (),
+        }
+        self
+    }
 }
 
 impl Location {
     /// Read the content of buffer as [String].
     pub fn read(&self) -> Result<String> {
-        use std::fs;
+        match self {
+            Location::Disk {
+                path_file,
+                enc,
+                compress,
+                ..
+            } => {
+                let r = open_reader(path_file, compress)?;
+                Ok(text::Encoding::from_reader(r, enc)?.into())
+            }
+            Location::Memory { text, .. } => Ok(text.as_ref().clone()),
+            Location::Ted { text, .. } => Ok(text.as_ref().clone()),
+        }
+    }
 
+    /// Read the content of buffer as [Rope]. Utf-8 files are streamed
+    /// into the rope, without holding a copy of the entire text, memory
+    /// and ted locations are not copied into an intermediate [String].
+    pub fn to_rope(&self) -> Result<Rope> {
         match self {
-            Location::Disk { path_file, enc, .. } => {
-                let fd = {
-                    let mut oo = fs::OpenOptions::new();
-                    err_at!(IOError, oo.read(true).open(path_file))?
-                };
-                Ok(text::Encoding::from_reader(fd, enc)?.into())
+            Location::Disk {
+                path_file,
+                enc,
+                compress,
+                ..
+            } if enc == "utf-8" || enc == "utf-8-bom" => {
+                let r = open_reader(path_file, compress)?;
+                utf8_to_rope(r, enc == "utf-8-bom")
             }
-            Location::Memory { text, .. } => Ok(text.clone()),
-            Location::Ted { text, .. } => Ok(text.clone()),
+            Location::Disk { .. } => Ok(Rope::from_str(&self.read()?)),
+            Location::Memory { text, .. } => Ok(Rope::from_str(text)),
+            Location::Ted { text, .. } => Ok(Rope::from_str(text)),
+        }
+    }
+
+    /// Save `text` to disk location, using the location's encoding. If
+    /// `append` is false, encoded text is saved using
+    /// [Location::save_bytes]. Otherwise text is appended to the target
+    /// file, as a new stream for compressed files.
+    pub fn save(&self, text: &str, append: bool) -> Result<()> {
+        use std::{convert::TryFrom, fs};
+
+        let (path_file, enc, compress) = match self {
+            Location::Disk {
+                path_file,
+                enc,
+                compress,
+                ..
+            } => (path::Path::new(path_file), enc, compress),
+            loc => err_at!(Invalid, msg: format!("can't save {}", loc))?,
+        };
+        let enc = text::Encoding::try_from((text.to_string(), enc.clone()))?;
+
+        if append {
+            use std::io::Write;
+
+            // byte-order-mark only at the start of file.
+            let bom = fs::metadata(path_file)
+                .map(|m| m.len() == 0)
+                .unwrap_or(true);
+            let mut fd = {
+                let mut oo = fs::OpenOptions::new();
+                err_at!(IOError, oo.append(true).create(true).open(path_file))?
+            };
+            let bytes = match compress {
+                Some(compress) => compress.encode(&enc.to_bytes(bom)?)?,
+                None => enc.to_bytes(bom)?,
+            };
+            return err_at!(IOError, fd.write_all(&bytes));
         }
+
+        self.save_bytes(&enc.to_bytes(true)?)
+    }
+
+    /// Save `bytes` as is to disk location, without any new-line or
+    /// encoding conversion, compressed files are re-compressed. Bytes
+    /// are written to a temporary file in the same directory and renamed
+    /// to the target file, preserving the target file's permissions.
+    /// When the location is a symbolic link, its target file is replaced,
+    /// the link is left as is.
+    pub fn save_bytes(&self, bytes: &[u8]) -> Result<()> {
+        use std::{borrow::Cow, fs, io::Write};
+
+        let (path_file, compress) = match self {
+            Location::Disk {
+                path_file,
+                compress,
+                ..
+            } => (path::Path::new(path_file), compress),
+            loc => err_at!(Invalid, msg: format!("can't save {}", loc))?,
+        };
+        let bytes = match compress {
+            Some(compress) => Cow::Owned(compress.encode(bytes)?),
+            None => Cow::Borrowed(bytes),
+        };
+
+        // resolve symbolic links, to save into the linked file.
+        let path_file = match fs::canonicalize(path_file) {
+            Ok(pbuf) => Cow::Owned(pbuf),
+            Err(_) => Cow::Borrowed(path_file),
+        };
+        let path_file = path_file.as_ref();
+
+        let tmp_file = {
+            let name = match path_file.file_name() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => err_at!(Invalid, msg: format!("{:?}", path_file))?,
+            };
+            path_file.with_file_name(format!(".{}.ted-save", name))
+        };
+        let res = {
+            let mut fd = err_at!(IOError, fs::File::create(&tmp_file))?;
+            err_at!(IOError, fd.write_all(&bytes))
+                .and_then(|_| err_at!(IOError, fd.sync_all()))
+                .and_then(|_| match fs::metadata(path_file) {
+                    Ok(m) => err_at!(IOError, fs::set_permissions(&tmp_file, m.permissions())),
+                    Err(_) => Ok(()),
+                })
+                .and_then(|_| err_at!(IOError, fs::rename(&tmp_file, path_file)))
+        };
+        if res.is_err() {
+            fs::remove_file(&tmp_file).ok();
+        }
+        res
+    }
+
+    /// Read the content as raw bytes, without decoding, for disk
+    /// locations, compressed files are decompressed. Memory and ted
+    /// locations return their utf-8 text.
+    pub fn read_bytes(&self) -> Result<Vec<u8>> {
+        use std::io::Read;
+
+        match self {
+            Location::Disk {
+                path_file,
+                compress,
+                ..
+            } => {
+                let mut bytes = vec![];
+                let mut r = open_reader(path_file, compress)?;
+                err_at!(IOError, r.read_to_end(&mut bytes))?;
+                Ok(bytes)
+            }
+            Location::Memory { text, .. } => Ok(text.as_bytes().to_vec()),
+            Location::Ted { text, .. } => Ok(text.as_bytes().to_vec()),
+        }
+    }
+
+    /// Read the bytes appended to an uncompressed disk file, after its
+    /// first `from` bytes. Return None if the location is not such a
+    /// file, or if the file was truncated to less than `from` bytes, in
+    /// which case the content shall be read again.
+    pub fn read_tail(&self, from: u64) -> Result<Option<Vec<u8>>> {
+        use std::{
+            fs,
+            io::{Read, Seek, SeekFrom},
+        };
+
+        let path_file = match self {
+            Location::Disk {
+                path_file,
+                compress: None,
+                ..
+            } => path_file,
+            _ => return Ok(None),
+        };
+
+        let mut fd = err_at!(IOError, fs::File::open(path_file))?;
+        let n = err_at!(IOError, fd.metadata())?.len();
+        if n < from {
+            return Ok(None);
+        }
+        err_at!(IOError, fd.seek(SeekFrom::Start(from)))?;
+        let mut bytes = Vec::with_capacity((n - from) as usize);
+        err_at!(IOError, fd.read_to_end(&mut bytes))?;
+        Ok(Some(bytes))
     }
 
     /// Read the content as String, and convert it to bytes.
@@ -127,6 +424,78 @@ impl Location {
         Ok(self.read()?.into())
     }
 
+    /// Return the file-encoding, memory and ted locations are always
+    /// `utf-8`.
+    pub fn to_encoding(&self) -> String {
+        match self {
+            Location::Disk { enc, .. } => enc.clone(),
+            _ => "utf-8".to_string(),
+        }
+    }
+
+    /// Return the directory, if this location is a directory listing.
+    pub fn to_dir(&self) -> Option<path::PathBuf> {
+        match self {
+            Location::Ted { name, .. } => {
+                let name = name.strip_prefix('[')?.strip_suffix(']')?;
+                let dir = path::Path::new(name);
+                match name.ends_with(path::MAIN_SEPARATOR) && dir.is_absolute() {
+                    true => Some(dir.to_path_buf()),
+                    false => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the path of entry listed in `line`, if this location is a
+    /// directory listing. Return the parent directory for `../`.
+    pub fn to_dir_entry(&self, line: &str) -> Option<path::PathBuf> {
+        let dir = self.to_dir()?;
+        let line = line.trim_end_matches(|ch| ch == '\r' || ch == '\n');
+        if line == format!("..{}", path::MAIN_SEPARATOR) {
+            return dir.parent().map(|dir| dir.to_path_buf());
+        }
+
+        let mut parts = line.trim_start().splitn(4, ' ');
+        let size = parts.next()?;
+        let (date, time) = (parts.next()?, parts.next()?);
+        let name = parts.next()?.trim_end_matches(path::MAIN_SEPARATOR);
+        let ok = size == "-" || size.parse::<u64>().is_ok();
+        match ok && date.len() > 0 && time.len() > 0 && name.len() > 0 {
+            true => Some(dir.join(name)),
+            false => None,
+        }
+    }
+
+    /// Return the compression for disk location, None if the file is not
+    /// compressed.
+    pub fn to_compress(&self) -> Option<Compress> {
+        match self {
+            Location::Disk { compress, .. } => *compress,
+            _ => None,
+        }
+    }
+
+    /// Return whether the file was modified on disk, by another program,
+    /// since it was last read or written from this location. Missing
+    /// files are not treated as changed.
+    pub fn is_changed(&self) -> bool {
+        use std::fs;
+
+        match self {
+            Location::Disk {
+                path_file,
+                stat: Some(stat),
+                ..
+            } => match fs::metadata(path_file).ok().and_then(|m| to_stat(&m)) {
+                Some(new_stat) => new_stat != *stat,
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
     /// Return whether the buffer is read-only.
     pub fn is_read_only(&self) -> bool {
         match self {
@@ -218,6 +587,79 @@ impl fmt::Display for Location {
     }
 }
 
+// directory's path, with a trailing separator.
+fn to_dir_name(dir: &path::Path) -> String {
+    let mut name = dir.to_string_lossy().to_string();
+    if !name.ends_with(path::MAIN_SEPARATOR) {
+        name.push(path::MAIN_SEPARATOR);
+    }
+    name
+}
+
+// format modified-time, in local time zone, as `YYYY-MM-DD HH:MM`.
+fn to_mtime(t: time::SystemTime) -> String {
+    let t: chrono::DateTime<chrono::Local> = t.into();
+    t.format("%Y-%m-%d %H:%M").to_string()
+}
+
+// modified-time and size of file, to detect changes done by other programs.
+fn to_stat(m: &std::fs::Metadata) -> Option<(time::SystemTime, u64)> {
+    m.modified().ok().map(|t| (t, m.len()))
+}
+
+// open file for reading, decompressing its content if `compress` is
+// specified.
+fn open_reader(
+    path_file: &ffi::OsStr,
+    compress: &Option<Compress>,
+) -> Result<Box<dyn io::Read + 'static>> {
+    use std::fs;
+
+    let fd = {
+        let mut oo = fs::OpenOptions::new();
+        err_at!(IOError, oo.read(true).open(path_file))?
+    };
+    match compress {
+        Some(compress) => Ok(compress.to_reader(fd)),
+        None => Ok(Box::new(fd)),
+    }
+}
+
+// stream utf-8 text from `r` into a rope, if `bom` is true leading
+// byte-order-mark is skipped. Multi-byte characters split between two
+// reads are carried over to the next read.
+fn utf8_to_rope<R>(mut r: R, bom: bool) -> Result<Rope>
+where
+    R: io::Read,
+{
+    use std::str::from_utf8;
+
+    let mut builder = RopeBuilder::new();
+    let mut buf = vec![0_u8; 64 * 1024];
+    let (mut n, mut first) = (0, true);
+    loop {
+        let m = err_at!(IOError, r.read(&mut buf[n..]))?;
+        n += m;
+        let valid = match from_utf8(&buf[..n]) {
+            Ok(_) => n,
+            Err(err) if m > 0 && err.error_len().is_none() => err.valid_up_to(),
+            Err(err) => err_at!(FailConvert, Err(err))?,
+        };
+        let s = err_at!(FailConvert, from_utf8(&buf[..valid]))?;
+        match s.chars().next() {
+            Some('\u{feff}') if first && bom => builder.append(&s['\u{feff}'.len_utf8()..]),
+            _ => builder.append(s),
+        }
+        first = first && valid == 0;
+
+        buf.copy_within(valid..n, 0);
+        n -= valid;
+        if m == 0 {
+            break Ok(builder.finish());
+        }
+    }
+}
+
 // convert obsolute path to path relative to cwd or home.
 fn disk_cwd_loc(loc: path::PathBuf) -> Result<ffi::OsString> {
     use std::env;
@@ -334,3 +776,7 @@ fn disk_to_tab_title(loc: &ffi::OsStr, mut wth: usize) -> Result<String> {
 
     Ok(acc)
 }
+
+#[cfg(test)]
+#[path = "location_test.rs"]
+mod location_test;
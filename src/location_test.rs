@@ -0,0 +1,83 @@
+use super::*;
+
+#[test]
+fn test_new_dir() {
+    use std::fs;
+
+    let dir = std::env::temp_dir().join("ted-test-new-dir");
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join("b.txt"), "hello").unwrap();
+    fs::write(dir.join("a file.txt"), "").unwrap();
+    let dir = fs::canonicalize(&dir).unwrap();
+
+    let loc = Location::new_disk(dir.as_os_str(), None).unwrap();
+    assert!(loc.is_read_only());
+    assert_eq!(loc.to_dir(), Some(dir.clone()));
+
+    let text = loc.read().unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 5, "{:?}", lines);
+    assert_eq!(lines[0], to_dir_name(&dir));
+    assert_eq!(lines[1], "../");
+    assert!(lines[2].starts_with("           - "), "{:?}", lines[2]);
+    assert!(lines[3].starts_with("           0 "), "{:?}", lines[3]);
+    assert!(lines[4].starts_with("           5 "), "{:?}", lines[4]);
+
+    let entries: Vec<Option<path::PathBuf>> = lines.iter().map(|l| loc.to_dir_entry(l)).collect();
+    assert_eq!(entries[0], None);
+    assert_eq!(entries[1], dir.parent().map(|p| p.to_path_buf()));
+    assert_eq!(entries[2], Some(dir.join("sub")));
+    assert_eq!(entries[3], Some(dir.join("a file.txt")));
+    assert_eq!(entries[4], Some(dir.join("b.txt")));
+
+    let loc = Location::new_disk(dir.join("b.txt").as_os_str(), None).unwrap();
+    assert_eq!(loc.to_dir(), None);
+    assert_eq!(loc.to_dir_entry(lines[4]), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_save_symlink() {
+    use std::{fs, os::unix::fs::symlink};
+
+    let dir = std::env::temp_dir().join("ted-test-save-symlink");
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    let (file, link) = (dir.join("file.txt"), dir.join("link.txt"));
+    fs::write(&file, "hello\n").unwrap();
+    symlink(&file, &link).unwrap();
+
+    let loc = Location::new_disk(link.as_os_str(), None).unwrap();
+    loc.save("hello world\n", false).unwrap();
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(fs::read_to_string(&file).unwrap(), "hello world\n");
+    assert_eq!(fs::read_to_string(&link).unwrap(), "hello world\n");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_read_tail() {
+    use std::fs;
+
+    let dir = std::env::temp_dir().join("ted-test-read-tail");
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("file.log");
+    fs::write(&file, "hello\n").unwrap();
+
+    let loc = Location::new_disk(file.as_os_str(), None).unwrap();
+    assert_eq!(loc.read_tail(6).unwrap(), Some(vec![]));
+    fs::write(&file, "hello\nworld\n").unwrap();
+    assert_eq!(loc.read_tail(6).unwrap(), Some(b"world\n".to_vec()));
+    fs::write(&file, "hey\n").unwrap();
+    assert_eq!(loc.read_tail(6).unwrap(), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -16,10 +16,14 @@ fn main() {
     }
 
     std::panic::set_hook(box |panic_info| {
-        let s = match panic_info.payload().downcast_ref::<String>() {
+        let mut s = match panic_info.payload().downcast_ref::<String>() {
             Some(s) => s.to_string(),
             None => "???".to_string(),
         };
+        // flush journals of modified buffers, to recover unsaved edits.
+        if let Err(err) = ted::swap::flush() {
+            s.push_str(&format!(", swap: {}", err));
+        }
         let mut strng = format!("panic occured: {}", s);
         strng.push_str(&format!("{}", std::backtrace::Backtrace::capture()));
         fs::write("ted-panic.out", strng.as_bytes()).unwrap();
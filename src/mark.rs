@@ -38,6 +38,20 @@ impl From<char> for Mark {
 }
 
 impl Mark {
+    /// Create mark `index` at `cursor` in buffer, like when restoring a
+    /// session.
+    pub fn new(index: char, buf: &Buffer, cursor: usize) -> Mark {
+        let cursor = std::cmp::min(cursor, buf.to_rope().len_chars());
+        let bc_xy = buf.to_xy_cursor(Some(cursor));
+        Mark {
+            index,
+            buf_id: buf.to_id(),
+            cursor,
+            col: bc_xy.col,
+            row: bc_xy.row,
+        }
+    }
+
     pub fn into_mark(mut self, buf: &Buffer) -> Self {
         self.buf_id = buf.to_id();
         self.cursor = buf.to_char_cursor();
@@ -42,7 +42,7 @@ impl PubSub {
             0 => None,
             1 if subs[0].topic == tp => Some(0),
             1 => None,
-            m if tp < &subs[m / 2].topic => Self::find_topic(tp, &subs[..m / 2]),
+            m if *tp < *subs[m / 2].topic => Self::find_topic(tp, &subs[..m / 2]),
             m => Self::find_topic(tp, &subs[m / 2..]).map(|n| (m / 2) + n),
         }
     }
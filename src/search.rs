@@ -0,0 +1,474 @@
+//! Module implement vim-style search patterns.
+//!
+//! Patterns typed by the user follow vim conventions, they are translated
+//! into [regex] syntax before compiling them. Following vim atoms are
+//! supported:
+//!
+//! * `\<` and `\>`, word boundary, translated to `\b`.
+//! * `\v`, `\m`, `\V`, switch between very-magic, magic and no-magic.
+//! * `\c` and `\C`, ignore case and match case for the whole pattern.
+//! * `\{n,m}`, `\{-n,m}`, repetition, greedy and non-greedy.
+//! * `\(`, `\)`, `\|`, `\+`, `\=`, `\?` in magic mode.
+//! * Character classes `\s`, `\d`, `\w`, `\a`, `\l`, `\u`, `\x`, `\o`, `\h`.
+//!
+//! A search command can also carry an offset and chain more searches,
+//! like `/foo/e+1` or `/foo/;/bar`, refer [Search::parse] for details.
+//!
+//! Substitute command `:s/{pattern}/{string}/[flags]` use the same pattern
+//! syntax, refer [Substitute::parse] for details.
+
+use regex::Regex;
+
+use crate::{event::DP, Error, Result};
+
+/// Offset to apply on the cursor, after a successful match.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Offset {
+    /// `[+-]n`, n lines below/above the match, cursor at first non-blank.
+    Line(isize),
+    /// `e[+-n]`, n characters right/left from the end of the match.
+    End(isize),
+    /// `s[+-n]` or `b[+-n]`, n characters right/left from the start.
+    Start(isize),
+    /// No offset, cursor at the start of the match.
+    None,
+}
+
+impl Default for Offset {
+    fn default() -> Offset {
+        Offset::None
+    }
+}
+
+/// Single search command, a `;` chained search command is parsed into
+/// a list of Search values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Search {
+    /// Pattern in vim syntax.
+    pub patt: String,
+    /// Direction of search, either [DP::Right] or [DP::Left].
+    pub dp: DP,
+    /// Cursor offset from the matching text.
+    pub offset: Offset,
+}
+
+impl Search {
+    /// Parse search command, excluding the leading `/` or `?`, into a
+    /// list of searches. `dp` is the direction of the first search,
+    /// [DP::Right] uses `/` as delimiter and [DP::Left] uses `?` as
+    /// delimiter. Subsequent searches chained with `;` carry their own
+    /// delimiter. An empty pattern re-uses the preceding pattern in the
+    /// chain, or `last` for the first search, like `//e`.
+    pub fn parse(text: &str, dp: DP, last: Option<&str>) -> Result<Vec<Search>> {
+        let mut last = last.map(|s| s.to_string());
+        let mut searches = vec![];
+        let mut chars: Vec<char> = text.chars().collect();
+        let mut dp = dp;
+        loop {
+            let delim = match dp {
+                DP::Right => '/',
+                DP::Left => '?',
+                _ => err_at!(Fatal, msg: format!("unreachable"))?,
+            };
+            let (patt, rest) = split_delim(&chars, delim);
+            let patt = match (patt.as_str(), &last) {
+                ("", Some(last)) => last.clone(),
+                ("", None) => err_at!(BadPattern, msg: format!("no previous pattern"))?,
+                (_, _) => patt,
+            };
+            last = Some(patt.clone());
+            let (offset, rest) = parse_offset(&rest)?;
+            searches.push(Search { patt, dp, offset });
+
+            chars = match rest.split_first() {
+                None => break Ok(searches),
+                Some((';', rest)) => match rest.split_first() {
+                    Some(('/', rest)) => {
+                        dp = DP::Right;
+                        rest.to_vec()
+                    }
+                    Some(('?', rest)) => {
+                        dp = DP::Left;
+                        rest.to_vec()
+                    }
+                    _ => {
+                        let msg = format!("expected / or ? after ; in {:?}", text);
+                        err_at!(BadPattern, msg: msg)?
+                    }
+                },
+                Some(_) => {
+                    let msg = format!("trailing characters in {:?}", text);
+                    err_at!(BadPattern, msg: msg)?
+                }
+            };
+        }
+    }
+
+    /// Compile this search's pattern, refer [to_regex] for details.
+    pub fn to_regex(&self, ignore_case: bool, smart_case: bool) -> Result<Regex> {
+        to_regex(&self.patt, ignore_case, smart_case)
+    }
+}
+
+/// Substitute command, `:s/{pattern}/{string}/[flags]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Substitute {
+    /// Search pattern, always in [DP::Right] direction without offset.
+    pub search: Search,
+    /// Replacement string, translated to [regex] replacement syntax.
+    pub replace: String,
+    /// `g` flag, replace all matches in the line, instead of the first.
+    pub global: bool,
+    /// `i` flag forces ignore-case, `I` flag forces match-case.
+    pub ignore_case: Option<bool>,
+    /// `e` flag, pattern not found is not an error.
+    pub no_error: bool,
+}
+
+impl Substitute {
+    /// Parse substitute command, excluding the command name. First
+    /// character is the delimiter, which cannot be alpha-numeric, `\`,
+    /// `"`, `|` or white-space. Empty pattern re-uses `last` pattern.
+    /// In the replacement string:
+    ///
+    /// * `&` and `\0` is the whole match, `\&` is a literal `&`.
+    /// * `\1` to `\9` is the n-th sub-match, captured using `\(` and `\)`.
+    /// * `\r` and `\n` insert a line break, `\t` insert a tab.
+    ///
+    /// Supported flags are `g`, `i`, `I` and `e`. Case modifiers like
+    /// `\u`, `\U`, `~` for the previous replacement string, `[count]`
+    /// and the `c` flag are not supported.
+    pub fn parse(text: &str, last: Option<&str>) -> Result<Substitute> {
+        let chars: Vec<char> = text.chars().collect();
+        let delim = match chars.first() {
+            Some(ch) if ch.is_alphanumeric() || ch.is_whitespace() || "\\\"|".contains(*ch) => {
+                err_at!(BadPattern, msg: format!("invalid delimiter {:?}", ch))?
+            }
+            Some(ch) => *ch,
+            None => err_at!(BadPattern, msg: format!("missing pattern"))?,
+        };
+
+        let (patt, rest) = split_delim(&chars[1..], delim);
+        let patt = match (patt.as_str(), last) {
+            ("", Some(last)) => last.to_string(),
+            ("", None) => err_at!(BadPattern, msg: format!("no previous pattern"))?,
+            (_, _) => patt,
+        };
+        let (replace, rest) = split_delim(&rest, delim);
+        let replace = translate_replace(&replace)?;
+
+        let mut val = Substitute {
+            search: Search {
+                patt,
+                dp: DP::Right,
+                offset: Offset::None,
+            },
+            replace,
+            global: false,
+            ignore_case: None,
+            no_error: false,
+        };
+        for ch in rest.into_iter() {
+            match ch {
+                'g' => val.global = true,
+                'i' => val.ignore_case = Some(true),
+                'I' => val.ignore_case = Some(false),
+                'e' => val.no_error = true,
+                ch => err_at!(BadPattern, msg: format!("invalid flag {:?} in {:?}", ch, text))?,
+            }
+        }
+        Ok(val)
+    }
+
+    /// Compile substitute pattern, refer [to_regex] for details. `i` and
+    /// `I` flags override both options.
+    pub fn to_regex(&self, ignore_case: bool, smart_case: bool) -> Result<Regex> {
+        match self.ignore_case {
+            Some(ignore_case) => self.search.to_regex(ignore_case, false),
+            None => self.search.to_regex(ignore_case, smart_case),
+        }
+    }
+}
+
+/// Translate vim pattern `patt` and compile it into regex. If
+/// `ignore_case` is true, match is case insensitive, unless `smart_case`
+/// is true and the pattern contains an upper-case character. `\c` and
+/// `\C` within the pattern override both options.
+pub fn to_regex(patt: &str, ignore_case: bool, smart_case: bool) -> Result<Regex> {
+    let (re, case) = translate(patt)?;
+    let icase = match case {
+        Some(icase) => icase,
+        None if ignore_case && smart_case => !has_upper(patt),
+        None => ignore_case,
+    };
+    let re = if_else!(icase, format!("(?mi){}", re), format!("(?m){}", re));
+    err_at!(BadPattern, Regex::new(&re), format!("{:?}", patt))
+}
+
+/// Escape `text` so that it can be used as literal vim pattern in
+/// `dp` direction, refer [Search::parse] for delimiters.
+pub fn escape(text: &str, dp: DP) -> String {
+    let delim = if_else!(dp == DP::Left, '?', '/');
+    let mut out = String::default();
+    for ch in text.chars() {
+        match ch {
+            '\\' | '.' | '*' | '[' | '~' | '^' | '$' => out.push('\\'),
+            ch if ch == delim => out.push('\\'),
+            _ => (),
+        }
+        out.push(ch);
+    }
+    out
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Magic {
+    Very,
+    Magic,
+    No,
+}
+
+/// Translate vim pattern into regex syntax. Return the translated pattern
+/// and case-sensitivity, if forced within the pattern using `\c` or `\C`.
+pub fn translate(patt: &str) -> Result<(String, Option<bool>)> {
+    let chars: Vec<char> = patt.chars().collect();
+    let (mut out, mut icase, mut magic) = (String::default(), None, Magic::Magic);
+
+    let mut i = 0;
+    while i < chars.len() {
+        let (ch, next) = (chars[i], chars.get(i + 1).cloned());
+        i += 1;
+        match (ch, next) {
+            ('\\', None) => out.push_str("\\\\"),
+            ('\\', Some(next)) => {
+                i += 1;
+                match next {
+                    'c' => icase = Some(true),
+                    'C' => icase = Some(false),
+                    'v' => magic = Magic::Very,
+                    'm' => magic = Magic::Magic,
+                    'V' | 'M' => magic = Magic::No,
+                    '{' if magic != Magic::Very => {
+                        i = multi(&chars, i, &mut out)?;
+                    }
+                    '<' | '>' if magic != Magic::Very => out.push_str("\\b"),
+                    '(' | ')' | '|' | '+' if magic == Magic::Magic => out.push(next),
+                    '=' | '?' if magic == Magic::Magic => out.push('?'),
+                    '.' | '*' if magic == Magic::No => out.push(next),
+                    '[' if magic == Magic::No => i = class(&chars, i, &mut out),
+                    '1'..='9' => {
+                        let msg = format!("back-reference not supported {:?}", patt);
+                        err_at!(BadPattern, msg: msg)?
+                    }
+                    next => match char_class(next) {
+                        Some(cls) => out.push_str(cls),
+                        None => out.push_str(&regex::escape(&next.to_string())),
+                    },
+                }
+            }
+            ('.', _) | ('*', _) if magic != Magic::No => out.push(ch),
+            ('[', _) if magic != Magic::No => i = class(&chars, i, &mut out),
+            ('^', _) | ('$', _) => out.push(ch),
+            ('<', _) | ('>', _) if magic == Magic::Very => out.push_str("\\b"),
+            ('(', _) | (')', _) | ('|', _) | ('+', _) if magic == Magic::Very => out.push(ch),
+            ('=', _) | ('?', _) if magic == Magic::Very => out.push('?'),
+            ('{', _) if magic == Magic::Very => i = multi(&chars, i, &mut out)?,
+            (ch, _) => out.push_str(&regex::escape(&ch.to_string())),
+        }
+    }
+
+    Ok((out, icase))
+}
+
+// translate `{n,m}` repetition, `i` points to the character after `{`.
+fn multi(chars: &[char], mut i: usize, out: &mut String) -> Result<usize> {
+    let mut spec = String::default();
+    loop {
+        match chars.get(i) {
+            Some('}') => break,
+            Some('\\') if chars.get(i + 1) == Some(&'}') => {
+                i += 1;
+                break;
+            }
+            Some(ch) => spec.push(*ch),
+            None => err_at!(BadPattern, msg: format!("missing }} in repetition"))?,
+        }
+        i += 1;
+    }
+
+    let (lazy, spec) = match spec.strip_prefix('-') {
+        Some(spec) => (true, spec.to_string()),
+        None => (false, spec),
+    };
+    let spec = match spec.as_str() {
+        "" | "," => "*".to_string(),
+        spec if spec.starts_with(',') => format!("{{0{}}}", spec),
+        spec => format!("{{{}}}", spec),
+    };
+    out.push_str(&spec);
+    if lazy {
+        out.push('?');
+    }
+    Ok(i + 1)
+}
+
+// copy the bracket expression `[...]`, `i` points to the character
+// after `[`. Unterminated brackets are treated as literal `[`.
+fn class(chars: &[char], i: usize, out: &mut String) -> usize {
+    let mut j = i;
+    if let Some('^') = chars.get(j) {
+        j += 1;
+    }
+    if let Some(']') = chars.get(j) {
+        j += 1;
+    }
+    while j < chars.len() && chars[j] != ']' {
+        j += if_else!(chars[j] == '\\', 2, 1);
+    }
+    if j >= chars.len() {
+        out.push_str("\\[");
+        i
+    } else {
+        out.push('[');
+        for ch in chars[i..j].iter() {
+            match ch {
+                '[' => out.push_str("\\["),
+                ch => out.push(*ch),
+            }
+        }
+        out.push(']');
+        j + 1
+    }
+}
+
+fn char_class(ch: char) -> Option<&'static str> {
+    let cls = match ch {
+        's' => "\\s",
+        'S' => "\\S",
+        'd' => "\\d",
+        'D' => "\\D",
+        'w' => "[0-9A-Za-z_]",
+        'W' => "[^0-9A-Za-z_]",
+        'a' => "[A-Za-z]",
+        'A' => "[^A-Za-z]",
+        'l' => "[a-z]",
+        'L' => "[^a-z]",
+        'u' => "[A-Z]",
+        'U' => "[^A-Z]",
+        'x' => "[0-9A-Fa-f]",
+        'X' => "[^0-9A-Fa-f]",
+        'o' => "[0-7]",
+        'O' => "[^0-7]",
+        'h' => "[A-Za-z_]",
+        'H' => "[^A-Za-z_]",
+        'n' => "\\n",
+        't' => "\\t",
+        'r' => "\\r",
+        'e' => "\\x1b",
+        _ => return None,
+    };
+    Some(cls)
+}
+
+// upper-case characters, skipping escaped atoms like `\S`, `\W`, `\C`.
+fn has_upper(patt: &str) -> bool {
+    let mut iter = patt.chars();
+    while let Some(ch) = iter.next() {
+        match ch {
+            '\\' => {
+                iter.next();
+            }
+            ch if ch.is_uppercase() => return true,
+            _ => (),
+        }
+    }
+    false
+}
+
+// split at the first un-escaped delimiter, escaped delimiters are
+// unescaped in the pattern.
+fn split_delim(chars: &[char], delim: char) -> (String, Vec<char>) {
+    let mut patt = String::default();
+    let mut i = 0;
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            ('\\', Some(ch)) if *ch == delim => {
+                patt.push(delim);
+                i += 2;
+            }
+            ('\\', Some(ch)) => {
+                patt.push('\\');
+                patt.push(*ch);
+                i += 2;
+            }
+            (ch, _) if ch == delim => return (patt, chars[i + 1..].to_vec()),
+            (ch, _) => {
+                patt.push(ch);
+                i += 1;
+            }
+        }
+    }
+    (patt, vec![])
+}
+
+// translate vim replacement string into regex replacement syntax.
+fn translate_replace(replace: &str) -> Result<String> {
+    let mut out = String::default();
+    let mut iter = replace.chars();
+    while let Some(ch) = iter.next() {
+        match (ch, iter.clone().next()) {
+            ('&', _) => out.push_str("${0}"),
+            ('$', _) => out.push_str("$$"),
+            ('\\', None) => out.push('\\'),
+            ('\\', Some(next)) => {
+                iter.next();
+                match next {
+                    '0'..='9' => out.push_str(&format!("${{{}}}", next)),
+                    'r' | 'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'u' | 'U' | 'l' | 'L' | 'e' | 'E' => {
+                        let msg = format!("case modifier not supported {:?}", replace);
+                        err_at!(BadPattern, msg: msg)?
+                    }
+                    '$' => out.push_str("$$"),
+                    next => out.push(next),
+                }
+            }
+            (ch, _) => out.push(ch),
+        }
+    }
+    Ok(out)
+}
+
+// parse offset, till the end or till the `;` character.
+fn parse_offset(chars: &[char]) -> Result<(Offset, Vec<char>)> {
+    let n = chars.iter().take_while(|ch| **ch != ';').count();
+    let (spec, rest): (String, Vec<char>) = {
+        let spec = chars[..n].iter().collect();
+        (spec, chars[n..].to_vec())
+    };
+
+    let num = |s: &str| -> Result<isize> {
+        match s {
+            "" => Ok(0),
+            "+" => Ok(1),
+            "-" => Ok(-1),
+            s => {
+                let s = s.strip_prefix('+').unwrap_or(s);
+                err_at!(BadPattern, s.parse::<isize>(), format!("offset {:?}", s))
+            }
+        }
+    };
+
+    let offset = match spec.chars().next() {
+        None => Offset::None,
+        Some('e') => Offset::End(num(&spec[1..])?),
+        Some('s') | Some('b') => Offset::Start(num(&spec[1..])?),
+        Some(_) => Offset::Line(num(&spec)?),
+    };
+    Ok((offset, rest))
+}
+
+#[cfg(test)]
+#[path = "search_test.rs"]
+mod search_test;
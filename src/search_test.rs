@@ -0,0 +1,121 @@
+use super::*;
+
+#[test]
+fn test_translate() {
+    let testcases = vec![
+        (r"\<foo\>", r"\bfoo\b", None),
+        (r"\vfoo(bar|baz)+", r"foo(bar|baz)+", None),
+        (r"\v<foo>", r"\bfoo\b", None),
+        (r"a\{2,3}", r"a{2,3}", None),
+        (r"a\{-1,}", r"a{1,}?", None),
+        (r"a\{,3}", r"a{0,3}", None),
+        (r"\cfoo", r"foo", Some(true)),
+        (r"foo\C", r"foo", Some(false)),
+        (r"f(o)o+", r"f\(o\)o\+", None),
+        (r"\(foo\|bar\)", r"(foo|bar)", None),
+        (r"\Vf.o*", r"f\.o\*", None),
+        (r"[a-z]\+", r"[a-z]+", None),
+    ];
+    for (patt, re, icase) in testcases.into_iter() {
+        let res = translate(patt).unwrap();
+        assert_eq!(res, (re.to_string(), icase), "{}", patt);
+    }
+}
+
+#[test]
+fn test_to_regex() {
+    assert!(to_regex("foo", true, false).unwrap().is_match("FOO"));
+    assert!(!to_regex("foo", false, false).unwrap().is_match("FOO"));
+    assert!(!to_regex("Foo", true, true).unwrap().is_match("FOO"));
+    assert!(to_regex("foo", true, true).unwrap().is_match("FOO"));
+    assert!(to_regex(r"\cfoo", false, false).unwrap().is_match("FOO"));
+    assert!(!to_regex(r"foo\C", true, false).unwrap().is_match("FOO"));
+    assert!(to_regex(r"\Sfoo", true, true).unwrap().is_match("XFOO"));
+}
+
+#[test]
+fn test_escape() {
+    let patt = escape("a.b/c?d", DP::Right);
+    assert_eq!(patt, r"a\.b\/c?d");
+    let searches = Search::parse(&patt, DP::Right, None).unwrap();
+    assert!(searches[0]
+        .to_regex(false, false)
+        .unwrap()
+        .is_match("a.b/c?d"));
+
+    let patt = escape("a.b/c?d", DP::Left);
+    let searches = Search::parse(&patt, DP::Left, None).unwrap();
+    assert!(searches[0]
+        .to_regex(false, false)
+        .unwrap()
+        .is_match("a.b/c?d"));
+}
+
+#[test]
+fn test_search_parse() {
+    let searches = Search::parse("foo", DP::Right, None).unwrap();
+    let s = Search {
+        patt: "foo".to_string(),
+        dp: DP::Right,
+        offset: Offset::None,
+    };
+    assert_eq!(searches, vec![s]);
+
+    let searches = Search::parse(r"a\/b/e+1;?bar?-", DP::Right, None).unwrap();
+    assert_eq!(searches.len(), 2);
+    assert_eq!(searches[0].patt, "a/b");
+    assert_eq!(searches[0].offset, Offset::End(1));
+    assert_eq!(searches[1].patt, "bar");
+    assert_eq!(searches[1].dp, DP::Left);
+    assert_eq!(searches[1].offset, Offset::Line(-1));
+
+    let searches = Search::parse("foo?s-2", DP::Left, None).unwrap();
+    assert_eq!(searches[0].offset, Offset::Start(-2));
+    let searches = Search::parse("foo/3", DP::Right, None).unwrap();
+    assert_eq!(searches[0].offset, Offset::Line(3));
+
+    assert!(Search::parse("foo/e;bar", DP::Right, None).is_err());
+
+    // empty pattern re-uses the previous pattern.
+    let searches = Search::parse("/e", DP::Right, Some("foo")).unwrap();
+    assert_eq!(searches[0].patt, "foo");
+    assert_eq!(searches[0].offset, Offset::End(0));
+    let searches = Search::parse("foo/;??", DP::Right, Some("bar")).unwrap();
+    assert_eq!(searches[1].patt, "foo");
+    assert_eq!(searches[1].dp, DP::Left);
+    assert!(Search::parse("", DP::Right, None).is_err());
+}
+
+#[test]
+fn test_substitute_parse() {
+    let subst = Substitute::parse(r"/f\(o\+\)/x&\1\&$\t/g", None).unwrap();
+    assert_eq!(subst.search.patt, r"f\(o\+\)");
+    assert_eq!(subst.replace, "x${0}${1}&$$\t");
+    assert_eq!(subst.global, true);
+    assert_eq!(subst.ignore_case, None);
+    let re = subst.to_regex(false, false).unwrap();
+    assert_eq!(
+        re.replace_all("a foo", subst.replace.as_str()),
+        "a xfoooo&$\t"
+    );
+
+    let subst = Substitute::parse(r"#a\#b#c\rd#Ie", None).unwrap();
+    assert_eq!(subst.search.patt, "a#b");
+    assert_eq!(subst.replace, "c\nd");
+    assert_eq!(subst.global, false);
+    assert_eq!(subst.ignore_case, Some(false));
+    assert_eq!(subst.no_error, true);
+    assert!(!subst.to_regex(true, false).unwrap().is_match("A#B"));
+
+    let subst = Substitute::parse("/foo", None).unwrap();
+    assert_eq!(subst.replace, "");
+    let subst = Substitute::parse("//bar/i", Some("foo")).unwrap();
+    assert_eq!(subst.search.patt, "foo");
+    assert!(subst.to_regex(false, false).unwrap().is_match("FOO"));
+
+    assert!(Substitute::parse("//bar/", None).is_err());
+    assert!(Substitute::parse("afooabara", None).is_err());
+    assert!(Substitute::parse("/foo/bar/x", None).is_err());
+    assert!(Substitute::parse(r"/foo/\u&/", None).is_err());
+    assert!(Substitute::parse("", None).is_err());
+}
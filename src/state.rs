@@ -22,12 +22,12 @@ use structopt::StructOpt;
 use std::{
     cmp,
     convert::{TryFrom, TryInto},
-    ffi, fs,
+    ffi, fs, io,
     iter::FromIterator,
     mem, path,
     str::FromStr,
-    sync::mpsc,
-    time,
+    sync::{mpsc, Arc, Mutex},
+    thread, time,
 };
 
 use crate::{
@@ -35,6 +35,7 @@ use crate::{
     colors::{self, ColorScheme},
     config,
     event::Event,
+    location::Location,
     pubsub::{Notify, PubSub},
     term::{self, Terminal},
     util,
@@ -69,9 +70,18 @@ pub struct Opt {
     #[structopt(long = "stats")]
     pub stats: bool,
 
+    #[structopt(long = "encoding")]
+    pub encoding: Option<String>,
+
     #[structopt(short = "R", long = "read-only")]
     pub read_only: bool,
 
+    #[structopt(short = "b", long = "binary")]
+    pub binary: bool,
+
+    #[structopt(long = "session")]
+    pub session: Option<String>,
+
     #[structopt(short = "p")]
     pub tab_page: bool,
 
@@ -118,6 +128,11 @@ pub struct State {
     pub schemes: Vec<ColorScheme>,
     /// Global subscribe-publish instance.
     pub subscribers: PubSub,
+    /// Content piped into standard input, read before taking over the
+    /// terminal.
+    pub stdin: Option<Location>,
+    /// Standard input piped into the pager, read as the content arrives.
+    pub pipe: Option<Pipe>,
 
     // state machine for tabed-windows.
     inner: Inner,
@@ -221,13 +236,38 @@ impl Inner {
             Inner::None => None,
         }
     }
+
+    fn to_timeout(&self) -> Option<time::Duration> {
+        match self {
+            Inner::Mono { tab } => tab.to_timeout(),
+            Inner::Multi { tabs, .. } => {
+                let mut iter = tabs.iter().filter(|tab| tab.active);
+                iter.next().and_then(|tab| tab.to_timeout())
+            }
+            Inner::None => None,
+        }
+    }
 }
 
 impl TryFrom<Opt> for State {
     type Error = Error;
 
     fn try_from(opts: Opt) -> Result<State> {
-        // first the terminal
+        // first drain the piped input, `-` stands for standard input.
+        let enc = opts.encoding.as_deref();
+        let piped = opts.files.len() == 0 || opts.files.iter().any(|f| f == "-");
+        let (stdin, pipe) = match opts.app.as_str() {
+            "less" if piped && is_stdin_tty() => {
+                let msg = format!("missing filename, usage: ted --app less <file>...");
+                err_at!(Invalid, msg: msg)?
+            }
+            "less" if piped => (None, Some(pipe_stdin(enc)?)),
+            _ if opts.files.iter().any(|f| f == "-") => {
+                (Some(read_stdin(enc, opts.read_only)?), None)
+            }
+            _ => (None, None),
+        };
+        // then the terminal
         let tm = Terminal::init()?;
         // then the logger
         init_logger(&opts)?;
@@ -247,6 +287,8 @@ impl TryFrom<Opt> for State {
             tm,
             schemes,
             subscribers: PubSub::default(),
+            stdin,
+            pipe,
             inner: Inner::default(),
         })
     }
@@ -271,6 +313,10 @@ impl State {
                 let coord = state.tm.to_screen_coord();
                 Ok(App::Code((&state, coord).try_into()?))
             }
+            "less" => {
+                let coord = state.tm.to_screen_coord();
+                Ok(App::Less((&state, coord).try_into()?))
+            }
             _ => err_at!(Invalid, msg: format!("invalid app {:?}", &opts.app)),
         }?;
 
@@ -331,7 +377,7 @@ impl State {
 impl State {
     /// main event-loop.
     pub fn event_loop(mut self) -> Result<String> {
-        use crossterm::event::read;
+        use crossterm::event::{poll, read};
 
         let mut stats = util::Latency::new("EVENT");
         let mut r_stats = util::Latency::new("READT");
@@ -346,10 +392,6 @@ impl State {
 
         let mut evnts: Event = self.opts.clone().into();
         loop {
-            if evnts.clone().any(|evnt| Self::is_quit(&evnt)) {
-                break;
-            }
-
             // handle event(s)
             {
                 let start = time::Instant::now();
@@ -358,6 +400,9 @@ impl State {
 
                 evnts = self.bubble_down(&mut inner, evnts)?;
                 evnts = self.bubble_up(&mut inner, evnts)?;
+                if evnts.clone().any(|evnt| evnt == Event::Quit) {
+                    break;
+                }
 
                 inner.on_refresh(&self)?;
                 if let Some(cursor) = inner.to_cursor() {
@@ -369,9 +414,12 @@ impl State {
 
             evnts.drain();
 
-            // new event
+            // new event, or timeout while application waits on more keys.
             let start = time::Instant::now();
-            evnts = err_at!(Fatal, read())?.into();
+            evnts = match inner.to_timeout() {
+                Some(timeout) if !err_at!(Fatal, poll(timeout))? => Event::Timeout,
+                _ => err_at!(Fatal, read())?.into(),
+            };
             r_stats.sample(start.elapsed());
         }
 
@@ -380,13 +428,6 @@ impl State {
         Ok(s)
     }
 
-    fn is_quit(evnt: &Event) -> bool {
-        match evnt {
-            Event::Char('q', _) => true,
-            _ => false,
-        }
-    }
-
     fn bubble_down(&mut self, inner: &mut Inner, evnts: Event) -> Result<Event> {
         let mut res_evnts: Event = Event::default();
         for evnt in evnts {
@@ -396,7 +437,21 @@ impl State {
     }
 
     fn bubble_up(&mut self, _inner: &mut Inner, evnts: Event) -> Result<Event> {
-        Ok(evnts)
+        use crate::event::Appn;
+
+        let mut res_evnts: Event = Event::default();
+        for evnt in evnts {
+            match evnt {
+                // option is already type-checked by the application.
+                Event::Appn(Appn::StateOption(name, op)) => {
+                    if let Err(err) = self.config.set_option(&name, &op) {
+                        error!("set option {}: {}", name, err)
+                    }
+                }
+                evnt => res_evnts.push(evnt),
+            }
+        }
+        Ok(res_evnts)
     }
 }
 
@@ -433,6 +488,10 @@ impl Tab {
         self.app.to_cursor()
     }
 
+    fn to_timeout(&self) -> Option<time::Duration> {
+        self.app.to_timeout()
+    }
+
     fn to_tab_title(&self, wth: usize, scheme: &ColorScheme) -> term::Span {
         let mut tt = self.app.to_tab_title(wth);
         tt.active = self.active;
@@ -477,6 +536,147 @@ impl TabTitle {
     }
 }
 
+/// Standard input piped into the pager. Content is read by a background
+/// thread, so that the pager can show the content as it arrives.
+#[derive(Clone)]
+pub struct Pipe {
+    inner: Arc<Mutex<PipeData>>,
+}
+
+#[derive(Default)]
+struct PipeData {
+    enc: Option<String>,
+    bytes: Vec<u8>,
+    eof: bool,
+}
+
+impl Pipe {
+    fn spawn<R>(mut r: R, encoding: Option<&str>) -> Pipe
+    where
+        R: io::Read + Send + 'static,
+    {
+        use crate::text;
+
+        let data = PipeData {
+            enc: encoding.map(|enc| enc.to_string()),
+            ..PipeData::default()
+        };
+        let inner = Arc::new(Mutex::new(data));
+        let pipe = Pipe {
+            inner: Arc::clone(&inner),
+        };
+
+        thread::spawn(move || {
+            let mut block = vec![0; 64 * 1024];
+            loop {
+                let res = r.read(&mut block);
+                let mut data = match inner.lock() {
+                    Ok(data) => data,
+                    Err(_) => break,
+                };
+                match res {
+                    Ok(0) => data.eof = true,
+                    Ok(n) => data.bytes.extend_from_slice(&block[..n]),
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => {
+                        error!("reading standard input: {}", err);
+                        data.eof = true
+                    }
+                }
+                // encoding is detected from the first read.
+                if data.enc.is_none() && (data.eof || data.bytes.len() > 0) {
+                    data.enc = Some(text::Encoding::detect_prefix(&data.bytes).to_string());
+                }
+                if data.eof {
+                    break;
+                }
+            }
+        });
+
+        pipe
+    }
+
+    /// Return the encoding, once it is detected, the bytes read after
+    /// the first `from` bytes, and whether standard input is closed.
+    pub fn to_tail(&self, from: usize) -> (Option<String>, Vec<u8>, bool) {
+        match self.inner.lock() {
+            Ok(data) => {
+                let bytes = data.bytes.get(from..).unwrap_or(&[]).to_vec();
+                (data.enc.clone(), bytes, data.eof)
+            }
+            Err(_) => (None, vec![], true),
+        }
+    }
+}
+
+/// Read all of standard input into a memory location, decoded using
+/// `encoding` or the detected encoding. Later, `/dev/tty` is re-opened
+/// as standard input, so that terminal can be switched to raw mode and
+/// keys can be read from the keyboard.
+fn read_stdin(encoding: Option<&str>, read_only: bool) -> Result<Location> {
+    use crate::text;
+    use std::io::Read;
+
+    let mut bytes = vec![];
+    err_at!(IOError, io::stdin().read_to_end(&mut bytes))?;
+    let enc = match encoding {
+        Some(enc) => enc,
+        None => text::Encoding::detect(&bytes),
+    };
+    let loc = Location::new_memory(bytes.as_slice(), enc, read_only)?;
+
+    reopen_tty()?;
+
+    Ok(loc)
+}
+
+/// Same as [read_stdin], but standard input is read in the background,
+/// refer [Pipe].
+fn pipe_stdin(encoding: Option<&str>) -> Result<Pipe> {
+    #[cfg(unix)]
+    let r = {
+        use std::os::unix::io::FromRawFd;
+
+        let fd = unsafe { libc::dup(libc::STDIN_FILENO) };
+        if fd < 0 {
+            err_at!(IOError, Err(io::Error::last_os_error()))?
+        }
+        unsafe { fs::File::from_raw_fd(fd) }
+    };
+    #[cfg(not(unix))]
+    let r = io::stdin();
+
+    reopen_tty()?;
+
+    Ok(Pipe::spawn(r, encoding))
+}
+
+// re-open `/dev/tty` as standard input, after the piped input is taken.
+fn reopen_tty() -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+
+        let tty = {
+            let mut opts = fs::OpenOptions::new();
+            err_at!(IOError, opts.read(true).write(true).open("/dev/tty"))?
+        };
+        if unsafe { libc::dup2(tty.as_raw_fd(), libc::STDIN_FILENO) } < 0 {
+            err_at!(IOError, Err(io::Error::last_os_error()))?
+        }
+    }
+
+    Ok(())
+}
+
+// return whether standard input is connected to a terminal.
+fn is_stdin_tty() -> bool {
+    #[cfg(unix)]
+    return unsafe { libc::isatty(libc::STDIN_FILENO) } == 1;
+    #[cfg(not(unix))]
+    return false;
+}
+
 fn init_logger(opts: &Opt) -> Result<()> {
     let home_dir = err_at!(
         Fatal,
@@ -521,5 +721,7 @@ fn init_logger(opts: &Opt) -> Result<()> {
 
 config![
     (scheme, String, "default".to_string()),
-    (max_tab_pages, u8, 16)
+    (max_tab_pages, u8, 16),
+    // files larger than large_file bytes are not parsed for syntax.
+    (large_file, usize, 16 * 1024 * 1024)
 ];
@@ -0,0 +1,253 @@
+//! Module `swap` implement journals for modified buffers, persisted as
+//! swap files under `~/.ted/swap/`.
+//!
+//! A journal holds the buffer's latest change, that is its text and cursor,
+//! along with the file's encoding and format. Journals are staged in memory
+//! as the buffers are edited, and written to swap files periodically, and
+//! when the application panics. When a file is opened again, its swap file
+//! is used to recover the unsaved edits.
+
+use lazy_static::lazy_static;
+#[allow(unused_imports)]
+use log::{debug, warn};
+use ropey::Rope;
+
+use std::{
+    collections::HashMap,
+    ffi, fs,
+    io::{self, Write},
+    path, process,
+    sync::{Mutex, MutexGuard, TryLockError},
+};
+
+use crate::{buffer::Buffer, location::Location, text, window::WinBuffer, Error, Result};
+
+lazy_static! {
+    // journals staged by this process, indexed by the file's path, flag
+    // is true when journal is not yet written to its swap file.
+    static ref JOURNALS: Mutex<HashMap<ffi::OsString, (Journal, bool)>> = {
+        Mutex::new(HashMap::new())
+    };
+}
+
+const MAGIC: &'static str = "ted-swap";
+
+/// Journal of a modified buffer, backed by a disk file.
+#[derive(Clone)]
+pub struct Journal {
+    pub path_file: ffi::OsString,
+    /// Process that wrote the journal.
+    pub pid: u32,
+    pub enc: String,
+    pub format: text::Format,
+    pub cursor: usize,
+    pub rope: Rope,
+}
+
+impl Journal {
+    /// Create a journal from buffer's latest change. Return None if buffer
+    /// is not backed by a disk file.
+    pub fn from_buffer(buf: &Buffer) -> Option<Journal> {
+        match buf.to_location() {
+            Location::Disk { path_file, enc, .. } => Some(Journal {
+                path_file,
+                pid: process::id(),
+                enc,
+                format: buf.format.clone(),
+                cursor: buf.to_char_cursor(),
+                rope: buf.to_rope(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Load journal from the swap file of `path_file`.
+    pub fn load(path_file: &ffi::OsStr) -> Result<Journal> {
+        match to_swap_file(path_file) {
+            Some(swap_file) => Self::load_from(path_file, &swap_file),
+            None => err_at!(IOError, msg: format!("can't find home-directory")),
+        }
+    }
+
+    /// Return whether the process, that wrote this journal, is running.
+    /// Always false on non-unix platforms.
+    #[cfg(unix)]
+    pub fn is_running(&self) -> bool {
+        unsafe { libc::kill(self.pid as libc::pid_t, 0) == 0 }
+    }
+
+    #[cfg(not(unix))]
+    pub fn is_running(&self) -> bool {
+        false
+    }
+
+    fn load_from(path_file: &ffi::OsStr, swap_file: &path::Path) -> Result<Journal> {
+        let data = err_at!(IOError, fs::read_to_string(swap_file))?;
+        let (header, txt) = match data.find("\n\n") {
+            Some(n) => (&data[..n], &data[n + 2..]),
+            None => err_at!(Invalid, msg: format!("invalid swap file {:?}", swap_file))?,
+        };
+
+        let mut lines = header.lines();
+        if lines.next() != Some(MAGIC) {
+            err_at!(Invalid, msg: format!("invalid swap file {:?}", swap_file))?
+        }
+        let mut journal = Journal {
+            path_file: path_file.to_os_string(),
+            pid: 0,
+            enc: "utf-8".to_string(),
+            format: text::Format::Unix,
+            cursor: 0,
+            rope: Rope::from_str(txt),
+        };
+        for line in lines {
+            let (key, val) = match line.find(' ') {
+                Some(n) => (&line[..n], &line[n + 1..]),
+                None => (line, ""),
+            };
+            match key {
+                "file" => (),
+                "pid" => journal.pid = err_at!(FailConvert, val.parse())?,
+                "encoding" => journal.enc = val.to_string(),
+                "format" => journal.format = val.parse()?,
+                "cursor" => journal.cursor = err_at!(FailConvert, val.parse())?,
+                _ => warn!("swap file {:?} unknown entry {:?}", swap_file, line),
+            }
+        }
+        Ok(journal)
+    }
+
+    // write journal to a temporary file and rename it as `swap_file`.
+    fn save_to(&self, swap_file: &path::Path) -> Result<()> {
+        if let Some(dir) = swap_file.parent() {
+            err_at!(IOError, fs::create_dir_all(dir))?;
+        }
+        let tmp_file = swap_file.with_extension("swp-tmp");
+        let res = {
+            let fd = err_at!(IOError, fs::File::create(&tmp_file))?;
+            let mut w = io::BufWriter::new(fd);
+            err_at!(IOError, self.write(&mut w))
+                .and_then(|_| err_at!(IOError, w.flush()))
+                .and_then(|_| err_at!(IOError, fs::rename(&tmp_file, swap_file)))
+        };
+        if res.is_err() {
+            fs::remove_file(&tmp_file).ok();
+        }
+        res
+    }
+
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(w, "{}", MAGIC)?;
+        writeln!(w, "file {}", self.path_file.to_string_lossy())?;
+        writeln!(w, "pid {}", self.pid)?;
+        writeln!(w, "encoding {}", self.enc)?;
+        writeln!(w, "format {}", self.format)?;
+        writeln!(w, "cursor {}", self.cursor)?;
+        writeln!(w, "")?;
+        for chunk in self.rope.chunks() {
+            w.write_all(chunk.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Return the swap file for `path_file`, path separators in the file's
+/// path are replaced with `%`. Return None if home-directory is not found.
+pub fn to_swap_file(path_file: &ffi::OsStr) -> Option<path::PathBuf> {
+    let home = dirs::home_dir()?.into_os_string();
+    let name: ffi::OsString = to_swap_name(path_file).into();
+    Some([home, ".ted".into(), "swap".into(), name].iter().collect())
+}
+
+/// Return whether a swap file exists for `path_file`, that was not
+/// written by this process, typically left behind by a crash.
+pub fn is_foreign(path_file: &ffi::OsStr) -> bool {
+    let owned = match lock_journals() {
+        Ok(journals) => journals.contains_key(path_file),
+        Err(_) => false,
+    };
+    let exists = to_swap_file(path_file).map(|f| f.exists());
+    !owned && exists.unwrap_or(false)
+}
+
+/// Stage journals of modified buffers. Journals staged earlier, whose
+/// buffers are no longer modified, are dropped along with their swap
+/// files.
+pub fn stage(journals: Vec<Journal>) -> Result<()> {
+    let mut staged = lock_journals()?;
+    staged.retain(|path_file, _| {
+        let ok = journals.iter().any(|j| &j.path_file == path_file);
+        if !ok {
+            remove_swap_file(path_file);
+        }
+        ok
+    });
+    for journal in journals.into_iter() {
+        staged.insert(journal.path_file.clone(), (journal, true));
+    }
+    Ok(())
+}
+
+/// Write staged journals, that changed since the last flush, to their
+/// swap files.
+pub fn flush() -> Result<()> {
+    let mut staged = lock_journals()?;
+    for (path_file, (journal, dirty)) in staged.iter_mut() {
+        if *dirty {
+            match to_swap_file(path_file) {
+                Some(swap_file) => journal.save_to(&swap_file)?,
+                None => err_at!(IOError, msg: format!("can't find home-directory"))?,
+            }
+            *dirty = false;
+        }
+    }
+    Ok(())
+}
+
+/// Drop all staged journals along with their swap files, typically when
+/// the application quits.
+pub fn clear() -> Result<()> {
+    let mut staged = lock_journals()?;
+    for path_file in staged.keys() {
+        remove_swap_file(path_file);
+    }
+    staged.clear();
+    Ok(())
+}
+
+/// Delete swap file for `path_file`, like when the user declines to
+/// recover from it.
+pub fn delete(path_file: &ffi::OsStr) -> Result<()> {
+    lock_journals()?.remove(path_file);
+    match to_swap_file(path_file) {
+        Some(swap_file) if swap_file.exists() => err_at!(IOError, fs::remove_file(swap_file)),
+        _ => Ok(()),
+    }
+}
+
+// journals are also flushed from the panic hook, where the lock might be
+// poisoned or held by the panicking thread.
+fn lock_journals() -> Result<MutexGuard<'static, HashMap<ffi::OsString, (Journal, bool)>>> {
+    match JOURNALS.try_lock() {
+        Ok(staged) => Ok(staged),
+        Err(TryLockError::Poisoned(err)) => Ok(err.into_inner()),
+        Err(TryLockError::WouldBlock) => err_at!(Fatal, msg: format!("journals locked")),
+    }
+}
+
+fn remove_swap_file(path_file: &ffi::OsStr) {
+    if let Some(swap_file) = to_swap_file(path_file) {
+        if let Err(err) = fs::remove_file(&swap_file) {
+            debug!("remove swap file {:?}: {}", swap_file, err);
+        }
+    }
+}
+
+fn to_swap_name(path_file: &ffi::OsStr) -> String {
+    let name = path_file.to_string_lossy();
+    format!("{}.swp", name.replace(path::MAIN_SEPARATOR, "%"))
+}
+
+#[cfg(test)]
+#[path = "swap_test.rs"]
+mod swap_test;
@@ -0,0 +1,36 @@
+use super::*;
+
+#[test]
+fn test_swap_name() {
+    let path_file: ffi::OsString = "/home/user/src/main.rs".into();
+    assert_eq!(to_swap_name(&path_file), "%home%user%src%main.rs.swp");
+}
+
+#[test]
+fn test_journal() {
+    let swap_file = std::env::temp_dir().join("ted-test-journal.swp");
+    let path_file: ffi::OsString = "/tmp/hello.txt".into();
+
+    let journal = Journal {
+        path_file: path_file.clone(),
+        pid: 1234,
+        enc: "latin1".to_string(),
+        format: text::Format::Dos,
+        cursor: 7,
+        rope: Rope::from_str("hello world\n\nhow are you"),
+    };
+    journal.save_to(&swap_file).unwrap();
+
+    let loaded = Journal::load_from(&path_file, &swap_file).unwrap();
+    assert_eq!(loaded.path_file, path_file);
+    assert_eq!(loaded.pid, 1234);
+    assert_eq!(loaded.enc, "latin1");
+    assert_eq!(loaded.format, text::Format::Dos);
+    assert_eq!(loaded.cursor, 7);
+    assert_eq!(loaded.rope.to_string(), "hello world\n\nhow are you");
+
+    fs::write(&swap_file, "hello world").unwrap();
+    assert!(Journal::load_from(&path_file, &swap_file).is_err());
+
+    fs::remove_file(&swap_file).ok();
+}
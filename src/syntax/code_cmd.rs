@@ -17,6 +17,7 @@ extern "C" {
 
 pub struct CodeCmd {
     parser: ts::Parser,
+    content: String,
     tree: Option<ts::Tree>,
 }
 
@@ -30,6 +31,7 @@ impl Clone for CodeCmd {
         };
         CodeCmd {
             parser,
+            content: self.content.clone(),
             tree: self.tree.clone(),
         }
     }
@@ -53,7 +55,11 @@ impl CodeCmd {
                 None
             }
         };
-        Ok(CodeCmd { parser, tree })
+        Ok(CodeCmd {
+            parser,
+            content: s.to_string(),
+            tree,
+        })
     }
 
     pub fn to_command_name(&self) -> Option<String> {
@@ -67,8 +73,36 @@ impl CodeCmd {
         }
     }
 
-    pub fn into_parse_tree(self) -> Option<ts::Tree> {
-        self.tree
+    /// Return the range prefixed to command, like `1,10` in `:1,10w`.
+    pub fn to_range(&self) -> Option<String> {
+        let root = self.tree.as_ref()?.root_node();
+        for i in 0..root.child_count() {
+            let node = root.child(i)?;
+            if node.kind() == "range" {
+                let text = self.content.get(node.start_byte()..node.end_byte())?;
+                return Some(text.trim().to_string());
+            }
+        }
+        None
+    }
+
+    /// Return the arguments to command as (kind, text) pairs, command
+    /// keyword is skipped. Text is trimmed of trailing white-space.
+    pub fn to_command_args(&self) -> Option<Vec<(String, String)>> {
+        let root = self.tree.as_ref()?.root_node();
+        let node = root.child(root.child_count().saturating_sub(1))?;
+        let node = match node.kind() {
+            "cmd" => node.child(0)?,
+            _ => return None,
+        };
+
+        let mut args = vec![];
+        for i in 1..node.child_count() {
+            let arg = node.child(i)?;
+            let text = self.content.get(arg.start_byte()..arg.end_byte())?;
+            args.push((arg.kind().to_string(), text.trim_end().to_string()));
+        }
+        Some(args)
     }
 }
 
@@ -85,11 +119,12 @@ impl Syntax for CodeCmd {
                 Event::Edit(edit) => match self.tree.take() {
                     Some(mut old_tree) => {
                         old_tree.edit(&edit.into_ts_input(buf)?);
-                        let s = buf.to_string();
-                        self.tree = self.parser.parse(&s, Some(&old_tree));
+                        self.content = buf.to_string();
+                        self.tree = self.parser.parse(&self.content, Some(&old_tree));
                     }
                     None => {
-                        self.tree = self.parser.parse(&buf.to_string(), None);
+                        self.content = buf.to_string();
+                        self.tree = self.parser.parse(&self.content, None);
                     }
                 },
                 evnt => new_evnt.push(evnt),
@@ -14,6 +14,7 @@ use std::{
 use crate::{
     buffer::Buffer,
     colors::{ColorScheme, Highlight},
+    compress::Compress,
     event::Event,
     location::Location,
     term,
@@ -123,25 +124,72 @@ syntax_for![
     (PlainText, PlainText, "txt-plain")
 ];
 
-pub fn detect(buf: &Buffer, scheme: &ColorScheme) -> Result<Syn> {
-    let loc = buf.to_location();
+/// Detect syntax for buffer from its file-type. Buffers larger than
+/// `large_file` bytes are treated as plain text and are not parsed.
+pub fn detect(buf: &Buffer, scheme: &ColorScheme, large_file: usize) -> Result<Syn> {
+    let n = buf.to_rope().len_bytes();
+    if n > large_file {
+        debug!("large file {} bytes, skip parsing {}", n, buf.to_id());
+        return Ok(Syn::PlainText(PlainText::new_large(scheme.clone())?));
+    }
+
+    let tt = match to_file_type(buf).as_str() {
+        "toml" => "toml".to_string(),
+        "tss" => "tss".to_string(),
+        _ => "".to_string(),
+    };
+
+    // TODO: find other ways to detect the file's type.
+
+    (tt.as_str(), buf.to_string().as_str(), scheme.clone()).try_into()
+}
+
+/// Parse the buffer's content using tree-sitter `parser`. Text is fed
+/// to the parser chunk by chunk, from the buffer's rope, instead of
+/// copying the entire text.
+pub fn parse(
+    parser: &mut ts::Parser,
+    buf: &Buffer,
+    old_tree: Option<&ts::Tree>,
+) -> Option<ts::Tree> {
+    let rope = buf.to_rope();
+    let n = rope.len_bytes();
+    parser.parse_with(
+        &mut |byte_idx, _| match byte_idx {
+            byte_idx if byte_idx < n => {
+                let (chunk, start, _, _) = rope.chunk_at_byte(byte_idx);
+                &chunk.as_bytes()[(byte_idx - start)..]
+            }
+            _ => &[],
+        },
+        old_tree,
+    )
+}
 
-    let tt = match &loc {
+/// Return the file-type for buffer, detected from its file's extension.
+/// Return empty string if file-type cannot be detected.
+pub fn to_file_type(buf: &Buffer) -> String {
+    match buf.to_location() {
         Location::Disk { path_file, .. } => {
-            let ext = path::Path::new(path_file).extension();
+            // compressed files, like `config.toml.gz`, take the file-type
+            // of the uncompressed file.
+            let file = path::Path::new(&path_file);
+            let file = match Compress::from_extension(file) {
+                Some(_) => path::Path::new(file.file_stem().unwrap_or_default()),
+                None => file,
+            };
+            let ext = file.extension();
             match ext.map(|ext| ext.to_str().unwrap_or("")) {
-                Some("toml") => "toml".to_string(),
-                Some("tss") => "tss".to_string(),
-                Some(_) | None => "".to_string(),
+                Some("rs") => "rust".to_string(),
+                Some("md") | Some("markdown") => "markdown".to_string(),
+                Some("txt") => "text".to_string(),
+                Some(ext) => ext.to_string(),
+                None => "".to_string(),
             }
         }
         Location::Ted { .. } => "".to_string(),
         Location::Memory { .. } => "".to_string(),
-    };
-
-    // TODO: find other ways to detect the file's type.
-
-    (tt.as_str(), buf.to_string().as_str(), scheme.clone()).try_into()
+    }
 }
 
 /// Syntax highlighting using tree-sitter and ted-style-sheet automata.
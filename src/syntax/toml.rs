@@ -85,11 +85,10 @@ impl Syntax for Toml {
                 Event::Edit(edit) => match self.tree.take() {
                     Some(mut old_tree) => {
                         old_tree.edit(&edit.into_ts_input(buf)?);
-                        let s = buf.to_string();
-                        self.tree = self.parser.parse(&s, Some(&old_tree));
+                        self.tree = syntax::parse(&mut self.parser, buf, Some(&old_tree));
                     }
                     None => {
-                        self.tree = self.parser.parse(&buf.to_string(), None);
+                        self.tree = syntax::parse(&mut self.parser, buf, None);
                     }
                 },
                 evnt => new_evnt.push(evnt),
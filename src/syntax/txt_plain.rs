@@ -6,7 +6,7 @@ use crate::{
     buffer::Buffer,
     colors::{ColorScheme, Highlight},
     event::Event,
-    syntax::Syntax,
+    syntax::{self, Syntax},
     term::{Span, Spanline},
     Error, Result,
 };
@@ -19,6 +19,8 @@ pub struct PlainText {
     parser: ts::Parser,
     tree: Option<ts::Tree>,
     scheme: ColorScheme,
+    // content is not parsed for large files.
+    large: bool,
 }
 
 impl Clone for PlainText {
@@ -33,6 +35,7 @@ impl Clone for PlainText {
             parser,
             tree: self.tree.clone(),
             scheme: self.scheme.clone(),
+            large: self.large,
         }
     }
 }
@@ -59,6 +62,21 @@ impl PlainText {
             parser,
             tree,
             scheme: scheme.clone(),
+            large: false,
+        })
+    }
+
+    /// Plain text for large files, content is neither parsed now nor
+    /// re-parsed on subsequent edits.
+    pub fn new_large(scheme: ColorScheme) -> Result<PlainText> {
+        let lang = unsafe { tree_sitter_txt_plain() };
+        let mut parser = ts::Parser::new();
+        err_at!(FailParse, parser.set_language(lang))?;
+        Ok(PlainText {
+            parser,
+            tree: None,
+            scheme,
+            large: true,
         })
     }
 }
@@ -73,14 +91,14 @@ impl Syntax for PlainText {
         let mut new_evnt: Event = Event::default();
         for evnt in evnt.into_iter() {
             match evnt {
+                Event::Edit(_) if self.large => (),
                 Event::Edit(edit) => match self.tree.take() {
                     Some(mut old_tree) => {
                         old_tree.edit(&edit.into_ts_input(buf)?);
-                        let s = buf.to_string();
-                        self.tree = self.parser.parse(&s, Some(&old_tree));
+                        self.tree = syntax::parse(&mut self.parser, buf, Some(&old_tree));
                     }
                     None => {
-                        self.tree = self.parser.parse(&buf.to_string(), None);
+                        self.tree = syntax::parse(&mut self.parser, buf, None);
                     }
                 },
                 evnt => new_evnt.push(evnt),
@@ -95,6 +113,10 @@ impl Syntax for PlainText {
     }
 
     fn to_status_cursor(&self) -> Result<Span> {
+        if self.large {
+            return Ok(format!("large file").into());
+        }
+
         let nodes: Vec<ts::Node> = match self.tree.as_ref() {
             Some(tree) => {
                 let mut tc = tree.walk();
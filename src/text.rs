@@ -2,7 +2,7 @@
 
 use unicode_width::UnicodeWidthChar;
 
-use std::{convert::TryFrom, io};
+use std::{convert::TryFrom, fmt, io, result, str::FromStr};
 
 use crate::{Error, Result};
 
@@ -10,50 +10,197 @@ use crate::{Error, Result};
 /// to disk/network or vice-versa.
 pub enum Encoding {
     Utf8(String),
+    /// UTF-8 prefixed with byte-order-mark.
+    Utf8Bom(String),
+    Utf16le(String),
+    Utf16be(String),
+    Utf32le(String),
+    Utf32be(String),
+    Latin1(String),
+    Cp1252(String),
+    ShiftJis(String),
 }
 
+/// Canonical names of supported encodings, UTF-16 and UTF-32 are always
+/// saved with byte-order-mark.
+pub const ENCODINGS: [&'static str; 9] = [
+    "utf-8",
+    "utf-8-bom",
+    "utf-16le",
+    "utf-16be",
+    "utf-32le",
+    "utf-32be",
+    "latin1",
+    "cp1252",
+    "shift-jis",
+];
+
 impl TryFrom<(String, String)> for Encoding {
     type Error = Error;
 
     fn try_from((s, enc): (String, String)) -> Result<Encoding> {
-        match enc.as_str() {
-            "utf-8" => Ok(Encoding::Utf8(s)),
-            "utf8" => Ok(Encoding::Utf8(s)),
-            enc => err_at!(Invalid, msg: format!("encoding `{}`", enc)),
-        }
+        let enc = match Encoding::to_canonical(&enc)? {
+            "utf-8" => Encoding::Utf8(s),
+            "utf-8-bom" => Encoding::Utf8Bom(s),
+            "utf-16le" => Encoding::Utf16le(s),
+            "utf-16be" => Encoding::Utf16be(s),
+            "utf-32le" => Encoding::Utf32le(s),
+            "utf-32be" => Encoding::Utf32be(s),
+            "latin1" => Encoding::Latin1(s),
+            "cp1252" => Encoding::Cp1252(s),
+            "shift-jis" => Encoding::ShiftJis(s),
+            enc => err_at!(Fatal, msg: format!("unreachable encoding `{}`", enc))?,
+        };
+        Ok(enc)
     }
 }
 
 impl From<Encoding> for String {
     fn from(enc: Encoding) -> String {
         match enc {
-            Encoding::Utf8(s) => s,
+            Encoding::Utf8(s) | Encoding::Utf8Bom(s) => s,
+            Encoding::Utf16le(s) | Encoding::Utf16be(s) => s,
+            Encoding::Utf32le(s) | Encoding::Utf32be(s) => s,
+            Encoding::Latin1(s) | Encoding::Cp1252(s) | Encoding::ShiftJis(s) => s,
         }
     }
 }
 
 impl Encoding {
+    /// Return the canonical name for encoding `enc`, refer [ENCODINGS].
+    /// Commonly used aliases are accepted.
+    pub fn to_canonical(enc: &str) -> Result<&'static str> {
+        let name = match enc.to_lowercase().as_str() {
+            "utf-8" | "utf8" => "utf-8",
+            "utf-8-bom" | "utf8-bom" => "utf-8-bom",
+            "utf-16le" | "utf16le" => "utf-16le",
+            "utf-16be" | "utf16be" => "utf-16be",
+            "utf-32le" | "utf32le" => "utf-32le",
+            "utf-32be" | "utf32be" => "utf-32be",
+            "latin1" | "latin-1" | "iso-8859-1" => "latin1",
+            "cp1252" | "windows-1252" => "cp1252",
+            "shift-jis" | "shift_jis" | "sjis" => "shift-jis",
+            _ => err_at!(Invalid, msg: format!("encoding `{}`", enc))?,
+        };
+        Ok(name)
+    }
+
+    /// Detect the encoding of `bytes`. Byte-order-mark, if present,
+    /// decides the encoding. Otherwise content with NUL bytes in
+    /// alternate positions is treated as UTF-16, else UTF-8 is tried,
+    /// falling back to `cp1252`.
+    pub fn detect(bytes: &[u8]) -> &'static str {
+        match bytes {
+            [0xEF, 0xBB, 0xBF, ..] => "utf-8-bom",
+            [0xFF, 0xFE, 0, 0, ..] => "utf-32le",
+            [0, 0, 0xFE, 0xFF, ..] => "utf-32be",
+            [0xFF, 0xFE, ..] => "utf-16le",
+            [0xFE, 0xFF, ..] => "utf-16be",
+            bytes => match detect_utf16(bytes) {
+                Some(enc) => enc,
+                None if std::str::from_utf8(bytes).is_ok() => "utf-8",
+                None => "cp1252",
+            },
+        }
+    }
+
+    /// Same as [Encoding::detect], but `bytes` is only a prefix sampled
+    /// from a larger content. A multi-byte sequence cut short at the
+    /// end of `bytes` is not treated as invalid utf-8.
+    pub fn detect_prefix(bytes: &[u8]) -> &'static str {
+        match Self::detect(bytes) {
+            "cp1252" => match std::str::from_utf8(bytes) {
+                Err(err) if err.error_len().is_none() => "utf-8",
+                _ => "cp1252",
+            },
+            enc => enc,
+        }
+    }
+
     /// Read bytes from `r`, using file-encoding `fenc`. If successful,
     /// resulting `Encoding` value can be converted to String.
     pub fn from_reader<R>(mut r: R, fenc: &str) -> Result<Encoding>
     where
         R: io::Read,
     {
-        use std::str::from_utf8;
-
         let mut buf = vec![];
         err_at!(IOError, r.read_to_end(&mut buf))?;
+        Self::decode(&buf, fenc)
+    }
 
-        match fenc {
-            "utf8" | "utf-8" => {
-                let buf = err_at!(FailConvert, from_utf8(&buf))?;
-                Ok(Encoding::Utf8(buf.to_string()))
+    /// Decode `bytes` using file-encoding `fenc`. Byte-order-mark is not
+    /// part of the decoded text.
+    pub fn decode(bytes: &[u8], fenc: &str) -> Result<Encoding> {
+        use std::str::from_utf8;
+
+        let enc = match Self::to_canonical(fenc)? {
+            "utf-8" => Encoding::Utf8(err_at!(FailConvert, from_utf8(bytes))?.to_string()),
+            "utf-8-bom" => {
+                let s = err_at!(FailConvert, from_utf8(bytes))?.to_string();
+                Encoding::Utf8Bom(strip_bom(s))
             }
-            _ => {
-                let s = format!("encoding {}", fenc);
-                err_at!(Err(Error::Invalid(String::new(), s)))
+            "utf-16le" => Encoding::Utf16le(decode_utf16(bytes, u16::from_le_bytes)?),
+            "utf-16be" => Encoding::Utf16be(decode_utf16(bytes, u16::from_be_bytes)?),
+            "utf-32le" => Encoding::Utf32le(decode_utf32(bytes, u32::from_le_bytes)?),
+            "utf-32be" => Encoding::Utf32be(decode_utf32(bytes, u32::from_be_bytes)?),
+            "latin1" => Encoding::Latin1(bytes.iter().map(|b| *b as char).collect()),
+            "cp1252" => Encoding::Cp1252(decode_legacy(bytes, encoding_rs::WINDOWS_1252)?),
+            "shift-jis" => Encoding::ShiftJis(decode_legacy(bytes, encoding_rs::SHIFT_JIS)?),
+            enc => err_at!(Fatal, msg: format!("unreachable encoding `{}`", enc))?,
+        };
+        Ok(enc)
+    }
+
+    /// Encode text into bytes, prefixed with byte-order-mark if `bom` is
+    /// true and encoding carries one.
+    pub fn to_bytes(&self, bom: bool) -> Result<Vec<u8>> {
+        let mut out: Vec<u8> = vec![];
+        match self {
+            Encoding::Utf8(s) => out.extend_from_slice(s.as_bytes()),
+            Encoding::Utf8Bom(s) => {
+                if bom {
+                    out.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+                }
+                out.extend_from_slice(s.as_bytes());
+            }
+            Encoding::Utf16le(s) => {
+                let iter = bom_iter(bom).chain(s.chars());
+                let mut units = [0_u16; 2];
+                for ch in iter {
+                    for unit in ch.encode_utf16(&mut units).iter() {
+                        out.extend_from_slice(&unit.to_le_bytes());
+                    }
+                }
+            }
+            Encoding::Utf16be(s) => {
+                let iter = bom_iter(bom).chain(s.chars());
+                let mut units = [0_u16; 2];
+                for ch in iter {
+                    for unit in ch.encode_utf16(&mut units).iter() {
+                        out.extend_from_slice(&unit.to_be_bytes());
+                    }
+                }
+            }
+            Encoding::Utf32le(s) => {
+                let iter = bom_iter(bom).chain(s.chars());
+                iter.for_each(|ch| out.extend_from_slice(&(ch as u32).to_le_bytes()));
+            }
+            Encoding::Utf32be(s) => {
+                let iter = bom_iter(bom).chain(s.chars());
+                iter.for_each(|ch| out.extend_from_slice(&(ch as u32).to_be_bytes()));
+            }
+            Encoding::Latin1(s) => {
+                for ch in s.chars() {
+                    match ch as u32 {
+                        n if n <= 0xFF => out.push(n as u8),
+                        _ => err_at!(FailConvert, msg: format!("{:?} not in latin1", ch))?,
+                    }
+                }
             }
+            Encoding::Cp1252(s) => out = encode_legacy(s, encoding_rs::WINDOWS_1252)?,
+            Encoding::ShiftJis(s) => out = encode_legacy(s, encoding_rs::SHIFT_JIS)?,
         }
+        Ok(out)
     }
 
     /// Serialize string into specified encoding and save them to `w`.
@@ -61,14 +208,88 @@ impl Encoding {
     where
         W: io::Write,
     {
-        match self {
-            Encoding::Utf8(s) => err_at!(IOError, w.write_all(s.as_bytes())),
+        err_at!(IOError, w.write_all(&self.to_bytes(true)?))
+    }
+}
+
+// content without byte-order-mark is detected as UTF-16, if at least
+// half of the code units, within the first few kilobytes, have their
+// upper byte as ZERO.
+fn detect_utf16(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.len() < 2 || bytes.len() % 2 != 0 {
+        return None;
+    }
+    let bytes = &bytes[..std::cmp::min(bytes.len(), 4096)];
+    let n = bytes.len() / 2;
+    let (mut evens, mut odds) = (0, 0);
+    for unit in bytes.chunks(2) {
+        evens += if_else!(unit[0] == 0, 1, 0);
+        odds += if_else!(unit[1] == 0, 1, 0);
+    }
+    match (evens, odds) {
+        (0, odds) if odds * 2 >= n => Some("utf-16le"),
+        (evens, 0) if evens * 2 >= n => Some("utf-16be"),
+        _ => None,
+    }
+}
+
+fn strip_bom(s: String) -> String {
+    match s.chars().next() {
+        Some('\u{feff}') => s['\u{feff}'.len_utf8()..].to_string(),
+        _ => s,
+    }
+}
+
+fn bom_iter(bom: bool) -> std::option::IntoIter<char> {
+    if_else!(bom, Some('\u{feff}'), None).into_iter()
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String> {
+    if bytes.len() % 2 != 0 {
+        err_at!(FailConvert, msg: format!("utf-16 odd number of bytes"))?
+    }
+    let units = bytes.chunks(2).map(|c| from_bytes([c[0], c[1]]));
+    let mut s = String::with_capacity(bytes.len() / 2);
+    for ch in std::char::decode_utf16(units) {
+        s.push(err_at!(FailConvert, ch)?);
+    }
+    Ok(strip_bom(s))
+}
+
+fn decode_utf32(bytes: &[u8], from_bytes: fn([u8; 4]) -> u32) -> Result<String> {
+    if bytes.len() % 4 != 0 {
+        err_at!(FailConvert, msg: format!("utf-32 incomplete code point"))?
+    }
+    let mut s = String::with_capacity(bytes.len() / 4);
+    for c in bytes.chunks(4) {
+        let n = from_bytes([c[0], c[1], c[2], c[3]]);
+        match std::char::from_u32(n) {
+            Some(ch) => s.push(ch),
+            None => err_at!(FailConvert, msg: format!("utf-32 invalid {:x}", n))?,
         }
     }
+    Ok(strip_bom(s))
 }
 
+fn decode_legacy(bytes: &[u8], enc: &'static encoding_rs::Encoding) -> Result<String> {
+    match enc.decode_without_bom_handling_and_without_replacement(bytes) {
+        Some(s) => Ok(s.to_string()),
+        None => err_at!(FailConvert, msg: format!("invalid {} text", enc.name())),
+    }
+}
+
+fn encode_legacy(s: &str, enc: &'static encoding_rs::Encoding) -> Result<Vec<u8>> {
+    match enc.encode(s) {
+        (_, _, true) => err_at!(FailConvert, msg: format!("can't encode text as {}", enc.name())),
+        (bytes, _, false) => Ok(bytes.to_vec()),
+    }
+}
+
+/// Names of supported text formats, refer [Format].
+pub const FORMATS: [&'static str; 3] = ["unix", "dos", "mac"];
+
 /// Text format. Mostly to deal with new-line.
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Format {
     Dos,
     Mac,
@@ -81,7 +302,70 @@ impl Default for Format {
     }
 }
 
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Format> {
+        match s {
+            "dos" => Ok(Format::Dos),
+            "mac" => Ok(Format::Mac),
+            "unix" => Ok(Format::Unix),
+            s => err_at!(Invalid, msg: format!("fileformat `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        match self {
+            Format::Dos => write!(f, "dos"),
+            Format::Mac => write!(f, "mac"),
+            Format::Unix => write!(f, "unix"),
+        }
+    }
+}
+
 impl Format {
+    /// Detect the dominant new-line format in `text`, ties are resolved
+    /// in favor of `Unix` and then `Dos`. Also return whether `text`
+    /// has mixed line endings.
+    pub fn detect(text: &str) -> (Format, bool) {
+        Self::detect_chunks(std::iter::once(text))
+    }
+
+    /// Same as [Format::detect], for text split into `chunks`, like a
+    /// rope's chunks. `"\r\n"` shall not be split across chunks.
+    pub fn detect_chunks<'a, I>(chunks: I) -> (Format, bool)
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let (mut dos, mut mac, mut unix) = (0, 0, 0);
+        for chunk in chunks {
+            let mut iter = chunk.bytes().peekable();
+            while let Some(byte) = iter.next() {
+                match byte {
+                    b'\r' if iter.peek() == Some(&b'\n') => {
+                        iter.next();
+                        dos += 1;
+                    }
+                    b'\r' => mac += 1,
+                    b'\n' => unix += 1,
+                    _ => (),
+                }
+            }
+        }
+
+        let format = if unix >= dos && unix >= mac {
+            Format::Unix
+        } else if dos >= mac {
+            Format::Dos
+        } else {
+            Format::Mac
+        };
+        let mixed = [dos, mac, unix].iter().filter(|n| **n > 0).count() > 1;
+        (format, mixed)
+    }
+
     /// Return the new-line string for this text-format variant.
     pub fn newline(&self) -> &'static str {
         match self {
@@ -155,3 +439,7 @@ where
         }
     }
 }
+
+#[cfg(test)]
+#[path = "text_test.rs"]
+mod text_test;
@@ -0,0 +1,88 @@
+use super::*;
+
+#[test]
+fn test_encoding_detect() {
+    assert_eq!(Encoding::detect(b"hello world"), "utf-8");
+    assert_eq!(Encoding::detect("naïve".as_bytes()), "utf-8");
+    assert_eq!(Encoding::detect(b""), "utf-8");
+    assert_eq!(Encoding::detect(b"\xEF\xBB\xBFhello"), "utf-8-bom");
+    assert_eq!(Encoding::detect(b"\xFF\xFEh\x00"), "utf-16le");
+    assert_eq!(Encoding::detect(b"\xFE\xFF\x00h"), "utf-16be");
+    assert_eq!(
+        Encoding::detect(b"\xFF\xFE\x00\x00h\x00\x00\x00"),
+        "utf-32le"
+    );
+    assert_eq!(
+        Encoding::detect(b"\x00\x00\xFE\xFF\x00\x00\x00h"),
+        "utf-32be"
+    );
+    assert_eq!(Encoding::detect(b"h\x00e\x00l\x00l\x00o\x00"), "utf-16le");
+    assert_eq!(Encoding::detect(b"\x00h\x00e\x00l\x00l\x00o"), "utf-16be");
+    assert_eq!(Encoding::detect(b"caf\xE9"), "cp1252");
+}
+
+#[test]
+fn test_encoding_round_trip() {
+    let text = "hello wörld\r\nπ = 3.14 😀\n";
+    for enc in [
+        "utf-8",
+        "utf-8-bom",
+        "utf-16le",
+        "utf-16be",
+        "utf-32le",
+        "utf-32be",
+    ]
+    .iter()
+    {
+        let val = Encoding::try_from((text.to_string(), enc.to_string())).unwrap();
+        let bytes = val.to_bytes(true).unwrap();
+        assert_eq!(Encoding::detect(&bytes), *enc, "{}", enc);
+        let s: String = Encoding::decode(&bytes, enc).unwrap().into();
+        assert_eq!(s, text, "{}", enc);
+    }
+
+    let val = Encoding::try_from((text.to_string(), "UTF16LE".to_string())).unwrap();
+    assert_eq!(val.to_bytes(false).unwrap()[..4], [b'h', 0, b'e', 0]);
+
+    let text = "café";
+    let val = Encoding::try_from((text.to_string(), "iso-8859-1".to_string())).unwrap();
+    let bytes = val.to_bytes(true).unwrap();
+    assert_eq!(bytes, b"caf\xE9");
+    let s: String = Encoding::decode(&bytes, "latin1").unwrap().into();
+    assert_eq!(s, text);
+
+    let val = Encoding::try_from(("π".to_string(), "latin1".to_string())).unwrap();
+    assert!(val.to_bytes(true).is_err());
+    assert!(Encoding::decode(b"\xFF", "utf-8").is_err());
+    assert!(Encoding::decode(b"h\x00e", "utf-16le").is_err());
+    assert!(Encoding::to_canonical("ebcdic").is_err());
+}
+
+#[test]
+fn test_format_detect() {
+    assert_eq!(Format::detect(""), (Format::Unix, false));
+    assert_eq!(Format::detect("hello\nworld\n"), (Format::Unix, false));
+    assert_eq!(Format::detect("hello\r\nworld\r\n"), (Format::Dos, false));
+    assert_eq!(Format::detect("hello\rworld\r"), (Format::Mac, false));
+    assert_eq!(Format::detect("a\r\nb\r\nc\n"), (Format::Dos, true));
+    assert_eq!(Format::detect("a\r\nb\rc\n"), (Format::Unix, true));
+    assert_eq!(Format::detect("a\r\nb\rc\r"), (Format::Mac, true));
+
+    assert_eq!("dos".parse::<Format>().unwrap(), Format::Dos);
+    assert_eq!(Format::Mac.to_string(), "mac");
+    assert!("windows".parse::<Format>().is_err());
+}
+
+#[test]
+fn test_detect_prefix() {
+    let bytes = "hello wörld".as_bytes();
+    assert_eq!(Encoding::detect(&bytes[..8]), "cp1252");
+    assert_eq!(Encoding::detect_prefix(&bytes[..8]), "utf-8");
+    assert_eq!(Encoding::detect_prefix(b"hello \xffworld"), "cp1252");
+
+    let chunks = vec!["a\r\n", "b\r\n", "c\n"];
+    assert_eq!(
+        Format::detect_chunks(chunks.into_iter()),
+        (Format::Dos, true)
+    );
+}
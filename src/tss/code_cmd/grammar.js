@@ -8,23 +8,116 @@ module.exports = grammar({
 
     newline: $ => /\r?\n/,
 
-    cmd: $ => choice($.set, $.buffer, $.buffers, $.edit),
+    cmd: $ => choice(
+        $.set,
+        $.setlocal,
+        $.setglobal,
+        $.edit,
+        $.buffer,
+        $.bnext,
+        $.bprevious,
+        $.bdelete,
+        $.buffers,
+        $.finder,
+        $.write,
+        $.update,
+        $.saveas,
+        $.wall,
+        $.read,
+        $.substitute,
+        $.quit,
+        $.wq,
+        $.xit,
+        $.qall,
+        $.wqall,
+        $.mksession,
+        $.mkdir,
+        $.mkfile,
+        $.rename,
+        $.remove,
+        $.nmap,
+        $.imap,
+        $.cmap,
+        $.noremap,
+        $.inoremap,
+        $.cnoremap,
+        $.unmap,
+        $.iunmap,
+        $.cunmap,
+    ),
+
+    range: $ => seq($.range_start, optional(seq(choice(',', ';'), $.range_end))),
+    range_start: $ => /([0-9.%$]+|\/([^\/\\\n]|\\.)*\/|\?([^?\\\n]|\\.)*\?|'[a-z])[+-]?[0-9]*/,
+    range_end: $ => /([0-9.$]+|\/([^\/\\\n]|\\.)*\/|\?([^?\\\n]|\\.)*\?|'[a-z])[+-]?[0-9]*/,
+
+    set: $ => seq(choice('set', 'se'), repeat($.set_arg)),
+    setlocal: $ => seq(choice('setlocal', 'setl'), repeat($.set_arg)),
+    setglobal: $ => seq(choice('setglobal', 'setg'), repeat($.set_arg)),
+    set_arg: $ => /([^ \t\r\n\\]|\\.)+/,
+
+    bang: $ => '!',
+    count: $ => /[0-9]+/,
+    append: $ => '>>',
+    file_name: $ => /[^!> \t\r\n][^\r\n]*/,
+    buffer_name: $ => /[^! \t\r\n][^\r\n]*/,
+    shell_cmd: $ => /[^\r\n]+/,
+
+    edit: $ => seq(choice('edit', 'e'), optional($.bang), optional($.file_name)),
+
+    buffer: $ => seq(choice('buffer', 'b'), optional($.buffer_name)),
+    bnext: $ => seq(choice('bnext', 'bn'), optional($.count)),
+    bprevious: $ => seq(choice('bprevious', 'bp', 'bNext', 'bN'), optional($.count)),
+    bdelete: $ => seq(
+        choice('bdelete', 'bd'), optional($.bang), optional($.buffer_name)
+    ),
+
+    buffers: $ => choice('buffers', 'ls', 'files'),
+
+    finder: $ => 'finder',
+
+    write: $ => seq(
+        choice('write', 'w'), optional($.bang), optional($.append), optional($.file_name)
+    ),
+    update: $ => seq(
+        choice('update', 'up'), optional($.bang), optional($.append), optional($.file_name)
+    ),
+    saveas: $ => seq(choice('saveas', 'sav'), optional($.bang), $.file_name),
+    wall: $ => seq(choice('wall', 'wa'), optional($.bang)),
+
+    read: $ => seq(
+        choice('read', 'r'), optional(choice($.file_name, seq($.bang, $.shell_cmd)))
+    ),
 
-    range: $ => seq($.range_start, optional(seq(',', $.range_end))),
-    range_start: $ => /([0-9.%]+|(\?[^?]+\?)|'[a-z])[+-]?[0-9]*/,
-    range_end: $ => /([0-9.$]+|(\/.*\/)|'[a-z])[+-]?[0-9]*/,
+    substitute: $ => seq(choice('substitute', 's'), $.subst_args),
+    subst_args: $ => /[^A-Za-z0-9 \t\r\n\\"|][^\r\n]*/,
 
-    set: $ => seq('set', $.config_param),
-    config_param: $ => choice(
-        'wrap',
-        'nowrap',
+    quit: $ => seq(choice('quit', 'q'), optional($.bang)),
+    wq: $ => seq('wq', optional($.bang), optional($.file_name)),
+    xit: $ => seq(
+        choice('xit', 'x', 'exit', 'exi'), optional($.bang), optional($.file_name)
     ),
+    qall: $ => seq(choice('qall', 'qa', 'quitall', 'quita'), optional($.bang)),
+    wqall: $ => seq(choice('wqall', 'wqa', 'xall', 'xa'), optional($.bang)),
 
-    edit: $ => seq('edit', /.*/),
+    mksession: $ => seq(choice('mksession', 'mks'), optional($.bang), optional($.file_name)),
 
-    buffer: $ => seq('buffer', /[0-9]+/),
+    mkdir: $ => seq('mkdir', $.file_name),
+    mkfile: $ => seq('mkfile', $.file_name),
+    rename: $ => seq(choice('rename', 'ren'), $.file_name),
+    remove: $ => choice('remove', 'rm'),
 
-    buffers: $ => seq('buffers'),
+    nmap: $ => seq(choice('nmap', 'nm', 'map'), optional($._map_args)),
+    imap: $ => seq(choice('imap', 'im'), optional($._map_args)),
+    cmap: $ => seq(choice('cmap', 'cm'), optional($._map_args)),
+    noremap: $ => seq(choice('noremap', 'no', 'nnoremap', 'nn'), optional($._map_args)),
+    inoremap: $ => seq(choice('inoremap', 'ino'), optional($._map_args)),
+    cnoremap: $ => seq(choice('cnoremap', 'cno'), optional($._map_args)),
+    unmap: $ => seq(choice('unmap', 'unm', 'nunmap', 'nun'), $.map_lhs),
+    iunmap: $ => seq(choice('iunmap', 'iu'), $.map_lhs),
+    cunmap: $ => seq(choice('cunmap', 'cu'), $.map_lhs),
+    _map_args: $ => seq($.map_lhs, optional($.map_rhs)),
+    map_lhs: $ => /[^ \t\r\n]+/,
+    map_rhs: $ => /[^ \t\r\n][^\r\n]*/,
   }
 });
 
@@ -6,37 +6,151 @@
 #endif
 
 #define LANGUAGE_VERSION 11
-#define STATE_COUNT 19
-#define LARGE_STATE_COUNT 3
-#define SYMBOL_COUNT 22
+#define STATE_COUNT 104
+#define LARGE_STATE_COUNT 6
+#define SYMBOL_COUNT 136
 #define ALIAS_COUNT 0
-#define TOKEN_COUNT 14
+#define TOKEN_COUNT 97
 #define EXTERNAL_TOKEN_COUNT 0
 #define FIELD_COUNT 0
-#define MAX_ALIAS_SEQUENCE_LENGTH 3
+#define MAX_ALIAS_SEQUENCE_LENGTH 4
 
 enum {
   anon_sym_COLON = 1,
   sym_newline = 2,
   anon_sym_COMMA = 3,
-  sym_range_start = 4,
-  sym_range_end = 5,
-  anon_sym_set = 6,
-  anon_sym_wrap = 7,
-  anon_sym_nowrap = 8,
-  anon_sym_edit = 9,
-  aux_sym_edit_token1 = 10,
-  anon_sym_buffer = 11,
-  aux_sym_buffer_token1 = 12,
-  anon_sym_buffers = 13,
-  sym_s = 14,
-  sym_cmd = 15,
-  sym_range = 16,
-  sym_set = 17,
-  sym_config_param = 18,
-  sym_edit = 19,
-  sym_buffer = 20,
-  sym_buffers = 21,
+  anon_sym_SEMI = 4,
+  sym_range_start = 5,
+  sym_range_end = 6,
+  anon_sym_set = 7,
+  anon_sym_se = 8,
+  anon_sym_setlocal = 9,
+  anon_sym_setl = 10,
+  anon_sym_setglobal = 11,
+  anon_sym_setg = 12,
+  sym_set_arg = 13,
+  sym_bang = 14,
+  sym_count = 15,
+  sym_append = 16,
+  sym_file_name = 17,
+  sym_buffer_name = 18,
+  sym_shell_cmd = 19,
+  anon_sym_edit = 20,
+  anon_sym_e = 21,
+  anon_sym_buffer = 22,
+  anon_sym_b = 23,
+  anon_sym_bnext = 24,
+  anon_sym_bn = 25,
+  anon_sym_bprevious = 26,
+  anon_sym_bp = 27,
+  anon_sym_bNext = 28,
+  anon_sym_bN = 29,
+  anon_sym_bdelete = 30,
+  anon_sym_bd = 31,
+  anon_sym_buffers = 32,
+  anon_sym_ls = 33,
+  anon_sym_files = 34,
+  sym_finder = 35,
+  anon_sym_write = 36,
+  anon_sym_w = 37,
+  anon_sym_update = 38,
+  anon_sym_up = 39,
+  anon_sym_saveas = 40,
+  anon_sym_sav = 41,
+  anon_sym_wall = 42,
+  anon_sym_wa = 43,
+  anon_sym_read = 44,
+  anon_sym_r = 45,
+  anon_sym_substitute = 46,
+  anon_sym_s = 47,
+  sym_subst_args = 48,
+  anon_sym_quit = 49,
+  anon_sym_q = 50,
+  anon_sym_wq = 51,
+  anon_sym_xit = 52,
+  anon_sym_x = 53,
+  anon_sym_exit = 54,
+  anon_sym_exi = 55,
+  anon_sym_qall = 56,
+  anon_sym_qa = 57,
+  anon_sym_quitall = 58,
+  anon_sym_quita = 59,
+  anon_sym_wqall = 60,
+  anon_sym_wqa = 61,
+  anon_sym_xall = 62,
+  anon_sym_xa = 63,
+  anon_sym_mksession = 64,
+  anon_sym_mks = 65,
+  anon_sym_mkdir = 66,
+  anon_sym_mkfile = 67,
+  anon_sym_rename = 68,
+  anon_sym_ren = 69,
+  anon_sym_remove = 70,
+  anon_sym_rm = 71,
+  anon_sym_nmap = 72,
+  anon_sym_nm = 73,
+  anon_sym_map = 74,
+  anon_sym_imap = 75,
+  anon_sym_im = 76,
+  anon_sym_cmap = 77,
+  anon_sym_cm = 78,
+  anon_sym_noremap = 79,
+  anon_sym_no = 80,
+  anon_sym_nnoremap = 81,
+  anon_sym_nn = 82,
+  anon_sym_inoremap = 83,
+  anon_sym_ino = 84,
+  anon_sym_cnoremap = 85,
+  anon_sym_cno = 86,
+  anon_sym_unmap = 87,
+  anon_sym_unm = 88,
+  anon_sym_nunmap = 89,
+  anon_sym_nun = 90,
+  anon_sym_iunmap = 91,
+  anon_sym_iu = 92,
+  anon_sym_cunmap = 93,
+  anon_sym_cu = 94,
+  sym_map_lhs = 95,
+  sym_map_rhs = 96,
+  sym_s = 97,
+  sym_cmd = 98,
+  sym_range = 99,
+  sym_set = 100,
+  sym_setlocal = 101,
+  sym_setglobal = 102,
+  sym_edit = 103,
+  sym_buffer = 104,
+  sym_bnext = 105,
+  sym_bprevious = 106,
+  sym_bdelete = 107,
+  sym_buffers = 108,
+  sym_write = 109,
+  sym_update = 110,
+  sym_saveas = 111,
+  sym_wall = 112,
+  sym_read = 113,
+  sym_substitute = 114,
+  sym_quit = 115,
+  sym_wq = 116,
+  sym_xit = 117,
+  sym_qall = 118,
+  sym_wqall = 119,
+  sym_mksession = 120,
+  sym_mkdir = 121,
+  sym_mkfile = 122,
+  sym_rename = 123,
+  sym_remove = 124,
+  sym_nmap = 125,
+  sym_imap = 126,
+  sym_cmap = 127,
+  sym_noremap = 128,
+  sym_inoremap = 129,
+  sym_cnoremap = 130,
+  sym_unmap = 131,
+  sym_iunmap = 132,
+  sym_cunmap = 133,
+  sym__map_args = 134,
+  aux_sym_set_repeat1 = 135,
 };
 
 static const char *ts_symbol_names[] = {
@@ -44,24 +158,138 @@ static const char *ts_symbol_names[] = {
   [anon_sym_COLON] = ":",
   [sym_newline] = "newline",
   [anon_sym_COMMA] = ",",
+  [anon_sym_SEMI] = ";",
   [sym_range_start] = "range_start",
   [sym_range_end] = "range_end",
   [anon_sym_set] = "set",
-  [anon_sym_wrap] = "wrap",
-  [anon_sym_nowrap] = "nowrap",
+  [anon_sym_se] = "se",
+  [anon_sym_setlocal] = "setlocal",
+  [anon_sym_setl] = "setl",
+  [anon_sym_setglobal] = "setglobal",
+  [anon_sym_setg] = "setg",
+  [sym_set_arg] = "set_arg",
+  [sym_bang] = "bang",
+  [sym_count] = "count",
+  [sym_append] = "append",
+  [sym_file_name] = "file_name",
+  [sym_buffer_name] = "buffer_name",
+  [sym_shell_cmd] = "shell_cmd",
   [anon_sym_edit] = "edit",
-  [aux_sym_edit_token1] = "edit_token1",
+  [anon_sym_e] = "e",
   [anon_sym_buffer] = "buffer",
-  [aux_sym_buffer_token1] = "buffer_token1",
+  [anon_sym_b] = "b",
+  [anon_sym_bnext] = "bnext",
+  [anon_sym_bn] = "bn",
+  [anon_sym_bprevious] = "bprevious",
+  [anon_sym_bp] = "bp",
+  [anon_sym_bNext] = "bNext",
+  [anon_sym_bN] = "bN",
+  [anon_sym_bdelete] = "bdelete",
+  [anon_sym_bd] = "bd",
   [anon_sym_buffers] = "buffers",
+  [anon_sym_ls] = "ls",
+  [anon_sym_files] = "files",
+  [sym_finder] = "finder",
+  [anon_sym_write] = "write",
+  [anon_sym_w] = "w",
+  [anon_sym_update] = "update",
+  [anon_sym_up] = "up",
+  [anon_sym_saveas] = "saveas",
+  [anon_sym_sav] = "sav",
+  [anon_sym_wall] = "wall",
+  [anon_sym_wa] = "wa",
+  [anon_sym_read] = "read",
+  [anon_sym_r] = "r",
+  [anon_sym_substitute] = "substitute",
+  [anon_sym_s] = "s",
+  [sym_subst_args] = "subst_args",
+  [anon_sym_quit] = "quit",
+  [anon_sym_q] = "q",
+  [anon_sym_wq] = "wq",
+  [anon_sym_xit] = "xit",
+  [anon_sym_x] = "x",
+  [anon_sym_exit] = "exit",
+  [anon_sym_exi] = "exi",
+  [anon_sym_qall] = "qall",
+  [anon_sym_qa] = "qa",
+  [anon_sym_quitall] = "quitall",
+  [anon_sym_quita] = "quita",
+  [anon_sym_wqall] = "wqall",
+  [anon_sym_wqa] = "wqa",
+  [anon_sym_xall] = "xall",
+  [anon_sym_xa] = "xa",
+  [anon_sym_mksession] = "mksession",
+  [anon_sym_mks] = "mks",
+  [anon_sym_mkdir] = "mkdir",
+  [anon_sym_mkfile] = "mkfile",
+  [anon_sym_rename] = "rename",
+  [anon_sym_ren] = "ren",
+  [anon_sym_remove] = "remove",
+  [anon_sym_rm] = "rm",
+  [anon_sym_nmap] = "nmap",
+  [anon_sym_nm] = "nm",
+  [anon_sym_map] = "map",
+  [anon_sym_imap] = "imap",
+  [anon_sym_im] = "im",
+  [anon_sym_cmap] = "cmap",
+  [anon_sym_cm] = "cm",
+  [anon_sym_noremap] = "noremap",
+  [anon_sym_no] = "no",
+  [anon_sym_nnoremap] = "nnoremap",
+  [anon_sym_nn] = "nn",
+  [anon_sym_inoremap] = "inoremap",
+  [anon_sym_ino] = "ino",
+  [anon_sym_cnoremap] = "cnoremap",
+  [anon_sym_cno] = "cno",
+  [anon_sym_unmap] = "unmap",
+  [anon_sym_unm] = "unm",
+  [anon_sym_nunmap] = "nunmap",
+  [anon_sym_nun] = "nun",
+  [anon_sym_iunmap] = "iunmap",
+  [anon_sym_iu] = "iu",
+  [anon_sym_cunmap] = "cunmap",
+  [anon_sym_cu] = "cu",
+  [sym_map_lhs] = "map_lhs",
+  [sym_map_rhs] = "map_rhs",
   [sym_s] = "s",
   [sym_cmd] = "cmd",
   [sym_range] = "range",
   [sym_set] = "set",
-  [sym_config_param] = "config_param",
+  [sym_setlocal] = "setlocal",
+  [sym_setglobal] = "setglobal",
   [sym_edit] = "edit",
   [sym_buffer] = "buffer",
+  [sym_bnext] = "bnext",
+  [sym_bprevious] = "bprevious",
+  [sym_bdelete] = "bdelete",
   [sym_buffers] = "buffers",
+  [sym_write] = "write",
+  [sym_update] = "update",
+  [sym_saveas] = "saveas",
+  [sym_wall] = "wall",
+  [sym_read] = "read",
+  [sym_substitute] = "substitute",
+  [sym_quit] = "quit",
+  [sym_wq] = "wq",
+  [sym_xit] = "xit",
+  [sym_qall] = "qall",
+  [sym_wqall] = "wqall",
+  [sym_mksession] = "mksession",
+  [sym_mkdir] = "mkdir",
+  [sym_mkfile] = "mkfile",
+  [sym_rename] = "rename",
+  [sym_remove] = "remove",
+  [sym_nmap] = "nmap",
+  [sym_imap] = "imap",
+  [sym_cmap] = "cmap",
+  [sym_noremap] = "noremap",
+  [sym_inoremap] = "inoremap",
+  [sym_cnoremap] = "cnoremap",
+  [sym_unmap] = "unmap",
+  [sym_iunmap] = "iunmap",
+  [sym_cunmap] = "cunmap",
+  [sym__map_args] = "_map_args",
+  [aux_sym_set_repeat1] = "set_repeat1",
 };
 
 static TSSymbol ts_symbol_map[] = {
@@ -69,24 +297,138 @@ static TSSymbol ts_symbol_map[] = {
   [anon_sym_COLON] = anon_sym_COLON,
   [sym_newline] = sym_newline,
   [anon_sym_COMMA] = anon_sym_COMMA,
+  [anon_sym_SEMI] = anon_sym_SEMI,
   [sym_range_start] = sym_range_start,
   [sym_range_end] = sym_range_end,
   [anon_sym_set] = anon_sym_set,
-  [anon_sym_wrap] = anon_sym_wrap,
-  [anon_sym_nowrap] = anon_sym_nowrap,
+  [anon_sym_se] = anon_sym_se,
+  [anon_sym_setlocal] = anon_sym_setlocal,
+  [anon_sym_setl] = anon_sym_setl,
+  [anon_sym_setglobal] = anon_sym_setglobal,
+  [anon_sym_setg] = anon_sym_setg,
+  [sym_set_arg] = sym_set_arg,
+  [sym_bang] = sym_bang,
+  [sym_count] = sym_count,
+  [sym_append] = sym_append,
+  [sym_file_name] = sym_file_name,
+  [sym_buffer_name] = sym_buffer_name,
+  [sym_shell_cmd] = sym_shell_cmd,
   [anon_sym_edit] = anon_sym_edit,
-  [aux_sym_edit_token1] = aux_sym_edit_token1,
+  [anon_sym_e] = anon_sym_e,
   [anon_sym_buffer] = anon_sym_buffer,
-  [aux_sym_buffer_token1] = aux_sym_buffer_token1,
+  [anon_sym_b] = anon_sym_b,
+  [anon_sym_bnext] = anon_sym_bnext,
+  [anon_sym_bn] = anon_sym_bn,
+  [anon_sym_bprevious] = anon_sym_bprevious,
+  [anon_sym_bp] = anon_sym_bp,
+  [anon_sym_bNext] = anon_sym_bNext,
+  [anon_sym_bN] = anon_sym_bN,
+  [anon_sym_bdelete] = anon_sym_bdelete,
+  [anon_sym_bd] = anon_sym_bd,
   [anon_sym_buffers] = anon_sym_buffers,
+  [anon_sym_ls] = anon_sym_ls,
+  [anon_sym_files] = anon_sym_files,
+  [sym_finder] = sym_finder,
+  [anon_sym_write] = anon_sym_write,
+  [anon_sym_w] = anon_sym_w,
+  [anon_sym_update] = anon_sym_update,
+  [anon_sym_up] = anon_sym_up,
+  [anon_sym_saveas] = anon_sym_saveas,
+  [anon_sym_sav] = anon_sym_sav,
+  [anon_sym_wall] = anon_sym_wall,
+  [anon_sym_wa] = anon_sym_wa,
+  [anon_sym_read] = anon_sym_read,
+  [anon_sym_r] = anon_sym_r,
+  [anon_sym_substitute] = anon_sym_substitute,
+  [anon_sym_s] = anon_sym_s,
+  [sym_subst_args] = sym_subst_args,
+  [anon_sym_quit] = anon_sym_quit,
+  [anon_sym_q] = anon_sym_q,
+  [anon_sym_wq] = anon_sym_wq,
+  [anon_sym_xit] = anon_sym_xit,
+  [anon_sym_x] = anon_sym_x,
+  [anon_sym_exit] = anon_sym_exit,
+  [anon_sym_exi] = anon_sym_exi,
+  [anon_sym_qall] = anon_sym_qall,
+  [anon_sym_qa] = anon_sym_qa,
+  [anon_sym_quitall] = anon_sym_quitall,
+  [anon_sym_quita] = anon_sym_quita,
+  [anon_sym_wqall] = anon_sym_wqall,
+  [anon_sym_wqa] = anon_sym_wqa,
+  [anon_sym_xall] = anon_sym_xall,
+  [anon_sym_xa] = anon_sym_xa,
+  [anon_sym_mksession] = anon_sym_mksession,
+  [anon_sym_mks] = anon_sym_mks,
+  [anon_sym_mkdir] = anon_sym_mkdir,
+  [anon_sym_mkfile] = anon_sym_mkfile,
+  [anon_sym_rename] = anon_sym_rename,
+  [anon_sym_ren] = anon_sym_ren,
+  [anon_sym_remove] = anon_sym_remove,
+  [anon_sym_rm] = anon_sym_rm,
+  [anon_sym_nmap] = anon_sym_nmap,
+  [anon_sym_nm] = anon_sym_nm,
+  [anon_sym_map] = anon_sym_map,
+  [anon_sym_imap] = anon_sym_imap,
+  [anon_sym_im] = anon_sym_im,
+  [anon_sym_cmap] = anon_sym_cmap,
+  [anon_sym_cm] = anon_sym_cm,
+  [anon_sym_noremap] = anon_sym_noremap,
+  [anon_sym_no] = anon_sym_no,
+  [anon_sym_nnoremap] = anon_sym_nnoremap,
+  [anon_sym_nn] = anon_sym_nn,
+  [anon_sym_inoremap] = anon_sym_inoremap,
+  [anon_sym_ino] = anon_sym_ino,
+  [anon_sym_cnoremap] = anon_sym_cnoremap,
+  [anon_sym_cno] = anon_sym_cno,
+  [anon_sym_unmap] = anon_sym_unmap,
+  [anon_sym_unm] = anon_sym_unm,
+  [anon_sym_nunmap] = anon_sym_nunmap,
+  [anon_sym_nun] = anon_sym_nun,
+  [anon_sym_iunmap] = anon_sym_iunmap,
+  [anon_sym_iu] = anon_sym_iu,
+  [anon_sym_cunmap] = anon_sym_cunmap,
+  [anon_sym_cu] = anon_sym_cu,
+  [sym_map_lhs] = sym_map_lhs,
+  [sym_map_rhs] = sym_map_rhs,
   [sym_s] = sym_s,
   [sym_cmd] = sym_cmd,
   [sym_range] = sym_range,
   [sym_set] = sym_set,
-  [sym_config_param] = sym_config_param,
+  [sym_setlocal] = sym_setlocal,
+  [sym_setglobal] = sym_setglobal,
   [sym_edit] = sym_edit,
   [sym_buffer] = sym_buffer,
+  [sym_bnext] = sym_bnext,
+  [sym_bprevious] = sym_bprevious,
+  [sym_bdelete] = sym_bdelete,
   [sym_buffers] = sym_buffers,
+  [sym_write] = sym_write,
+  [sym_update] = sym_update,
+  [sym_saveas] = sym_saveas,
+  [sym_wall] = sym_wall,
+  [sym_read] = sym_read,
+  [sym_substitute] = sym_substitute,
+  [sym_quit] = sym_quit,
+  [sym_wq] = sym_wq,
+  [sym_xit] = sym_xit,
+  [sym_qall] = sym_qall,
+  [sym_wqall] = sym_wqall,
+  [sym_mksession] = sym_mksession,
+  [sym_mkdir] = sym_mkdir,
+  [sym_mkfile] = sym_mkfile,
+  [sym_rename] = sym_rename,
+  [sym_remove] = sym_remove,
+  [sym_nmap] = sym_nmap,
+  [sym_imap] = sym_imap,
+  [sym_cmap] = sym_cmap,
+  [sym_noremap] = sym_noremap,
+  [sym_inoremap] = sym_inoremap,
+  [sym_cnoremap] = sym_cnoremap,
+  [sym_unmap] = sym_unmap,
+  [sym_iunmap] = sym_iunmap,
+  [sym_cunmap] = sym_cunmap,
+  [sym__map_args] = sym__map_args,
+  [aux_sym_set_repeat1] = aux_sym_set_repeat1,
 };
 
 static const TSSymbolMetadata ts_symbol_metadata[] = {
@@ -106,6 +448,10 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = false,
   },
+  [anon_sym_SEMI] = {
+    .visible = true,
+    .named = false,
+  },
   [sym_range_start] = {
     .visible = true,
     .named = true,
@@ -118,34 +464,362 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = false,
   },
-  [anon_sym_wrap] = {
+  [anon_sym_se] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_setlocal] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_setl] = {
     .visible = true,
     .named = false,
   },
-  [anon_sym_nowrap] = {
+  [anon_sym_setglobal] = {
     .visible = true,
     .named = false,
   },
+  [anon_sym_setg] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_set_arg] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_bang] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_count] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_append] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_file_name] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_buffer_name] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_shell_cmd] = {
+    .visible = true,
+    .named = true,
+  },
   [anon_sym_edit] = {
     .visible = true,
     .named = false,
   },
-  [aux_sym_edit_token1] = {
-    .visible = false,
+  [anon_sym_e] = {
+    .visible = true,
     .named = false,
   },
   [anon_sym_buffer] = {
     .visible = true,
     .named = false,
   },
-  [aux_sym_buffer_token1] = {
-    .visible = false,
+  [anon_sym_b] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_bnext] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_bn] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_bprevious] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_bp] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_bNext] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_bN] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_bdelete] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_bd] = {
+    .visible = true,
     .named = false,
   },
   [anon_sym_buffers] = {
     .visible = true,
     .named = false,
   },
+  [anon_sym_ls] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_files] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_finder] = {
+    .visible = true,
+    .named = true,
+  },
+  [anon_sym_write] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_w] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_update] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_up] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_saveas] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_sav] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_wall] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_wa] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_read] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_r] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_substitute] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_s] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_subst_args] = {
+    .visible = true,
+    .named = true,
+  },
+  [anon_sym_quit] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_q] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_wq] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_xit] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_x] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_exit] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_exi] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_qall] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_qa] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_quitall] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_quita] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_wqall] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_wqa] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_xall] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_xa] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_mksession] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_mks] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_mkdir] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_mkfile] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_rename] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_ren] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_remove] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_rm] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_nmap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_nm] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_map] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_imap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_im] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_cmap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_cm] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_noremap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_no] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_nnoremap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_nn] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_inoremap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_ino] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_cnoremap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_cno] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_unmap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_unm] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_nunmap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_nun] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_iunmap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_iu] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_cunmap] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_cu] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_map_lhs] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_map_rhs] = {
+    .visible = true,
+    .named = true,
+  },
   [sym_s] = {
     .visible = true,
     .named = true,
@@ -162,7 +836,11 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = true,
   },
-  [sym_config_param] = {
+  [sym_setlocal] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_setglobal] = {
     .visible = true,
     .named = true,
   },
@@ -174,10 +852,130 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = true,
   },
+  [sym_bnext] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_bprevious] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_bdelete] = {
+    .visible = true,
+    .named = true,
+  },
   [sym_buffers] = {
     .visible = true,
     .named = true,
   },
+  [sym_write] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_update] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_saveas] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_wall] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_read] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_substitute] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_quit] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_wq] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_xit] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_qall] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_wqall] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_mksession] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_mkdir] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_mkfile] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_rename] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_remove] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_nmap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_imap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_cmap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_noremap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_inoremap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_cnoremap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_unmap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_iunmap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_cunmap] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym__map_args] = {
+    .visible = false,
+    .named = true,
+  },
+  [aux_sym_set_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
 };
 
 static TSSymbol ts_alias_sequences[1][MAX_ALIAS_SEQUENCE_LENGTH] = {
@@ -189,247 +987,966 @@ static bool ts_lex(TSLexer *lexer, TSStateId state) {
   eof = lexer->eof(lexer);
   switch (state) {
     case 0:
-      if (eof) ADVANCE(28);
-      if (lookahead == '\n') ADVANCE(30);
+      if (eof) ADVANCE(141);
+      if (lookahead == '\n') ADVANCE(143);
       if (lookahead == '\r') ADVANCE(1);
-      if (lookahead == '$') ADVANCE(38);
-      if (lookahead == '%') ADVANCE(33);
-      if (lookahead == '\'') ADVANCE(24);
-      if (lookahead == ',') ADVANCE(31);
-      if (lookahead == '.') ADVANCE(32);
-      if (lookahead == '/') ADVANCE(4);
-      if (lookahead == ':') ADVANCE(29);
-      if (lookahead == '?') ADVANCE(26);
-      if (lookahead == 'b') ADVANCE(22);
-      if (lookahead == 'e') ADVANCE(8);
-      if (lookahead == 'n') ADVANCE(14);
-      if (lookahead == 's') ADVANCE(9);
-      if (lookahead == 'w') ADVANCE(17);
+      if (lookahead == '!') ADVANCE(159);
+      if (lookahead == ',') ADVANCE(144);
+      if (lookahead == ':') ADVANCE(142);
+      if (lookahead == ';') ADVANCE(145);
+      if (lookahead == '>') ADVANCE(7);
+      if (lookahead == 'b') ADVANCE(169);
+      if (lookahead == 'c') ADVANCE(67);
+      if (lookahead == 'e') ADVANCE(167);
+      if (lookahead == 'f') ADVANCE(47);
+      if (lookahead == 'i') ADVANCE(68);
+      if (lookahead == 'l') ADVANCE(103);
+      if (lookahead == 'm') ADVANCE(10);
+      if (lookahead == 'n') ADVANCE(69);
+      if (lookahead == 'q') ADVANCE(196);
+      if (lookahead == 'r') ADVANCE(191);
+      if (lookahead == 's') ADVANCE(193);
+      if (lookahead == 'u') ADVANCE(78);
+      if (lookahead == 'w') ADVANCE(183);
+      if (lookahead == 'x') ADVANCE(199);
       if (lookahead == '\t' ||
           lookahead == ' ') SKIP(0)
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(32);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(160);
+      END_STATE();
+    case 1:
+      if (lookahead == '\n') ADVANCE(143);
+      END_STATE();
+    case 2:
+      if (lookahead == '\n') ADVANCE(143);
+      if (lookahead == '\r') ADVANCE(1);
+      if (lookahead == '\'') ADVANCE(129);
+      if (lookahead == '/') ADVANCE(6);
+      if (lookahead == '?') ADVANCE(9);
+      if (lookahead == '\t' ||
+          lookahead == ' ') SKIP(2)
+      if (lookahead == '$' ||
+          ('.' <= lookahead && lookahead <= '9')) ADVANCE(149);
+      END_STATE();
+    case 3:
+      if (lookahead == '\n') ADVANCE(143);
+      if (lookahead == '\r') ADVANCE(1);
+      if (lookahead == '\t' ||
+          lookahead == ' ') ADVANCE(164);
+      if (lookahead != 0) ADVANCE(165);
+      END_STATE();
+    case 4:
+      if (lookahead == '\n') ADVANCE(143);
+      if (lookahead == '\r') ADVANCE(1);
+      if (lookahead == '\t' ||
+          lookahead == ' ') SKIP(4)
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          (lookahead < '0' || '9' < lookahead) &&
+          (lookahead < 'A' || 'Z' < lookahead) &&
+          lookahead != '\\' &&
+          (lookahead < 'a' || 'z' < lookahead) &&
+          lookahead != '|') ADVANCE(194);
+      END_STATE();
+    case 5:
+      if (lookahead == '/') ADVANCE(147);
+      if (lookahead == '\\') ADVANCE(130);
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(5);
+      END_STATE();
+    case 6:
+      if (lookahead == '/') ADVANCE(150);
+      if (lookahead == '\\') ADVANCE(133);
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(6);
+      END_STATE();
+    case 7:
+      if (lookahead == '>') ADVANCE(161);
+      END_STATE();
+    case 8:
+      if (lookahead == '?') ADVANCE(147);
+      if (lookahead == '\\') ADVANCE(131);
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(8);
+      END_STATE();
+    case 9:
+      if (lookahead == '?') ADVANCE(150);
+      if (lookahead == '\\') ADVANCE(134);
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(9);
+      END_STATE();
+    case 10:
+      if (lookahead == 'a') ADVANCE(87);
+      if (lookahead == 'k') ADVANCE(27);
+      END_STATE();
+    case 11:
+      if (lookahead == 'a') ADVANCE(26);
+      if (lookahead == 'm') ADVANCE(86);
+      if (lookahead == 'n') ADVANCE(215);
+      END_STATE();
+    case 12:
+      if (lookahead == 'a') ADVANCE(105);
+      END_STATE();
+    case 13:
+      if (lookahead == 'a') ADVANCE(92);
+      END_STATE();
+    case 14:
+      if (lookahead == 'a') ADVANCE(93);
+      END_STATE();
+    case 15:
+      if (lookahead == 'a') ADVANCE(62);
+      END_STATE();
+    case 16:
+      if (lookahead == 'a') ADVANCE(94);
+      END_STATE();
+    case 17:
+      if (lookahead == 'a') ADVANCE(63);
+      END_STATE();
+    case 18:
+      if (lookahead == 'a') ADVANCE(95);
+      END_STATE();
+    case 19:
+      if (lookahead == 'a') ADVANCE(96);
+      END_STATE();
+    case 20:
+      if (lookahead == 'a') ADVANCE(97);
+      END_STATE();
+    case 21:
+      if (lookahead == 'a') ADVANCE(98);
+      END_STATE();
+    case 22:
+      if (lookahead == 'a') ADVANCE(117);
+      END_STATE();
+    case 23:
+      if (lookahead == 'b') ADVANCE(109);
+      END_STATE();
+    case 24:
+      if (lookahead == 'b') ADVANCE(17);
+      END_STATE();
+    case 25:
+      if (lookahead == 'c') ADVANCE(15);
+      END_STATE();
+    case 26:
+      if (lookahead == 'd') ADVANCE(190);
+      END_STATE();
+    case 27:
+      if (lookahead == 'd') ADVANCE(50);
+      if (lookahead == 'f') ADVANCE(56);
+      if (lookahead == 's') ADVANCE(211);
+      END_STATE();
+    case 28:
+      if (lookahead == 'd') ADVANCE(39);
+      END_STATE();
+    case 29:
+      if (lookahead == 'e') ADVANCE(182);
+      END_STATE();
+    case 30:
+      if (lookahead == 'e') ADVANCE(213);
+      END_STATE();
+    case 31:
+      if (lookahead == 'e') ADVANCE(216);
+      END_STATE();
+    case 32:
+      if (lookahead == 'e') ADVANCE(214);
+      END_STATE();
+    case 33:
+      if (lookahead == 'e') ADVANCE(184);
+      END_STATE();
+    case 34:
+      if (lookahead == 'e') ADVANCE(176);
+      END_STATE();
+    case 35:
+      if (lookahead == 'e') ADVANCE(192);
+      END_STATE();
+    case 36:
+      if (lookahead == 'e') ADVANCE(124);
+      END_STATE();
+    case 37:
+      if (lookahead == 'e') ADVANCE(104);
+      END_STATE();
+    case 38:
+      if (lookahead == 'e') ADVANCE(100);
       END_STATE();
-    case 1:
-      if (lookahead == '\n') ADVANCE(30);
+    case 39:
+      if (lookahead == 'e') ADVANCE(101);
       END_STATE();
-    case 2:
-      if (lookahead == '\n') ADVANCE(30);
-      if (lookahead == '\r') ADVANCE(1);
-      if (lookahead == '\'') ADVANCE(25);
-      if (lookahead == '/') ADVANCE(4);
-      if (lookahead == '\t' ||
-          lookahead == ' ') SKIP(2)
-      if (lookahead == '$' ||
-          ('.' <= lookahead && lookahead <= '9')) ADVANCE(38);
+    case 40:
+      if (lookahead == 'e') ADVANCE(118);
       END_STATE();
-    case 3:
-      if (lookahead == '\n') ADVANCE(30);
-      if (lookahead == '\r') ADVANCE(1);
-      if (lookahead == '\t' ||
-          lookahead == ' ') SKIP(3)
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(49);
+    case 41:
+      if (lookahead == 'e') ADVANCE(74);
       END_STATE();
-    case 4:
-      if (lookahead == '/') ADVANCE(36);
-      if (lookahead != 0 &&
-          lookahead != '\n') ADVANCE(4);
+    case 42:
+      if (lookahead == 'e') ADVANCE(75);
       END_STATE();
-    case 5:
-      if (lookahead == '?') ADVANCE(34);
-      if (lookahead != 0) ADVANCE(5);
+    case 43:
+      if (lookahead == 'e') ADVANCE(76);
       END_STATE();
-    case 6:
-      if (lookahead == 'a') ADVANCE(15);
+    case 44:
+      if (lookahead == 'e') ADVANCE(77);
       END_STATE();
-    case 7:
-      if (lookahead == 'a') ADVANCE(16);
+    case 45:
+      if (lookahead == 'f') ADVANCE(46);
       END_STATE();
-    case 8:
-      if (lookahead == 'd') ADVANCE(13);
+    case 46:
+      if (lookahead == 'f') ADVANCE(38);
       END_STATE();
-    case 9:
-      if (lookahead == 'e') ADVANCE(20);
+    case 47:
+      if (lookahead == 'i') ADVANCE(64);
       END_STATE();
-    case 10:
-      if (lookahead == 'e') ADVANCE(18);
+    case 48:
+      if (lookahead == 'i') ADVANCE(201);
       END_STATE();
-    case 11:
-      if (lookahead == 'f') ADVANCE(10);
+    case 49:
+      if (lookahead == 'i') ADVANCE(111);
       END_STATE();
-    case 12:
-      if (lookahead == 'f') ADVANCE(11);
+    case 50:
+      if (lookahead == 'i') ADVANCE(99);
       END_STATE();
-    case 13:
-      if (lookahead == 'i') ADVANCE(21);
+    case 51:
+      if (lookahead == 'i') ADVANCE(112);
       END_STATE();
-    case 14:
-      if (lookahead == 'o') ADVANCE(23);
+    case 52:
+      if (lookahead == 'i') ADVANCE(116);
       END_STATE();
-    case 15:
-      if (lookahead == 'p') ADVANCE(42);
+    case 53:
+      if (lookahead == 'i') ADVANCE(83);
       END_STATE();
-    case 16:
-      if (lookahead == 'p') ADVANCE(43);
+    case 54:
+      if (lookahead == 'i') ADVANCE(84);
       END_STATE();
-    case 17:
-      if (lookahead == 'r') ADVANCE(6);
+    case 55:
+      if (lookahead == 'i') ADVANCE(115);
       END_STATE();
-    case 18:
-      if (lookahead == 'r') ADVANCE(48);
+    case 56:
+      if (lookahead == 'i') ADVANCE(66);
       END_STATE();
-    case 19:
-      if (lookahead == 'r') ADVANCE(7);
+    case 57:
+      if (lookahead == 'l') ADVANCE(202);
       END_STATE();
-    case 20:
-      if (lookahead == 't') ADVANCE(41);
+    case 58:
+      if (lookahead == 'l') ADVANCE(188);
       END_STATE();
-    case 21:
-      if (lookahead == 't') ADVANCE(44);
+    case 59:
+      if (lookahead == 'l') ADVANCE(208);
       END_STATE();
-    case 22:
-      if (lookahead == 'u') ADVANCE(12);
+    case 60:
+      if (lookahead == 'l') ADVANCE(206);
       END_STATE();
-    case 23:
-      if (lookahead == 'w') ADVANCE(19);
+    case 61:
+      if (lookahead == 'l') ADVANCE(204);
       END_STATE();
-    case 24:
-      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(34);
+    case 62:
+      if (lookahead == 'l') ADVANCE(154);
       END_STATE();
-    case 25:
-      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(39);
+    case 63:
+      if (lookahead == 'l') ADVANCE(156);
       END_STATE();
-    case 26:
+    case 64:
+      if (lookahead == 'l') ADVANCE(37);
+      if (lookahead == 'n') ADVANCE(28);
+      END_STATE();
+    case 65:
+      if (lookahead == 'l') ADVANCE(40);
+      END_STATE();
+    case 66:
+      if (lookahead == 'l') ADVANCE(30);
+      END_STATE();
+    case 67:
+      if (lookahead == 'm') ADVANCE(224);
+      if (lookahead == 'n') ADVANCE(81);
+      if (lookahead == 'u') ADVANCE(240);
+      END_STATE();
+    case 68:
+      if (lookahead == 'm') ADVANCE(222);
+      if (lookahead == 'n') ADVANCE(82);
+      if (lookahead == 'u') ADVANCE(238);
+      END_STATE();
+    case 69:
+      if (lookahead == 'm') ADVANCE(219);
+      if (lookahead == 'n') ADVANCE(228);
+      if (lookahead == 'o') ADVANCE(226);
+      if (lookahead == 'u') ADVANCE(79);
+      END_STATE();
+    case 70:
+      if (lookahead == 'm') ADVANCE(234);
+      END_STATE();
+    case 71:
+      if (lookahead == 'm') ADVANCE(13);
+      END_STATE();
+    case 72:
+      if (lookahead == 'm') ADVANCE(32);
+      END_STATE();
+    case 73:
+      if (lookahead == 'm') ADVANCE(14);
+      END_STATE();
+    case 74:
+      if (lookahead == 'm') ADVANCE(18);
+      END_STATE();
+    case 75:
+      if (lookahead == 'm') ADVANCE(19);
+      END_STATE();
+    case 76:
+      if (lookahead == 'm') ADVANCE(20);
+      END_STATE();
+    case 77:
+      if (lookahead == 'm') ADVANCE(21);
+      END_STATE();
+    case 78:
+      if (lookahead == 'n') ADVANCE(70);
+      if (lookahead == 'p') ADVANCE(185);
+      END_STATE();
+    case 79:
+      if (lookahead == 'n') ADVANCE(236);
+      END_STATE();
+    case 80:
+      if (lookahead == 'n') ADVANCE(210);
+      END_STATE();
+    case 81:
+      if (lookahead == 'o') ADVANCE(232);
+      END_STATE();
+    case 82:
+      if (lookahead == 'o') ADVANCE(230);
+      END_STATE();
+    case 83:
+      if (lookahead == 'o') ADVANCE(121);
+      END_STATE();
+    case 84:
+      if (lookahead == 'o') ADVANCE(80);
+      END_STATE();
+    case 85:
+      if (lookahead == 'o') ADVANCE(24);
+      END_STATE();
+    case 86:
+      if (lookahead == 'o') ADVANCE(125);
+      END_STATE();
+    case 87:
+      if (lookahead == 'p') ADVANCE(220);
+      END_STATE();
+    case 88:
+      if (lookahead == 'p') ADVANCE(223);
+      END_STATE();
+    case 89:
+      if (lookahead == 'p') ADVANCE(221);
+      END_STATE();
+    case 90:
+      if (lookahead == 'p') ADVANCE(218);
+      END_STATE();
+    case 91:
+      if (lookahead == 'p') ADVANCE(233);
+      END_STATE();
+    case 92:
+      if (lookahead == 'p') ADVANCE(239);
+      END_STATE();
+    case 93:
+      if (lookahead == 'p') ADVANCE(237);
+      END_STATE();
+    case 94:
+      if (lookahead == 'p') ADVANCE(235);
+      END_STATE();
+    case 95:
+      if (lookahead == 'p') ADVANCE(225);
+      END_STATE();
+    case 96:
+      if (lookahead == 'p') ADVANCE(231);
+      END_STATE();
+    case 97:
+      if (lookahead == 'p') ADVANCE(229);
+      END_STATE();
+    case 98:
+      if (lookahead == 'p') ADVANCE(227);
+      END_STATE();
+    case 99:
+      if (lookahead == 'r') ADVANCE(212);
+      END_STATE();
+    case 100:
+      if (lookahead == 'r') ADVANCE(168);
+      END_STATE();
+    case 101:
+      if (lookahead == 'r') ADVANCE(181);
+      END_STATE();
+    case 102:
+      if (lookahead == 'r') ADVANCE(44);
+      END_STATE();
+    case 103:
+      if (lookahead == 's') ADVANCE(179);
+      END_STATE();
+    case 104:
+      if (lookahead == 's') ADVANCE(180);
+      END_STATE();
+    case 105:
+      if (lookahead == 's') ADVANCE(186);
+      END_STATE();
+    case 106:
+      if (lookahead == 's') ADVANCE(172);
+      END_STATE();
+    case 107:
+      if (lookahead == 's') ADVANCE(108);
+      END_STATE();
+    case 108:
+      if (lookahead == 's') ADVANCE(54);
+      END_STATE();
+    case 109:
+      if (lookahead == 's') ADVANCE(120);
+      END_STATE();
+    case 110:
+      if (lookahead == 't') ADVANCE(198);
+      END_STATE();
+    case 111:
+      if (lookahead == 't') ADVANCE(166);
+      END_STATE();
+    case 112:
+      if (lookahead == 't') ADVANCE(195);
+      END_STATE();
+    case 113:
+      if (lookahead == 't') ADVANCE(174);
+      END_STATE();
+    case 114:
+      if (lookahead == 't') ADVANCE(170);
+      END_STATE();
+    case 115:
+      if (lookahead == 't') ADVANCE(122);
+      END_STATE();
+    case 116:
+      if (lookahead == 't') ADVANCE(29);
+      END_STATE();
+    case 117:
+      if (lookahead == 't') ADVANCE(33);
+      END_STATE();
+    case 118:
+      if (lookahead == 't') ADVANCE(34);
+      END_STATE();
+    case 119:
+      if (lookahead == 't') ADVANCE(35);
+      END_STATE();
+    case 120:
+      if (lookahead == 't') ADVANCE(55);
+      END_STATE();
+    case 121:
+      if (lookahead == 'u') ADVANCE(106);
+      END_STATE();
+    case 122:
+      if (lookahead == 'u') ADVANCE(119);
+      END_STATE();
+    case 123:
+      if (lookahead == 'v') ADVANCE(187);
+      END_STATE();
+    case 124:
+      if (lookahead == 'v') ADVANCE(53);
+      END_STATE();
+    case 125:
+      if (lookahead == 'v') ADVANCE(31);
+      END_STATE();
+    case 126:
+      if (lookahead == 'x') ADVANCE(113);
+      END_STATE();
+    case 127:
+      if (lookahead == 'x') ADVANCE(114);
+      END_STATE();
+    case 128:
+      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(147);
+      END_STATE();
+    case 129:
+      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(150);
+      END_STATE();
+    case 130:
       if (lookahead != 0 &&
-          lookahead != '?') ADVANCE(5);
+          lookahead != '\n') ADVANCE(5);
       END_STATE();
-    case 27:
-      if (eof) ADVANCE(28);
-      if (lookahead == '\n') ADVANCE(30);
+    case 131:
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(8);
+      END_STATE();
+    case 132:
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(158);
+      END_STATE();
+    case 133:
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(6);
+      END_STATE();
+    case 134:
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(9);
+      END_STATE();
+    case 135:
+      if (eof) ADVANCE(141);
+      if (lookahead == '\n') ADVANCE(143);
       if (lookahead == '\r') ADVANCE(1);
-      if (lookahead == '\'') ADVANCE(24);
-      if (lookahead == '?') ADVANCE(26);
-      if (lookahead == 'b') ADVANCE(22);
-      if (lookahead == 'e') ADVANCE(8);
-      if (lookahead == 's') ADVANCE(9);
+      if (lookahead == '!') ADVANCE(159);
+      if (lookahead == '>') ADVANCE(7);
       if (lookahead == '\t' ||
-          lookahead == ' ') SKIP(27)
-      if (lookahead == '%' ||
-          lookahead == '.' ||
-          ('0' <= lookahead && lookahead <= '9')) ADVANCE(33);
+          lookahead == ' ') SKIP(135)
+      if (lookahead != 0) ADVANCE(162);
       END_STATE();
-    case 28:
+    case 136:
+      if (eof) ADVANCE(141);
+      if (lookahead == '\n') ADVANCE(143);
+      if (lookahead == '\r') ADVANCE(1);
+      if (lookahead == '!') ADVANCE(159);
+      if (lookahead == '\t' ||
+          lookahead == ' ') SKIP(136)
+      if (lookahead != 0) ADVANCE(163);
+      END_STATE();
+    case 137:
+      if (eof) ADVANCE(141);
+      if (lookahead == '\n') ADVANCE(143);
+      if (lookahead == '\r') ADVANCE(1);
+      if (lookahead == '\'') ADVANCE(128);
+      if (lookahead == '/') ADVANCE(5);
+      if (lookahead == '?') ADVANCE(8);
+      if (lookahead == 'b') ADVANCE(169);
+      if (lookahead == 'c') ADVANCE(67);
+      if (lookahead == 'e') ADVANCE(167);
+      if (lookahead == 'f') ADVANCE(47);
+      if (lookahead == 'i') ADVANCE(68);
+      if (lookahead == 'l') ADVANCE(103);
+      if (lookahead == 'm') ADVANCE(10);
+      if (lookahead == 'n') ADVANCE(69);
+      if (lookahead == 'q') ADVANCE(196);
+      if (lookahead == 'r') ADVANCE(191);
+      if (lookahead == 's') ADVANCE(193);
+      if (lookahead == 'u') ADVANCE(78);
+      if (lookahead == 'w') ADVANCE(183);
+      if (lookahead == 'x') ADVANCE(199);
+      if (lookahead == '\t' ||
+          lookahead == ' ') SKIP(137)
+      if (lookahead == '$' ||
+          lookahead == '%' ||
+          ('.' <= lookahead && lookahead <= '9')) ADVANCE(146);
+      END_STATE();
+    case 138:
+      if (eof) ADVANCE(141);
+      if (lookahead == '\n') ADVANCE(143);
+      if (lookahead == '\r') ADVANCE(1);
+      if (lookahead == '\\') ADVANCE(132);
+      if (lookahead == '\t' ||
+          lookahead == ' ') SKIP(138)
+      if (lookahead != 0) ADVANCE(158);
+      END_STATE();
+    case 139:
+      if (eof) ADVANCE(141);
+      if (lookahead == '\n') ADVANCE(143);
+      if (lookahead == '\r') ADVANCE(1);
+      if (lookahead == '\t' ||
+          lookahead == ' ') SKIP(139)
+      if (lookahead != 0) ADVANCE(241);
+      END_STATE();
+    case 140:
+      if (eof) ADVANCE(141);
+      if (lookahead == '\n') ADVANCE(143);
+      if (lookahead == '\r') ADVANCE(1);
+      if (lookahead == '\t' ||
+          lookahead == ' ') SKIP(140)
+      if (lookahead != 0) ADVANCE(242);
+      END_STATE();
+    case 141:
       ACCEPT_TOKEN(ts_builtin_sym_end);
       END_STATE();
-    case 29:
+    case 142:
       ACCEPT_TOKEN(anon_sym_COLON);
       END_STATE();
-    case 30:
+    case 143:
       ACCEPT_TOKEN(sym_newline);
       END_STATE();
-    case 31:
+    case 144:
       ACCEPT_TOKEN(anon_sym_COMMA);
       END_STATE();
-    case 32:
-      ACCEPT_TOKEN(sym_range_start);
-      if (lookahead == '$') ADVANCE(38);
-      if (lookahead == '%') ADVANCE(33);
-      if (lookahead == '.') ADVANCE(32);
-      if (lookahead == '+' ||
-          lookahead == '-') ADVANCE(35);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(32);
+    case 145:
+      ACCEPT_TOKEN(anon_sym_SEMI);
       END_STATE();
-    case 33:
+    case 146:
       ACCEPT_TOKEN(sym_range_start);
-      if (lookahead == '%' ||
-          lookahead == '.') ADVANCE(33);
       if (lookahead == '+' ||
-          lookahead == '-') ADVANCE(35);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(33);
+          lookahead == '-') ADVANCE(148);
+      if (lookahead == '$' ||
+          lookahead == '%' ||
+          lookahead == '.') ADVANCE(146);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(146);
       END_STATE();
-    case 34:
+    case 147:
       ACCEPT_TOKEN(sym_range_start);
       if (lookahead == '+' ||
-          lookahead == '-') ADVANCE(35);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(35);
+          lookahead == '-') ADVANCE(148);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(148);
       END_STATE();
-    case 35:
+    case 148:
       ACCEPT_TOKEN(sym_range_start);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(35);
-      END_STATE();
-    case 36:
-      ACCEPT_TOKEN(sym_range_end);
-      if (lookahead == '/') ADVANCE(36);
-      if (lookahead == '+' ||
-          lookahead == '-') ADVANCE(37);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(37);
-      if (lookahead != 0 &&
-          lookahead != '\n') ADVANCE(4);
-      END_STATE();
-    case 37:
-      ACCEPT_TOKEN(sym_range_end);
-      if (lookahead == '/') ADVANCE(36);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(37);
-      if (lookahead != 0 &&
-          lookahead != '\n') ADVANCE(4);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(148);
       END_STATE();
-    case 38:
+    case 149:
       ACCEPT_TOKEN(sym_range_end);
       if (lookahead == '$' ||
-          lookahead == '.') ADVANCE(38);
+          lookahead == '.') ADVANCE(149);
       if (lookahead == '+' ||
-          lookahead == '-') ADVANCE(40);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(38);
+          lookahead == '-') ADVANCE(151);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(149);
       END_STATE();
-    case 39:
+    case 150:
       ACCEPT_TOKEN(sym_range_end);
       if (lookahead == '+' ||
-          lookahead == '-') ADVANCE(40);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(40);
+          lookahead == '-') ADVANCE(151);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(151);
       END_STATE();
-    case 40:
+    case 151:
       ACCEPT_TOKEN(sym_range_end);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(40);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(151);
       END_STATE();
-    case 41:
+    case 152:
       ACCEPT_TOKEN(anon_sym_set);
+      if (lookahead == 'g') ADVANCE(157);
+      if (lookahead == 'l') ADVANCE(155);
       END_STATE();
-    case 42:
-      ACCEPT_TOKEN(anon_sym_wrap);
+    case 153:
+      ACCEPT_TOKEN(anon_sym_se);
+      if (lookahead == 't') ADVANCE(152);
       END_STATE();
-    case 43:
-      ACCEPT_TOKEN(anon_sym_nowrap);
+    case 154:
+      ACCEPT_TOKEN(anon_sym_setlocal);
       END_STATE();
-    case 44:
-      ACCEPT_TOKEN(anon_sym_edit);
+    case 155:
+      ACCEPT_TOKEN(anon_sym_setl);
+      if (lookahead == 'o') ADVANCE(25);
       END_STATE();
-    case 45:
-      ACCEPT_TOKEN(aux_sym_edit_token1);
-      if (lookahead == '\n') ADVANCE(30);
-      if (lookahead != 0) ADVANCE(47);
+    case 156:
+      ACCEPT_TOKEN(anon_sym_setglobal);
       END_STATE();
-    case 46:
-      ACCEPT_TOKEN(aux_sym_edit_token1);
-      if (lookahead == '\r') ADVANCE(45);
+    case 157:
+      ACCEPT_TOKEN(anon_sym_setg);
+      if (lookahead == 'l') ADVANCE(85);
+      END_STATE();
+    case 158:
+      ACCEPT_TOKEN(sym_set_arg);
+      if (lookahead == '\\') ADVANCE(132);
+      if (lookahead != 0 &&
+          lookahead != '\t' &&
+          lookahead != '\n' &&
+          lookahead != '\r' &&
+          lookahead != ' ') ADVANCE(158);
+      END_STATE();
+    case 159:
+      ACCEPT_TOKEN(sym_bang);
+      END_STATE();
+    case 160:
+      ACCEPT_TOKEN(sym_count);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(160);
+      END_STATE();
+    case 161:
+      ACCEPT_TOKEN(sym_append);
+      END_STATE();
+    case 162:
+      ACCEPT_TOKEN(sym_file_name);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != '\r') ADVANCE(162);
+      END_STATE();
+    case 163:
+      ACCEPT_TOKEN(sym_buffer_name);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != '\r') ADVANCE(163);
+      END_STATE();
+    case 164:
+      ACCEPT_TOKEN(sym_shell_cmd);
       if (lookahead == '\t' ||
-          lookahead == ' ') ADVANCE(46);
+          lookahead == ' ') ADVANCE(164);
       if (lookahead != 0 &&
-          lookahead != '\n') ADVANCE(47);
+          lookahead != '\n' &&
+          lookahead != '\r') ADVANCE(165);
       END_STATE();
-    case 47:
-      ACCEPT_TOKEN(aux_sym_edit_token1);
+    case 165:
+      ACCEPT_TOKEN(sym_shell_cmd);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != '\r') ADVANCE(165);
+      END_STATE();
+    case 166:
+      ACCEPT_TOKEN(anon_sym_edit);
+      END_STATE();
+    case 167:
+      ACCEPT_TOKEN(anon_sym_e);
+      if (lookahead == 'd') ADVANCE(49);
+      if (lookahead == 'x') ADVANCE(48);
+      END_STATE();
+    case 168:
+      ACCEPT_TOKEN(anon_sym_buffer);
+      if (lookahead == 's') ADVANCE(178);
+      END_STATE();
+    case 169:
+      ACCEPT_TOKEN(anon_sym_b);
+      if (lookahead == 'N') ADVANCE(175);
+      if (lookahead == 'd') ADVANCE(177);
+      if (lookahead == 'n') ADVANCE(171);
+      if (lookahead == 'p') ADVANCE(173);
+      if (lookahead == 'u') ADVANCE(45);
+      END_STATE();
+    case 170:
+      ACCEPT_TOKEN(anon_sym_bnext);
+      END_STATE();
+    case 171:
+      ACCEPT_TOKEN(anon_sym_bn);
+      if (lookahead == 'e') ADVANCE(127);
+      END_STATE();
+    case 172:
+      ACCEPT_TOKEN(anon_sym_bprevious);
+      END_STATE();
+    case 173:
+      ACCEPT_TOKEN(anon_sym_bp);
+      if (lookahead == 'r') ADVANCE(36);
+      END_STATE();
+    case 174:
+      ACCEPT_TOKEN(anon_sym_bNext);
+      END_STATE();
+    case 175:
+      ACCEPT_TOKEN(anon_sym_bN);
+      if (lookahead == 'e') ADVANCE(126);
+      END_STATE();
+    case 176:
+      ACCEPT_TOKEN(anon_sym_bdelete);
+      END_STATE();
+    case 177:
+      ACCEPT_TOKEN(anon_sym_bd);
+      if (lookahead == 'e') ADVANCE(65);
+      END_STATE();
+    case 178:
+      ACCEPT_TOKEN(anon_sym_buffers);
+      END_STATE();
+    case 179:
+      ACCEPT_TOKEN(anon_sym_ls);
+      END_STATE();
+    case 180:
+      ACCEPT_TOKEN(anon_sym_files);
+      END_STATE();
+    case 181:
+      ACCEPT_TOKEN(sym_finder);
+      END_STATE();
+    case 182:
+      ACCEPT_TOKEN(anon_sym_write);
+      END_STATE();
+    case 183:
+      ACCEPT_TOKEN(anon_sym_w);
+      if (lookahead == 'a') ADVANCE(189);
+      if (lookahead == 'q') ADVANCE(197);
+      if (lookahead == 'r') ADVANCE(52);
+      END_STATE();
+    case 184:
+      ACCEPT_TOKEN(anon_sym_update);
+      END_STATE();
+    case 185:
+      ACCEPT_TOKEN(anon_sym_up);
+      if (lookahead == 'd') ADVANCE(22);
+      END_STATE();
+    case 186:
+      ACCEPT_TOKEN(anon_sym_saveas);
+      END_STATE();
+    case 187:
+      ACCEPT_TOKEN(anon_sym_sav);
+      if (lookahead == 'e') ADVANCE(12);
+      END_STATE();
+    case 188:
+      ACCEPT_TOKEN(anon_sym_wall);
+      END_STATE();
+    case 189:
+      ACCEPT_TOKEN(anon_sym_wa);
+      if (lookahead == 'l') ADVANCE(58);
+      END_STATE();
+    case 190:
+      ACCEPT_TOKEN(anon_sym_read);
+      END_STATE();
+    case 191:
+      ACCEPT_TOKEN(anon_sym_r);
+      if (lookahead == 'e') ADVANCE(11);
+      if (lookahead == 'm') ADVANCE(217);
+      END_STATE();
+    case 192:
+      ACCEPT_TOKEN(anon_sym_substitute);
+      END_STATE();
+    case 193:
+      ACCEPT_TOKEN(anon_sym_s);
+      if (lookahead == 'a') ADVANCE(123);
+      if (lookahead == 'e') ADVANCE(153);
+      if (lookahead == 'u') ADVANCE(23);
+      END_STATE();
+    case 194:
+      ACCEPT_TOKEN(sym_subst_args);
       if (lookahead != 0 &&
-          lookahead != '\n') ADVANCE(47);
+          lookahead != '\n' &&
+          lookahead != '\r') ADVANCE(194);
+      END_STATE();
+    case 195:
+      ACCEPT_TOKEN(anon_sym_quit);
+      if (lookahead == 'a') ADVANCE(205);
+      END_STATE();
+    case 196:
+      ACCEPT_TOKEN(anon_sym_q);
+      if (lookahead == 'a') ADVANCE(203);
+      if (lookahead == 'u') ADVANCE(51);
+      END_STATE();
+    case 197:
+      ACCEPT_TOKEN(anon_sym_wq);
+      if (lookahead == 'a') ADVANCE(207);
+      END_STATE();
+    case 198:
+      ACCEPT_TOKEN(anon_sym_xit);
+      END_STATE();
+    case 199:
+      ACCEPT_TOKEN(anon_sym_x);
+      if (lookahead == 'a') ADVANCE(209);
+      if (lookahead == 'i') ADVANCE(110);
+      END_STATE();
+    case 200:
+      ACCEPT_TOKEN(anon_sym_exit);
+      END_STATE();
+    case 201:
+      ACCEPT_TOKEN(anon_sym_exi);
+      if (lookahead == 't') ADVANCE(200);
+      END_STATE();
+    case 202:
+      ACCEPT_TOKEN(anon_sym_qall);
+      END_STATE();
+    case 203:
+      ACCEPT_TOKEN(anon_sym_qa);
+      if (lookahead == 'l') ADVANCE(57);
+      END_STATE();
+    case 204:
+      ACCEPT_TOKEN(anon_sym_quitall);
+      END_STATE();
+    case 205:
+      ACCEPT_TOKEN(anon_sym_quita);
+      if (lookahead == 'l') ADVANCE(61);
+      END_STATE();
+    case 206:
+      ACCEPT_TOKEN(anon_sym_wqall);
+      END_STATE();
+    case 207:
+      ACCEPT_TOKEN(anon_sym_wqa);
+      if (lookahead == 'l') ADVANCE(60);
+      END_STATE();
+    case 208:
+      ACCEPT_TOKEN(anon_sym_xall);
+      END_STATE();
+    case 209:
+      ACCEPT_TOKEN(anon_sym_xa);
+      if (lookahead == 'l') ADVANCE(59);
+      END_STATE();
+    case 210:
+      ACCEPT_TOKEN(anon_sym_mksession);
+      END_STATE();
+    case 211:
+      ACCEPT_TOKEN(anon_sym_mks);
+      if (lookahead == 'e') ADVANCE(107);
+      END_STATE();
+    case 212:
+      ACCEPT_TOKEN(anon_sym_mkdir);
+      END_STATE();
+    case 213:
+      ACCEPT_TOKEN(anon_sym_mkfile);
+      END_STATE();
+    case 214:
+      ACCEPT_TOKEN(anon_sym_rename);
+      END_STATE();
+    case 215:
+      ACCEPT_TOKEN(anon_sym_ren);
+      if (lookahead == 'a') ADVANCE(72);
+      END_STATE();
+    case 216:
+      ACCEPT_TOKEN(anon_sym_remove);
+      END_STATE();
+    case 217:
+      ACCEPT_TOKEN(anon_sym_rm);
+      END_STATE();
+    case 218:
+      ACCEPT_TOKEN(anon_sym_nmap);
+      END_STATE();
+    case 219:
+      ACCEPT_TOKEN(anon_sym_nm);
+      if (lookahead == 'a') ADVANCE(90);
+      END_STATE();
+    case 220:
+      ACCEPT_TOKEN(anon_sym_map);
+      END_STATE();
+    case 221:
+      ACCEPT_TOKEN(anon_sym_imap);
+      END_STATE();
+    case 222:
+      ACCEPT_TOKEN(anon_sym_im);
+      if (lookahead == 'a') ADVANCE(89);
+      END_STATE();
+    case 223:
+      ACCEPT_TOKEN(anon_sym_cmap);
+      END_STATE();
+    case 224:
+      ACCEPT_TOKEN(anon_sym_cm);
+      if (lookahead == 'a') ADVANCE(88);
+      END_STATE();
+    case 225:
+      ACCEPT_TOKEN(anon_sym_noremap);
+      END_STATE();
+    case 226:
+      ACCEPT_TOKEN(anon_sym_no);
+      if (lookahead == 'r') ADVANCE(41);
+      END_STATE();
+    case 227:
+      ACCEPT_TOKEN(anon_sym_nnoremap);
+      END_STATE();
+    case 228:
+      ACCEPT_TOKEN(anon_sym_nn);
+      if (lookahead == 'o') ADVANCE(102);
+      END_STATE();
+    case 229:
+      ACCEPT_TOKEN(anon_sym_inoremap);
+      END_STATE();
+    case 230:
+      ACCEPT_TOKEN(anon_sym_ino);
+      if (lookahead == 'r') ADVANCE(43);
+      END_STATE();
+    case 231:
+      ACCEPT_TOKEN(anon_sym_cnoremap);
+      END_STATE();
+    case 232:
+      ACCEPT_TOKEN(anon_sym_cno);
+      if (lookahead == 'r') ADVANCE(42);
+      END_STATE();
+    case 233:
+      ACCEPT_TOKEN(anon_sym_unmap);
+      END_STATE();
+    case 234:
+      ACCEPT_TOKEN(anon_sym_unm);
+      if (lookahead == 'a') ADVANCE(91);
+      END_STATE();
+    case 235:
+      ACCEPT_TOKEN(anon_sym_nunmap);
+      END_STATE();
+    case 236:
+      ACCEPT_TOKEN(anon_sym_nun);
+      if (lookahead == 'm') ADVANCE(16);
+      END_STATE();
+    case 237:
+      ACCEPT_TOKEN(anon_sym_iunmap);
+      END_STATE();
+    case 238:
+      ACCEPT_TOKEN(anon_sym_iu);
+      if (lookahead == 'n') ADVANCE(73);
+      END_STATE();
+    case 239:
+      ACCEPT_TOKEN(anon_sym_cunmap);
       END_STATE();
-    case 48:
-      ACCEPT_TOKEN(anon_sym_buffer);
-      if (lookahead == 's') ADVANCE(50);
+    case 240:
+      ACCEPT_TOKEN(anon_sym_cu);
+      if (lookahead == 'n') ADVANCE(71);
       END_STATE();
-    case 49:
-      ACCEPT_TOKEN(aux_sym_buffer_token1);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(49);
+    case 241:
+      ACCEPT_TOKEN(sym_map_lhs);
+      if (lookahead != 0 &&
+          lookahead != '\t' &&
+          lookahead != '\n' &&
+          lookahead != '\r' &&
+          lookahead != ' ') ADVANCE(241);
       END_STATE();
-    case 50:
-      ACCEPT_TOKEN(anon_sym_buffers);
+    case 242:
+      ACCEPT_TOKEN(sym_map_rhs);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != '\r') ADVANCE(242);
       END_STATE();
     default:
       return false;
@@ -439,23 +1956,108 @@ static bool ts_lex(TSLexer *lexer, TSStateId state) {
 static TSLexMode ts_lex_modes[STATE_COUNT] = {
   [0] = {.lex_state = 0},
   [1] = {.lex_state = 0},
-  [2] = {.lex_state = 27},
+  [2] = {.lex_state = 137},
   [3] = {.lex_state = 0},
   [4] = {.lex_state = 0},
   [5] = {.lex_state = 0},
-  [6] = {.lex_state = 0},
-  [7] = {.lex_state = 0},
-  [8] = {.lex_state = 46},
-  [9] = {.lex_state = 3},
-  [10] = {.lex_state = 0},
-  [11] = {.lex_state = 0},
-  [12] = {.lex_state = 0},
-  [13] = {.lex_state = 2},
-  [14] = {.lex_state = 0},
-  [15] = {.lex_state = 0},
-  [16] = {.lex_state = 0},
-  [17] = {.lex_state = 0},
-  [18] = {.lex_state = 0},
+  [6] = {.lex_state = 135},
+  [7] = {.lex_state = 135},
+  [8] = {.lex_state = 138},
+  [9] = {.lex_state = 138},
+  [10] = {.lex_state = 135},
+  [11] = {.lex_state = 135},
+  [12] = {.lex_state = 135},
+  [13] = {.lex_state = 136},
+  [14] = {.lex_state = 138},
+  [15] = {.lex_state = 138},
+  [16] = {.lex_state = 138},
+  [17] = {.lex_state = 139},
+  [18] = {.lex_state = 139},
+  [19] = {.lex_state = 135},
+  [20] = {.lex_state = 139},
+  [21] = {.lex_state = 139},
+  [22] = {.lex_state = 135},
+  [23] = {.lex_state = 135},
+  [24] = {.lex_state = 139},
+  [25] = {.lex_state = 139},
+  [26] = {.lex_state = 135},
+  [27] = {.lex_state = 138},
+  [28] = {.lex_state = 138},
+  [29] = {.lex_state = 136},
+  [30] = {.lex_state = 0},
+  [31] = {.lex_state = 135},
+  [32] = {.lex_state = 0},
+  [33] = {.lex_state = 135},
+  [34] = {.lex_state = 0},
+  [35] = {.lex_state = 0},
+  [36] = {.lex_state = 0},
+  [37] = {.lex_state = 135},
+  [38] = {.lex_state = 136},
+  [39] = {.lex_state = 0},
+  [40] = {.lex_state = 140},
+  [41] = {.lex_state = 135},
+  [42] = {.lex_state = 135},
+  [43] = {.lex_state = 135},
+  [44] = {.lex_state = 135},
+  [45] = {.lex_state = 135},
+  [46] = {.lex_state = 135},
+  [47] = {.lex_state = 0},
+  [48] = {.lex_state = 0},
+  [49] = {.lex_state = 2},
+  [50] = {.lex_state = 0},
+  [51] = {.lex_state = 0},
+  [52] = {.lex_state = 0},
+  [53] = {.lex_state = 0},
+  [54] = {.lex_state = 0},
+  [55] = {.lex_state = 0},
+  [56] = {.lex_state = 0},
+  [57] = {.lex_state = 0},
+  [58] = {.lex_state = 0},
+  [59] = {.lex_state = 135},
+  [60] = {.lex_state = 0},
+  [61] = {.lex_state = 0},
+  [62] = {.lex_state = 3},
+  [63] = {.lex_state = 0},
+  [64] = {.lex_state = 0},
+  [65] = {.lex_state = 0},
+  [66] = {.lex_state = 4},
+  [67] = {.lex_state = 0},
+  [68] = {.lex_state = 139},
+  [69] = {.lex_state = 0},
+  [70] = {.lex_state = 0},
+  [71] = {.lex_state = 0},
+  [72] = {.lex_state = 139},
+  [73] = {.lex_state = 0},
+  [74] = {.lex_state = 0},
+  [75] = {.lex_state = 0},
+  [76] = {.lex_state = 0},
+  [77] = {.lex_state = 139},
+  [78] = {.lex_state = 0},
+  [79] = {.lex_state = 0},
+  [80] = {.lex_state = 0},
+  [81] = {.lex_state = 0},
+  [82] = {.lex_state = 0},
+  [83] = {.lex_state = 0},
+  [84] = {.lex_state = 0},
+  [85] = {.lex_state = 0},
+  [86] = {.lex_state = 0},
+  [87] = {.lex_state = 0},
+  [88] = {.lex_state = 0},
+  [89] = {.lex_state = 135},
+  [90] = {.lex_state = 0},
+  [91] = {.lex_state = 0},
+  [92] = {.lex_state = 135},
+  [93] = {.lex_state = 0},
+  [94] = {.lex_state = 135},
+  [95] = {.lex_state = 0},
+  [96] = {.lex_state = 0},
+  [97] = {.lex_state = 0},
+  [98] = {.lex_state = 0},
+  [99] = {.lex_state = 0},
+  [100] = {.lex_state = 0},
+  [101] = {.lex_state = 0},
+  [102] = {.lex_state = 0},
+  [103] = {.lex_state = 0},
 };
 
 static uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
@@ -464,168 +2066,1232 @@ static uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
     [anon_sym_COLON] = ACTIONS(1),
     [sym_newline] = ACTIONS(3),
     [anon_sym_COMMA] = ACTIONS(1),
-    [sym_range_start] = ACTIONS(1),
-    [sym_range_end] = ACTIONS(1),
+    [anon_sym_SEMI] = ACTIONS(1),
     [anon_sym_set] = ACTIONS(1),
-    [anon_sym_wrap] = ACTIONS(1),
-    [anon_sym_nowrap] = ACTIONS(1),
+    [anon_sym_se] = ACTIONS(1),
+    [anon_sym_setlocal] = ACTIONS(1),
+    [anon_sym_setl] = ACTIONS(1),
+    [anon_sym_setglobal] = ACTIONS(1),
+    [anon_sym_setg] = ACTIONS(1),
+    [sym_bang] = ACTIONS(1),
+    [sym_count] = ACTIONS(1),
+    [sym_append] = ACTIONS(1),
     [anon_sym_edit] = ACTIONS(1),
+    [anon_sym_e] = ACTIONS(1),
     [anon_sym_buffer] = ACTIONS(1),
-    [aux_sym_buffer_token1] = ACTIONS(1),
+    [anon_sym_b] = ACTIONS(1),
+    [anon_sym_bnext] = ACTIONS(1),
+    [anon_sym_bn] = ACTIONS(1),
+    [anon_sym_bprevious] = ACTIONS(1),
+    [anon_sym_bp] = ACTIONS(1),
+    [anon_sym_bNext] = ACTIONS(1),
+    [anon_sym_bN] = ACTIONS(1),
+    [anon_sym_bdelete] = ACTIONS(1),
+    [anon_sym_bd] = ACTIONS(1),
     [anon_sym_buffers] = ACTIONS(1),
+    [anon_sym_ls] = ACTIONS(1),
+    [anon_sym_files] = ACTIONS(1),
+    [sym_finder] = ACTIONS(1),
+    [anon_sym_write] = ACTIONS(1),
+    [anon_sym_w] = ACTIONS(1),
+    [anon_sym_update] = ACTIONS(1),
+    [anon_sym_up] = ACTIONS(1),
+    [anon_sym_saveas] = ACTIONS(1),
+    [anon_sym_sav] = ACTIONS(1),
+    [anon_sym_wall] = ACTIONS(1),
+    [anon_sym_wa] = ACTIONS(1),
+    [anon_sym_read] = ACTIONS(1),
+    [anon_sym_r] = ACTIONS(1),
+    [anon_sym_substitute] = ACTIONS(1),
+    [anon_sym_s] = ACTIONS(1),
+    [anon_sym_quit] = ACTIONS(1),
+    [anon_sym_q] = ACTIONS(1),
+    [anon_sym_wq] = ACTIONS(1),
+    [anon_sym_xit] = ACTIONS(1),
+    [anon_sym_x] = ACTIONS(1),
+    [anon_sym_exit] = ACTIONS(1),
+    [anon_sym_exi] = ACTIONS(1),
+    [anon_sym_qall] = ACTIONS(1),
+    [anon_sym_qa] = ACTIONS(1),
+    [anon_sym_quitall] = ACTIONS(1),
+    [anon_sym_quita] = ACTIONS(1),
+    [anon_sym_wqall] = ACTIONS(1),
+    [anon_sym_wqa] = ACTIONS(1),
+    [anon_sym_xall] = ACTIONS(1),
+    [anon_sym_xa] = ACTIONS(1),
+    [anon_sym_mksession] = ACTIONS(1),
+    [anon_sym_mks] = ACTIONS(1),
+    [anon_sym_mkdir] = ACTIONS(1),
+    [anon_sym_mkfile] = ACTIONS(1),
+    [anon_sym_rename] = ACTIONS(1),
+    [anon_sym_ren] = ACTIONS(1),
+    [anon_sym_remove] = ACTIONS(1),
+    [anon_sym_rm] = ACTIONS(1),
+    [anon_sym_nmap] = ACTIONS(1),
+    [anon_sym_nm] = ACTIONS(1),
+    [anon_sym_map] = ACTIONS(1),
+    [anon_sym_imap] = ACTIONS(1),
+    [anon_sym_im] = ACTIONS(1),
+    [anon_sym_cmap] = ACTIONS(1),
+    [anon_sym_cm] = ACTIONS(1),
+    [anon_sym_noremap] = ACTIONS(1),
+    [anon_sym_no] = ACTIONS(1),
+    [anon_sym_nnoremap] = ACTIONS(1),
+    [anon_sym_nn] = ACTIONS(1),
+    [anon_sym_inoremap] = ACTIONS(1),
+    [anon_sym_ino] = ACTIONS(1),
+    [anon_sym_cnoremap] = ACTIONS(1),
+    [anon_sym_cno] = ACTIONS(1),
+    [anon_sym_unmap] = ACTIONS(1),
+    [anon_sym_unm] = ACTIONS(1),
+    [anon_sym_nunmap] = ACTIONS(1),
+    [anon_sym_nun] = ACTIONS(1),
+    [anon_sym_iunmap] = ACTIONS(1),
+    [anon_sym_iu] = ACTIONS(1),
+    [anon_sym_cunmap] = ACTIONS(1),
+    [anon_sym_cu] = ACTIONS(1),
   },
   [1] = {
-    [sym_s] = STATE(7),
+    [sym_s] = STATE(54),
     [anon_sym_COLON] = ACTIONS(5),
     [sym_newline] = ACTIONS(3),
   },
   [2] = {
-    [sym_cmd] = STATE(11),
+    [sym_cmd] = STATE(76),
     [sym_range] = STATE(3),
-    [sym_set] = STATE(12),
-    [sym_edit] = STATE(12),
-    [sym_buffer] = STATE(12),
-    [sym_buffers] = STATE(12),
+    [sym_set] = STATE(65),
+    [sym_setlocal] = STATE(65),
+    [sym_setglobal] = STATE(65),
+    [sym_edit] = STATE(65),
+    [sym_buffer] = STATE(65),
+    [sym_bnext] = STATE(65),
+    [sym_bprevious] = STATE(65),
+    [sym_bdelete] = STATE(65),
+    [sym_buffers] = STATE(65),
+    [sym_write] = STATE(65),
+    [sym_update] = STATE(65),
+    [sym_saveas] = STATE(65),
+    [sym_wall] = STATE(65),
+    [sym_read] = STATE(65),
+    [sym_substitute] = STATE(65),
+    [sym_quit] = STATE(65),
+    [sym_wq] = STATE(65),
+    [sym_xit] = STATE(65),
+    [sym_qall] = STATE(65),
+    [sym_wqall] = STATE(65),
+    [sym_mksession] = STATE(65),
+    [sym_mkdir] = STATE(65),
+    [sym_mkfile] = STATE(65),
+    [sym_rename] = STATE(65),
+    [sym_remove] = STATE(65),
+    [sym_nmap] = STATE(65),
+    [sym_imap] = STATE(65),
+    [sym_cmap] = STATE(65),
+    [sym_noremap] = STATE(65),
+    [sym_inoremap] = STATE(65),
+    [sym_cnoremap] = STATE(65),
+    [sym_unmap] = STATE(65),
+    [sym_iunmap] = STATE(65),
+    [sym_cunmap] = STATE(65),
     [ts_builtin_sym_end] = ACTIONS(7),
     [sym_newline] = ACTIONS(3),
     [sym_range_start] = ACTIONS(9),
     [anon_sym_set] = ACTIONS(11),
-    [anon_sym_edit] = ACTIONS(13),
-    [anon_sym_buffer] = ACTIONS(15),
-    [anon_sym_buffers] = ACTIONS(17),
+    [anon_sym_se] = ACTIONS(11),
+    [anon_sym_setlocal] = ACTIONS(13),
+    [anon_sym_setl] = ACTIONS(15),
+    [anon_sym_setglobal] = ACTIONS(17),
+    [anon_sym_setg] = ACTIONS(19),
+    [anon_sym_edit] = ACTIONS(21),
+    [anon_sym_e] = ACTIONS(23),
+    [anon_sym_buffer] = ACTIONS(25),
+    [anon_sym_b] = ACTIONS(25),
+    [anon_sym_bnext] = ACTIONS(27),
+    [anon_sym_bn] = ACTIONS(29),
+    [anon_sym_bprevious] = ACTIONS(31),
+    [anon_sym_bp] = ACTIONS(33),
+    [anon_sym_bNext] = ACTIONS(31),
+    [anon_sym_bN] = ACTIONS(33),
+    [anon_sym_bdelete] = ACTIONS(35),
+    [anon_sym_bd] = ACTIONS(37),
+    [anon_sym_buffers] = ACTIONS(39),
+    [anon_sym_ls] = ACTIONS(39),
+    [anon_sym_files] = ACTIONS(39),
+    [sym_finder] = ACTIONS(41),
+    [anon_sym_write] = ACTIONS(43),
+    [anon_sym_w] = ACTIONS(45),
+    [anon_sym_update] = ACTIONS(47),
+    [anon_sym_up] = ACTIONS(49),
+    [anon_sym_saveas] = ACTIONS(51),
+    [anon_sym_sav] = ACTIONS(53),
+    [anon_sym_wall] = ACTIONS(55),
+    [anon_sym_wa] = ACTIONS(57),
+    [anon_sym_read] = ACTIONS(59),
+    [anon_sym_r] = ACTIONS(61),
+    [anon_sym_substitute] = ACTIONS(63),
+    [anon_sym_s] = ACTIONS(65),
+    [anon_sym_quit] = ACTIONS(67),
+    [anon_sym_q] = ACTIONS(67),
+    [anon_sym_wq] = ACTIONS(69),
+    [anon_sym_xit] = ACTIONS(71),
+    [anon_sym_x] = ACTIONS(73),
+    [anon_sym_exit] = ACTIONS(71),
+    [anon_sym_exi] = ACTIONS(73),
+    [anon_sym_qall] = ACTIONS(75),
+    [anon_sym_qa] = ACTIONS(77),
+    [anon_sym_quitall] = ACTIONS(75),
+    [anon_sym_quita] = ACTIONS(77),
+    [anon_sym_wqall] = ACTIONS(79),
+    [anon_sym_wqa] = ACTIONS(81),
+    [anon_sym_xall] = ACTIONS(79),
+    [anon_sym_xa] = ACTIONS(81),
+    [anon_sym_mksession] = ACTIONS(83),
+    [anon_sym_mks] = ACTIONS(85),
+    [anon_sym_mkdir] = ACTIONS(87),
+    [anon_sym_mkfile] = ACTIONS(89),
+    [anon_sym_rename] = ACTIONS(91),
+    [anon_sym_ren] = ACTIONS(93),
+    [anon_sym_remove] = ACTIONS(95),
+    [anon_sym_rm] = ACTIONS(95),
+    [anon_sym_nmap] = ACTIONS(97),
+    [anon_sym_nm] = ACTIONS(99),
+    [anon_sym_map] = ACTIONS(97),
+    [anon_sym_imap] = ACTIONS(101),
+    [anon_sym_im] = ACTIONS(103),
+    [anon_sym_cmap] = ACTIONS(105),
+    [anon_sym_cm] = ACTIONS(107),
+    [anon_sym_noremap] = ACTIONS(109),
+    [anon_sym_no] = ACTIONS(111),
+    [anon_sym_nnoremap] = ACTIONS(109),
+    [anon_sym_nn] = ACTIONS(111),
+    [anon_sym_inoremap] = ACTIONS(113),
+    [anon_sym_ino] = ACTIONS(115),
+    [anon_sym_cnoremap] = ACTIONS(117),
+    [anon_sym_cno] = ACTIONS(119),
+    [anon_sym_unmap] = ACTIONS(121),
+    [anon_sym_unm] = ACTIONS(123),
+    [anon_sym_nunmap] = ACTIONS(121),
+    [anon_sym_nun] = ACTIONS(123),
+    [anon_sym_iunmap] = ACTIONS(125),
+    [anon_sym_iu] = ACTIONS(127),
+    [anon_sym_cunmap] = ACTIONS(129),
+    [anon_sym_cu] = ACTIONS(131),
+  },
+  [3] = {
+    [sym_cmd] = STATE(87),
+    [sym_set] = STATE(65),
+    [sym_setlocal] = STATE(65),
+    [sym_setglobal] = STATE(65),
+    [sym_edit] = STATE(65),
+    [sym_buffer] = STATE(65),
+    [sym_bnext] = STATE(65),
+    [sym_bprevious] = STATE(65),
+    [sym_bdelete] = STATE(65),
+    [sym_buffers] = STATE(65),
+    [sym_write] = STATE(65),
+    [sym_update] = STATE(65),
+    [sym_saveas] = STATE(65),
+    [sym_wall] = STATE(65),
+    [sym_read] = STATE(65),
+    [sym_substitute] = STATE(65),
+    [sym_quit] = STATE(65),
+    [sym_wq] = STATE(65),
+    [sym_xit] = STATE(65),
+    [sym_qall] = STATE(65),
+    [sym_wqall] = STATE(65),
+    [sym_mksession] = STATE(65),
+    [sym_mkdir] = STATE(65),
+    [sym_mkfile] = STATE(65),
+    [sym_rename] = STATE(65),
+    [sym_remove] = STATE(65),
+    [sym_nmap] = STATE(65),
+    [sym_imap] = STATE(65),
+    [sym_cmap] = STATE(65),
+    [sym_noremap] = STATE(65),
+    [sym_inoremap] = STATE(65),
+    [sym_cnoremap] = STATE(65),
+    [sym_unmap] = STATE(65),
+    [sym_iunmap] = STATE(65),
+    [sym_cunmap] = STATE(65),
+    [ts_builtin_sym_end] = ACTIONS(133),
+    [sym_newline] = ACTIONS(3),
+    [anon_sym_set] = ACTIONS(11),
+    [anon_sym_se] = ACTIONS(11),
+    [anon_sym_setlocal] = ACTIONS(13),
+    [anon_sym_setl] = ACTIONS(15),
+    [anon_sym_setglobal] = ACTIONS(17),
+    [anon_sym_setg] = ACTIONS(19),
+    [anon_sym_edit] = ACTIONS(21),
+    [anon_sym_e] = ACTIONS(23),
+    [anon_sym_buffer] = ACTIONS(25),
+    [anon_sym_b] = ACTIONS(25),
+    [anon_sym_bnext] = ACTIONS(27),
+    [anon_sym_bn] = ACTIONS(29),
+    [anon_sym_bprevious] = ACTIONS(31),
+    [anon_sym_bp] = ACTIONS(33),
+    [anon_sym_bNext] = ACTIONS(31),
+    [anon_sym_bN] = ACTIONS(33),
+    [anon_sym_bdelete] = ACTIONS(35),
+    [anon_sym_bd] = ACTIONS(37),
+    [anon_sym_buffers] = ACTIONS(39),
+    [anon_sym_ls] = ACTIONS(39),
+    [anon_sym_files] = ACTIONS(39),
+    [sym_finder] = ACTIONS(41),
+    [anon_sym_write] = ACTIONS(43),
+    [anon_sym_w] = ACTIONS(45),
+    [anon_sym_update] = ACTIONS(47),
+    [anon_sym_up] = ACTIONS(49),
+    [anon_sym_saveas] = ACTIONS(51),
+    [anon_sym_sav] = ACTIONS(53),
+    [anon_sym_wall] = ACTIONS(55),
+    [anon_sym_wa] = ACTIONS(57),
+    [anon_sym_read] = ACTIONS(59),
+    [anon_sym_r] = ACTIONS(61),
+    [anon_sym_substitute] = ACTIONS(63),
+    [anon_sym_s] = ACTIONS(65),
+    [anon_sym_quit] = ACTIONS(67),
+    [anon_sym_q] = ACTIONS(67),
+    [anon_sym_wq] = ACTIONS(69),
+    [anon_sym_xit] = ACTIONS(71),
+    [anon_sym_x] = ACTIONS(73),
+    [anon_sym_exit] = ACTIONS(71),
+    [anon_sym_exi] = ACTIONS(73),
+    [anon_sym_qall] = ACTIONS(75),
+    [anon_sym_qa] = ACTIONS(77),
+    [anon_sym_quitall] = ACTIONS(75),
+    [anon_sym_quita] = ACTIONS(77),
+    [anon_sym_wqall] = ACTIONS(79),
+    [anon_sym_wqa] = ACTIONS(81),
+    [anon_sym_xall] = ACTIONS(79),
+    [anon_sym_xa] = ACTIONS(81),
+    [anon_sym_mksession] = ACTIONS(83),
+    [anon_sym_mks] = ACTIONS(85),
+    [anon_sym_mkdir] = ACTIONS(87),
+    [anon_sym_mkfile] = ACTIONS(89),
+    [anon_sym_rename] = ACTIONS(91),
+    [anon_sym_ren] = ACTIONS(93),
+    [anon_sym_remove] = ACTIONS(95),
+    [anon_sym_rm] = ACTIONS(95),
+    [anon_sym_nmap] = ACTIONS(97),
+    [anon_sym_nm] = ACTIONS(99),
+    [anon_sym_map] = ACTIONS(97),
+    [anon_sym_imap] = ACTIONS(101),
+    [anon_sym_im] = ACTIONS(103),
+    [anon_sym_cmap] = ACTIONS(105),
+    [anon_sym_cm] = ACTIONS(107),
+    [anon_sym_noremap] = ACTIONS(109),
+    [anon_sym_no] = ACTIONS(111),
+    [anon_sym_nnoremap] = ACTIONS(109),
+    [anon_sym_nn] = ACTIONS(111),
+    [anon_sym_inoremap] = ACTIONS(113),
+    [anon_sym_ino] = ACTIONS(115),
+    [anon_sym_cnoremap] = ACTIONS(117),
+    [anon_sym_cno] = ACTIONS(119),
+    [anon_sym_unmap] = ACTIONS(121),
+    [anon_sym_unm] = ACTIONS(123),
+    [anon_sym_nunmap] = ACTIONS(121),
+    [anon_sym_nun] = ACTIONS(123),
+    [anon_sym_iunmap] = ACTIONS(125),
+    [anon_sym_iu] = ACTIONS(127),
+    [anon_sym_cunmap] = ACTIONS(129),
+    [anon_sym_cu] = ACTIONS(131),
+  },
+  [4] = {
+    [ts_builtin_sym_end] = ACTIONS(135),
+    [sym_newline] = ACTIONS(3),
+    [anon_sym_COMMA] = ACTIONS(137),
+    [anon_sym_SEMI] = ACTIONS(137),
+    [anon_sym_set] = ACTIONS(139),
+    [anon_sym_se] = ACTIONS(139),
+    [anon_sym_setlocal] = ACTIONS(135),
+    [anon_sym_setl] = ACTIONS(139),
+    [anon_sym_setglobal] = ACTIONS(135),
+    [anon_sym_setg] = ACTIONS(139),
+    [anon_sym_edit] = ACTIONS(135),
+    [anon_sym_e] = ACTIONS(139),
+    [anon_sym_buffer] = ACTIONS(139),
+    [anon_sym_b] = ACTIONS(139),
+    [anon_sym_bnext] = ACTIONS(135),
+    [anon_sym_bn] = ACTIONS(139),
+    [anon_sym_bprevious] = ACTIONS(135),
+    [anon_sym_bp] = ACTIONS(139),
+    [anon_sym_bNext] = ACTIONS(135),
+    [anon_sym_bN] = ACTIONS(139),
+    [anon_sym_bdelete] = ACTIONS(135),
+    [anon_sym_bd] = ACTIONS(139),
+    [anon_sym_buffers] = ACTIONS(135),
+    [anon_sym_ls] = ACTIONS(135),
+    [anon_sym_files] = ACTIONS(135),
+    [sym_finder] = ACTIONS(135),
+    [anon_sym_write] = ACTIONS(135),
+    [anon_sym_w] = ACTIONS(139),
+    [anon_sym_update] = ACTIONS(135),
+    [anon_sym_up] = ACTIONS(139),
+    [anon_sym_saveas] = ACTIONS(135),
+    [anon_sym_sav] = ACTIONS(139),
+    [anon_sym_wall] = ACTIONS(135),
+    [anon_sym_wa] = ACTIONS(139),
+    [anon_sym_read] = ACTIONS(135),
+    [anon_sym_r] = ACTIONS(139),
+    [anon_sym_substitute] = ACTIONS(135),
+    [anon_sym_s] = ACTIONS(139),
+    [anon_sym_quit] = ACTIONS(139),
+    [anon_sym_q] = ACTIONS(139),
+    [anon_sym_wq] = ACTIONS(139),
+    [anon_sym_xit] = ACTIONS(135),
+    [anon_sym_x] = ACTIONS(139),
+    [anon_sym_exit] = ACTIONS(135),
+    [anon_sym_exi] = ACTIONS(139),
+    [anon_sym_qall] = ACTIONS(135),
+    [anon_sym_qa] = ACTIONS(139),
+    [anon_sym_quitall] = ACTIONS(135),
+    [anon_sym_quita] = ACTIONS(139),
+    [anon_sym_wqall] = ACTIONS(135),
+    [anon_sym_wqa] = ACTIONS(139),
+    [anon_sym_xall] = ACTIONS(135),
+    [anon_sym_xa] = ACTIONS(139),
+    [anon_sym_mksession] = ACTIONS(135),
+    [anon_sym_mks] = ACTIONS(139),
+    [anon_sym_mkdir] = ACTIONS(135),
+    [anon_sym_mkfile] = ACTIONS(135),
+    [anon_sym_rename] = ACTIONS(135),
+    [anon_sym_ren] = ACTIONS(139),
+    [anon_sym_remove] = ACTIONS(135),
+    [anon_sym_rm] = ACTIONS(135),
+    [anon_sym_nmap] = ACTIONS(135),
+    [anon_sym_nm] = ACTIONS(139),
+    [anon_sym_map] = ACTIONS(135),
+    [anon_sym_imap] = ACTIONS(135),
+    [anon_sym_im] = ACTIONS(139),
+    [anon_sym_cmap] = ACTIONS(135),
+    [anon_sym_cm] = ACTIONS(139),
+    [anon_sym_noremap] = ACTIONS(135),
+    [anon_sym_no] = ACTIONS(139),
+    [anon_sym_nnoremap] = ACTIONS(135),
+    [anon_sym_nn] = ACTIONS(139),
+    [anon_sym_inoremap] = ACTIONS(135),
+    [anon_sym_ino] = ACTIONS(139),
+    [anon_sym_cnoremap] = ACTIONS(135),
+    [anon_sym_cno] = ACTIONS(139),
+    [anon_sym_unmap] = ACTIONS(135),
+    [anon_sym_unm] = ACTIONS(139),
+    [anon_sym_nunmap] = ACTIONS(135),
+    [anon_sym_nun] = ACTIONS(139),
+    [anon_sym_iunmap] = ACTIONS(135),
+    [anon_sym_iu] = ACTIONS(139),
+    [anon_sym_cunmap] = ACTIONS(135),
+    [anon_sym_cu] = ACTIONS(139),
+  },
+  [5] = {
+    [ts_builtin_sym_end] = ACTIONS(141),
+    [sym_newline] = ACTIONS(3),
+    [anon_sym_set] = ACTIONS(143),
+    [anon_sym_se] = ACTIONS(143),
+    [anon_sym_setlocal] = ACTIONS(141),
+    [anon_sym_setl] = ACTIONS(143),
+    [anon_sym_setglobal] = ACTIONS(141),
+    [anon_sym_setg] = ACTIONS(143),
+    [anon_sym_edit] = ACTIONS(141),
+    [anon_sym_e] = ACTIONS(143),
+    [anon_sym_buffer] = ACTIONS(143),
+    [anon_sym_b] = ACTIONS(143),
+    [anon_sym_bnext] = ACTIONS(141),
+    [anon_sym_bn] = ACTIONS(143),
+    [anon_sym_bprevious] = ACTIONS(141),
+    [anon_sym_bp] = ACTIONS(143),
+    [anon_sym_bNext] = ACTIONS(141),
+    [anon_sym_bN] = ACTIONS(143),
+    [anon_sym_bdelete] = ACTIONS(141),
+    [anon_sym_bd] = ACTIONS(143),
+    [anon_sym_buffers] = ACTIONS(141),
+    [anon_sym_ls] = ACTIONS(141),
+    [anon_sym_files] = ACTIONS(141),
+    [sym_finder] = ACTIONS(141),
+    [anon_sym_write] = ACTIONS(141),
+    [anon_sym_w] = ACTIONS(143),
+    [anon_sym_update] = ACTIONS(141),
+    [anon_sym_up] = ACTIONS(143),
+    [anon_sym_saveas] = ACTIONS(141),
+    [anon_sym_sav] = ACTIONS(143),
+    [anon_sym_wall] = ACTIONS(141),
+    [anon_sym_wa] = ACTIONS(143),
+    [anon_sym_read] = ACTIONS(141),
+    [anon_sym_r] = ACTIONS(143),
+    [anon_sym_substitute] = ACTIONS(141),
+    [anon_sym_s] = ACTIONS(143),
+    [anon_sym_quit] = ACTIONS(143),
+    [anon_sym_q] = ACTIONS(143),
+    [anon_sym_wq] = ACTIONS(143),
+    [anon_sym_xit] = ACTIONS(141),
+    [anon_sym_x] = ACTIONS(143),
+    [anon_sym_exit] = ACTIONS(141),
+    [anon_sym_exi] = ACTIONS(143),
+    [anon_sym_qall] = ACTIONS(141),
+    [anon_sym_qa] = ACTIONS(143),
+    [anon_sym_quitall] = ACTIONS(141),
+    [anon_sym_quita] = ACTIONS(143),
+    [anon_sym_wqall] = ACTIONS(141),
+    [anon_sym_wqa] = ACTIONS(143),
+    [anon_sym_xall] = ACTIONS(141),
+    [anon_sym_xa] = ACTIONS(143),
+    [anon_sym_mksession] = ACTIONS(141),
+    [anon_sym_mks] = ACTIONS(143),
+    [anon_sym_mkdir] = ACTIONS(141),
+    [anon_sym_mkfile] = ACTIONS(141),
+    [anon_sym_rename] = ACTIONS(141),
+    [anon_sym_ren] = ACTIONS(143),
+    [anon_sym_remove] = ACTIONS(141),
+    [anon_sym_rm] = ACTIONS(141),
+    [anon_sym_nmap] = ACTIONS(141),
+    [anon_sym_nm] = ACTIONS(143),
+    [anon_sym_map] = ACTIONS(141),
+    [anon_sym_imap] = ACTIONS(141),
+    [anon_sym_im] = ACTIONS(143),
+    [anon_sym_cmap] = ACTIONS(141),
+    [anon_sym_cm] = ACTIONS(143),
+    [anon_sym_noremap] = ACTIONS(141),
+    [anon_sym_no] = ACTIONS(143),
+    [anon_sym_nnoremap] = ACTIONS(141),
+    [anon_sym_nn] = ACTIONS(143),
+    [anon_sym_inoremap] = ACTIONS(141),
+    [anon_sym_ino] = ACTIONS(143),
+    [anon_sym_cnoremap] = ACTIONS(141),
+    [anon_sym_cno] = ACTIONS(143),
+    [anon_sym_unmap] = ACTIONS(141),
+    [anon_sym_unm] = ACTIONS(143),
+    [anon_sym_nunmap] = ACTIONS(141),
+    [anon_sym_nun] = ACTIONS(143),
+    [anon_sym_iunmap] = ACTIONS(141),
+    [anon_sym_iu] = ACTIONS(143),
+    [anon_sym_cunmap] = ACTIONS(141),
+    [anon_sym_cu] = ACTIONS(143),
   },
 };
 
 static uint16_t ts_small_parse_table[] = {
-  [0] = 8,
+  [0] = 5,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(145), 1,
+      ts_builtin_sym_end,
+    ACTIONS(147), 1,
+      sym_bang,
+    ACTIONS(149), 1,
+      sym_append,
+    ACTIONS(151), 1,
+      sym_file_name,
+  [16] = 5,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(153), 1,
+      ts_builtin_sym_end,
+    ACTIONS(155), 1,
+      sym_bang,
+    ACTIONS(157), 1,
+      sym_append,
+    ACTIONS(159), 1,
+      sym_file_name,
+  [32] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(161), 1,
+      ts_builtin_sym_end,
+    ACTIONS(163), 1,
+      sym_set_arg,
+    STATE(15), 1,
+      aux_sym_set_repeat1,
+  [45] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(165), 1,
+      ts_builtin_sym_end,
+    ACTIONS(167), 1,
+      sym_set_arg,
+    STATE(28), 1,
+      aux_sym_set_repeat1,
+  [58] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(169), 1,
+      ts_builtin_sym_end,
+    ACTIONS(171), 1,
+      sym_bang,
+    ACTIONS(173), 1,
+      sym_file_name,
+  [71] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(175), 1,
+      ts_builtin_sym_end,
+    ACTIONS(177), 1,
+      sym_append,
+    ACTIONS(179), 1,
+      sym_file_name,
+  [84] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(181), 1,
+      ts_builtin_sym_end,
+    ACTIONS(183), 1,
+      sym_append,
+    ACTIONS(185), 1,
+      sym_file_name,
+  [97] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(187), 1,
+      ts_builtin_sym_end,
+    ACTIONS(189), 1,
+      sym_bang,
+    ACTIONS(191), 1,
+      sym_buffer_name,
+  [110] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(163), 1,
+      sym_set_arg,
+    ACTIONS(193), 1,
+      ts_builtin_sym_end,
+    STATE(15), 1,
+      aux_sym_set_repeat1,
+  [123] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(195), 1,
+      ts_builtin_sym_end,
+    ACTIONS(197), 1,
+      sym_set_arg,
+    STATE(15), 1,
+      aux_sym_set_repeat1,
+  [136] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(200), 1,
+      ts_builtin_sym_end,
+    ACTIONS(202), 1,
+      sym_set_arg,
+    STATE(8), 1,
+      aux_sym_set_repeat1,
+  [149] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(204), 1,
+      ts_builtin_sym_end,
+    ACTIONS(206), 1,
+      sym_map_lhs,
+    STATE(78), 1,
+      sym__map_args,
+  [162] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(206), 1,
+      sym_map_lhs,
+    ACTIONS(208), 1,
+      ts_builtin_sym_end,
+    STATE(79), 1,
+      sym__map_args,
+  [175] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(210), 1,
+      ts_builtin_sym_end,
+    ACTIONS(212), 1,
+      sym_bang,
+    ACTIONS(214), 1,
+      sym_file_name,
+  [188] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(206), 1,
+      sym_map_lhs,
+    ACTIONS(216), 1,
+      ts_builtin_sym_end,
+    STATE(80), 1,
+      sym__map_args,
+  [201] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(206), 1,
+      sym_map_lhs,
+    ACTIONS(218), 1,
+      ts_builtin_sym_end,
+    STATE(81), 1,
+      sym__map_args,
+  [214] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(220), 1,
+      ts_builtin_sym_end,
+    ACTIONS(222), 1,
+      sym_bang,
+    ACTIONS(224), 1,
+      sym_file_name,
+  [227] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(226), 1,
+      ts_builtin_sym_end,
+    ACTIONS(228), 1,
+      sym_bang,
+    ACTIONS(230), 1,
+      sym_file_name,
+  [240] = 4,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(206), 1,
+      sym_map_lhs,
+    ACTIONS(232), 1,
+      ts_builtin_sym_end,
+    STATE(82), 1,
+      sym__map_args,
+  [253] = 4,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(11), 1,
-      anon_sym_set,
-    ACTIONS(13), 1,
-      anon_sym_edit,
-    ACTIONS(15), 1,
-      anon_sym_buffer,
-    ACTIONS(17), 1,
-      anon_sym_buffers,
-    ACTIONS(19), 1,
+    ACTIONS(206), 1,
+      sym_map_lhs,
+    ACTIONS(234), 1,
       ts_builtin_sym_end,
-    STATE(18), 1,
-      sym_cmd,
-    STATE(12), 4,
-      sym_set,
-      sym_edit,
-      sym_buffer,
-      sym_buffers,
-  [28] = 4,
+    STATE(83), 1,
+      sym__map_args,
+  [266] = 4,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(23), 1,
-      anon_sym_COMMA,
-    ACTIONS(25), 1,
-      anon_sym_buffer,
-    ACTIONS(21), 4,
+    ACTIONS(236), 1,
       ts_builtin_sym_end,
-      anon_sym_set,
-      anon_sym_edit,
-      anon_sym_buffers,
-  [44] = 3,
+    ACTIONS(238), 1,
+      sym_bang,
+    ACTIONS(240), 1,
+      sym_file_name,
+  [279] = 4,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(29), 1,
-      anon_sym_buffer,
-    ACTIONS(27), 4,
+    ACTIONS(242), 1,
       ts_builtin_sym_end,
-      anon_sym_set,
-      anon_sym_edit,
-      anon_sym_buffers,
-  [57] = 3,
+    ACTIONS(244), 1,
+      sym_set_arg,
+    STATE(14), 1,
+      aux_sym_set_repeat1,
+  [292] = 4,
     ACTIONS(3), 1,
       sym_newline,
+    ACTIONS(163), 1,
+      sym_set_arg,
+    ACTIONS(246), 1,
+      ts_builtin_sym_end,
     STATE(15), 1,
-      sym_config_param,
-    ACTIONS(31), 2,
-      anon_sym_wrap,
-      anon_sym_nowrap,
-  [68] = 2,
+      aux_sym_set_repeat1,
+  [305] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(248), 1,
+      ts_builtin_sym_end,
+    ACTIONS(250), 1,
+      sym_buffer_name,
+  [315] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(252), 1,
+      ts_builtin_sym_end,
+    ACTIONS(254), 1,
+      sym_count,
+  [325] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(256), 1,
+      sym_bang,
+    ACTIONS(258), 1,
+      sym_file_name,
+  [335] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(260), 1,
+      ts_builtin_sym_end,
+    ACTIONS(262), 1,
+      sym_bang,
+  [345] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(264), 1,
+      ts_builtin_sym_end,
+    ACTIONS(266), 1,
+      sym_file_name,
+  [355] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(268), 1,
+      ts_builtin_sym_end,
+    ACTIONS(270), 1,
+      sym_bang,
+  [365] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(272), 1,
+      ts_builtin_sym_end,
+    ACTIONS(274), 1,
+      sym_bang,
+  [375] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(276), 1,
+      ts_builtin_sym_end,
+    ACTIONS(278), 1,
+      sym_bang,
+  [385] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(280), 1,
+      ts_builtin_sym_end,
+    ACTIONS(282), 1,
+      sym_file_name,
+  [395] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(284), 1,
+      ts_builtin_sym_end,
+    ACTIONS(286), 1,
+      sym_buffer_name,
+  [405] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(288), 1,
+      ts_builtin_sym_end,
+    ACTIONS(290), 1,
+      sym_count,
+  [415] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(292), 1,
+      ts_builtin_sym_end,
+    ACTIONS(294), 1,
+      sym_map_rhs,
+  [425] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(296), 1,
+      ts_builtin_sym_end,
+    ACTIONS(298), 1,
+      sym_file_name,
+  [435] = 3,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(300), 1,
+      ts_builtin_sym_end,
+    ACTIONS(302), 1,
+      sym_file_name,
+  [445] = 3,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(33), 1,
+    ACTIONS(304), 1,
       ts_builtin_sym_end,
-  [75] = 2,
-    ACTIONS(35), 1,
+    ACTIONS(306), 1,
+      sym_file_name,
+  [455] = 3,
+    ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(37), 1,
-      aux_sym_edit_token1,
-  [82] = 2,
+    ACTIONS(181), 1,
+      ts_builtin_sym_end,
+    ACTIONS(185), 1,
+      sym_file_name,
+  [465] = 3,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(39), 1,
-      aux_sym_buffer_token1,
-  [89] = 2,
+    ACTIONS(175), 1,
+      ts_builtin_sym_end,
+    ACTIONS(179), 1,
+      sym_file_name,
+  [475] = 3,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(41), 1,
+    ACTIONS(308), 1,
       ts_builtin_sym_end,
-  [96] = 2,
+    ACTIONS(310), 1,
+      sym_file_name,
+  [485] = 2,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(19), 1,
+    ACTIONS(312), 1,
       ts_builtin_sym_end,
-  [103] = 2,
+  [492] = 2,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(43), 1,
+    ACTIONS(314), 1,
       ts_builtin_sym_end,
-  [110] = 2,
+  [499] = 2,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(45), 1,
+    ACTIONS(316), 1,
       sym_range_end,
-  [117] = 2,
+  [506] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(308), 1,
+      ts_builtin_sym_end,
+  [513] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(318), 1,
+      ts_builtin_sym_end,
+  [520] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(320), 1,
+      ts_builtin_sym_end,
+  [527] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(322), 1,
+      ts_builtin_sym_end,
+  [534] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(324), 1,
+      ts_builtin_sym_end,
+  [541] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(248), 1,
+      ts_builtin_sym_end,
+  [548] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(326), 1,
+      ts_builtin_sym_end,
+  [555] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(181), 1,
+      ts_builtin_sym_end,
+  [562] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(175), 1,
+      ts_builtin_sym_end,
+  [569] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(328), 1,
+      sym_file_name,
+  [576] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(330), 1,
+      ts_builtin_sym_end,
+  [583] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(332), 1,
+      ts_builtin_sym_end,
+  [590] = 2,
+    ACTIONS(334), 1,
+      sym_newline,
+    ACTIONS(336), 1,
+      sym_shell_cmd,
+  [597] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(338), 1,
+      ts_builtin_sym_end,
+  [604] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(340), 1,
+      ts_builtin_sym_end,
+  [611] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(342), 1,
+      ts_builtin_sym_end,
+  [618] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(344), 1,
+      sym_subst_args,
+  [625] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(304), 1,
+      ts_builtin_sym_end,
+  [632] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(346), 1,
+      sym_map_lhs,
+  [639] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(300), 1,
+      ts_builtin_sym_end,
+  [646] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(348), 1,
+      ts_builtin_sym_end,
+  [653] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(350), 1,
+      ts_builtin_sym_end,
+  [660] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(352), 1,
+      sym_map_lhs,
+  [667] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(296), 1,
+      ts_builtin_sym_end,
+  [674] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(354), 1,
+      ts_builtin_sym_end,
+  [681] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(356), 1,
+      ts_builtin_sym_end,
+  [688] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(133), 1,
+      ts_builtin_sym_end,
+  [695] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(358), 1,
+      sym_map_lhs,
+  [702] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(360), 1,
+      ts_builtin_sym_end,
+  [709] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(362), 1,
+      ts_builtin_sym_end,
+  [716] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(364), 1,
+      ts_builtin_sym_end,
+  [723] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(366), 1,
+      ts_builtin_sym_end,
+  [730] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(368), 1,
+      ts_builtin_sym_end,
+  [737] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(370), 1,
+      ts_builtin_sym_end,
+  [744] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(372), 1,
+      ts_builtin_sym_end,
+  [751] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(374), 1,
+      ts_builtin_sym_end,
+  [758] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(376), 1,
+      ts_builtin_sym_end,
+  [765] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(378), 1,
+      ts_builtin_sym_end,
+  [772] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(380), 1,
+      ts_builtin_sym_end,
+  [779] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(382), 1,
+      sym_file_name,
+  [786] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(384), 1,
+      ts_builtin_sym_end,
+  [793] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(386), 1,
+      ts_builtin_sym_end,
+  [800] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(388), 1,
+      sym_file_name,
+  [807] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(280), 1,
+      ts_builtin_sym_end,
+  [814] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(390), 1,
+      sym_file_name,
+  [821] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(264), 1,
+      ts_builtin_sym_end,
+  [828] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(392), 1,
+      ts_builtin_sym_end,
+  [835] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(394), 1,
+      ts_builtin_sym_end,
+  [842] = 2,
+    ACTIONS(3), 1,
+      sym_newline,
+    ACTIONS(396), 1,
+      ts_builtin_sym_end,
+  [849] = 2,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(47), 1,
+    ACTIONS(398), 1,
       ts_builtin_sym_end,
-  [124] = 2,
+  [856] = 2,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(49), 1,
+    ACTIONS(400), 1,
       ts_builtin_sym_end,
-  [131] = 2,
+  [863] = 2,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(51), 1,
+    ACTIONS(402), 1,
       ts_builtin_sym_end,
-  [138] = 2,
+  [870] = 2,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(53), 1,
+    ACTIONS(404), 1,
       ts_builtin_sym_end,
-  [145] = 2,
+  [877] = 2,
     ACTIONS(3), 1,
       sym_newline,
-    ACTIONS(55), 1,
+    ACTIONS(406), 1,
       ts_builtin_sym_end,
 };
 
 static uint32_t ts_small_parse_table_map[] = {
-  [SMALL_STATE(3)] = 0,
-  [SMALL_STATE(4)] = 28,
-  [SMALL_STATE(5)] = 44,
-  [SMALL_STATE(6)] = 57,
-  [SMALL_STATE(7)] = 68,
-  [SMALL_STATE(8)] = 75,
-  [SMALL_STATE(9)] = 82,
-  [SMALL_STATE(10)] = 89,
-  [SMALL_STATE(11)] = 96,
-  [SMALL_STATE(12)] = 103,
-  [SMALL_STATE(13)] = 110,
-  [SMALL_STATE(14)] = 117,
-  [SMALL_STATE(15)] = 124,
-  [SMALL_STATE(16)] = 131,
-  [SMALL_STATE(17)] = 138,
-  [SMALL_STATE(18)] = 145,
+  [SMALL_STATE(6)] = 0,
+  [SMALL_STATE(7)] = 16,
+  [SMALL_STATE(8)] = 32,
+  [SMALL_STATE(9)] = 45,
+  [SMALL_STATE(10)] = 58,
+  [SMALL_STATE(11)] = 71,
+  [SMALL_STATE(12)] = 84,
+  [SMALL_STATE(13)] = 97,
+  [SMALL_STATE(14)] = 110,
+  [SMALL_STATE(15)] = 123,
+  [SMALL_STATE(16)] = 136,
+  [SMALL_STATE(17)] = 149,
+  [SMALL_STATE(18)] = 162,
+  [SMALL_STATE(19)] = 175,
+  [SMALL_STATE(20)] = 188,
+  [SMALL_STATE(21)] = 201,
+  [SMALL_STATE(22)] = 214,
+  [SMALL_STATE(23)] = 227,
+  [SMALL_STATE(24)] = 240,
+  [SMALL_STATE(25)] = 253,
+  [SMALL_STATE(26)] = 266,
+  [SMALL_STATE(27)] = 279,
+  [SMALL_STATE(28)] = 292,
+  [SMALL_STATE(29)] = 305,
+  [SMALL_STATE(30)] = 315,
+  [SMALL_STATE(31)] = 325,
+  [SMALL_STATE(32)] = 335,
+  [SMALL_STATE(33)] = 345,
+  [SMALL_STATE(34)] = 355,
+  [SMALL_STATE(35)] = 365,
+  [SMALL_STATE(36)] = 375,
+  [SMALL_STATE(37)] = 385,
+  [SMALL_STATE(38)] = 395,
+  [SMALL_STATE(39)] = 405,
+  [SMALL_STATE(40)] = 415,
+  [SMALL_STATE(41)] = 425,
+  [SMALL_STATE(42)] = 435,
+  [SMALL_STATE(43)] = 445,
+  [SMALL_STATE(44)] = 455,
+  [SMALL_STATE(45)] = 465,
+  [SMALL_STATE(46)] = 475,
+  [SMALL_STATE(47)] = 485,
+  [SMALL_STATE(48)] = 492,
+  [SMALL_STATE(49)] = 499,
+  [SMALL_STATE(50)] = 506,
+  [SMALL_STATE(51)] = 513,
+  [SMALL_STATE(52)] = 520,
+  [SMALL_STATE(53)] = 527,
+  [SMALL_STATE(54)] = 534,
+  [SMALL_STATE(55)] = 541,
+  [SMALL_STATE(56)] = 548,
+  [SMALL_STATE(57)] = 555,
+  [SMALL_STATE(58)] = 562,
+  [SMALL_STATE(59)] = 569,
+  [SMALL_STATE(60)] = 576,
+  [SMALL_STATE(61)] = 583,
+  [SMALL_STATE(62)] = 590,
+  [SMALL_STATE(63)] = 597,
+  [SMALL_STATE(64)] = 604,
+  [SMALL_STATE(65)] = 611,
+  [SMALL_STATE(66)] = 618,
+  [SMALL_STATE(67)] = 625,
+  [SMALL_STATE(68)] = 632,
+  [SMALL_STATE(69)] = 639,
+  [SMALL_STATE(70)] = 646,
+  [SMALL_STATE(71)] = 653,
+  [SMALL_STATE(72)] = 660,
+  [SMALL_STATE(73)] = 667,
+  [SMALL_STATE(74)] = 674,
+  [SMALL_STATE(75)] = 681,
+  [SMALL_STATE(76)] = 688,
+  [SMALL_STATE(77)] = 695,
+  [SMALL_STATE(78)] = 702,
+  [SMALL_STATE(79)] = 709,
+  [SMALL_STATE(80)] = 716,
+  [SMALL_STATE(81)] = 723,
+  [SMALL_STATE(82)] = 730,
+  [SMALL_STATE(83)] = 737,
+  [SMALL_STATE(84)] = 744,
+  [SMALL_STATE(85)] = 751,
+  [SMALL_STATE(86)] = 758,
+  [SMALL_STATE(87)] = 765,
+  [SMALL_STATE(88)] = 772,
+  [SMALL_STATE(89)] = 779,
+  [SMALL_STATE(90)] = 786,
+  [SMALL_STATE(91)] = 793,
+  [SMALL_STATE(92)] = 800,
+  [SMALL_STATE(93)] = 807,
+  [SMALL_STATE(94)] = 814,
+  [SMALL_STATE(95)] = 821,
+  [SMALL_STATE(96)] = 828,
+  [SMALL_STATE(97)] = 835,
+  [SMALL_STATE(98)] = 842,
+  [SMALL_STATE(99)] = 849,
+  [SMALL_STATE(100)] = 856,
+  [SMALL_STATE(101)] = 863,
+  [SMALL_STATE(102)] = 870,
+  [SMALL_STATE(103)] = 877,
 };
 
 static TSParseActionEntry ts_parse_actions[] = {
@@ -635,29 +3301,204 @@ static TSParseActionEntry ts_parse_actions[] = {
   [5] = {.entry = {.count = 1, .reusable = true}}, SHIFT(2),
   [7] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_s, 1),
   [9] = {.entry = {.count = 1, .reusable = true}}, SHIFT(4),
-  [11] = {.entry = {.count = 1, .reusable = true}}, SHIFT(6),
-  [13] = {.entry = {.count = 1, .reusable = true}}, SHIFT(8),
-  [15] = {.entry = {.count = 1, .reusable = false}}, SHIFT(9),
-  [17] = {.entry = {.count = 1, .reusable = true}}, SHIFT(10),
-  [19] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_s, 2),
-  [21] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_range, 1),
-  [23] = {.entry = {.count = 1, .reusable = true}}, SHIFT(13),
-  [25] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_range, 1),
-  [27] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_range, 3),
-  [29] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_range, 3),
-  [31] = {.entry = {.count = 1, .reusable = true}}, SHIFT(14),
-  [33] = {.entry = {.count = 1, .reusable = true}},  ACCEPT_INPUT(),
-  [35] = {.entry = {.count = 1, .reusable = false}}, SHIFT_EXTRA(),
-  [37] = {.entry = {.count = 1, .reusable = false}}, SHIFT(16),
-  [39] = {.entry = {.count = 1, .reusable = true}}, SHIFT(17),
-  [41] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_buffers, 1),
-  [43] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cmd, 1),
-  [45] = {.entry = {.count = 1, .reusable = true}}, SHIFT(5),
-  [47] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_config_param, 1),
-  [49] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_set, 2),
-  [51] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_edit, 2),
-  [53] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_buffer, 2),
-  [55] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_s, 3),
+  [11] = {.entry = {.count = 1, .reusable = false}}, SHIFT(27),
+  [13] = {.entry = {.count = 1, .reusable = true}}, SHIFT(16),
+  [15] = {.entry = {.count = 1, .reusable = false}}, SHIFT(16),
+  [17] = {.entry = {.count = 1, .reusable = true}}, SHIFT(9),
+  [19] = {.entry = {.count = 1, .reusable = false}}, SHIFT(9),
+  [21] = {.entry = {.count = 1, .reusable = true}}, SHIFT(10),
+  [23] = {.entry = {.count = 1, .reusable = false}}, SHIFT(10),
+  [25] = {.entry = {.count = 1, .reusable = false}}, SHIFT(38),
+  [27] = {.entry = {.count = 1, .reusable = true}}, SHIFT(39),
+  [29] = {.entry = {.count = 1, .reusable = false}}, SHIFT(39),
+  [31] = {.entry = {.count = 1, .reusable = true}}, SHIFT(30),
+  [33] = {.entry = {.count = 1, .reusable = false}}, SHIFT(30),
+  [35] = {.entry = {.count = 1, .reusable = true}}, SHIFT(13),
+  [37] = {.entry = {.count = 1, .reusable = false}}, SHIFT(13),
+  [39] = {.entry = {.count = 1, .reusable = true}}, SHIFT(56),
+  [41] = {.entry = {.count = 1, .reusable = true}}, SHIFT(65),
+  [43] = {.entry = {.count = 1, .reusable = true}}, SHIFT(7),
+  [45] = {.entry = {.count = 1, .reusable = false}}, SHIFT(7),
+  [47] = {.entry = {.count = 1, .reusable = true}}, SHIFT(6),
+  [49] = {.entry = {.count = 1, .reusable = false}}, SHIFT(6),
+  [51] = {.entry = {.count = 1, .reusable = true}}, SHIFT(31),
+  [53] = {.entry = {.count = 1, .reusable = false}}, SHIFT(31),
+  [55] = {.entry = {.count = 1, .reusable = true}}, SHIFT(32),
+  [57] = {.entry = {.count = 1, .reusable = false}}, SHIFT(32),
+  [59] = {.entry = {.count = 1, .reusable = true}}, SHIFT(19),
+  [61] = {.entry = {.count = 1, .reusable = false}}, SHIFT(19),
+  [63] = {.entry = {.count = 1, .reusable = true}}, SHIFT(66),
+  [65] = {.entry = {.count = 1, .reusable = false}}, SHIFT(66),
+  [67] = {.entry = {.count = 1, .reusable = false}}, SHIFT(34),
+  [69] = {.entry = {.count = 1, .reusable = false}}, SHIFT(22),
+  [71] = {.entry = {.count = 1, .reusable = true}}, SHIFT(23),
+  [73] = {.entry = {.count = 1, .reusable = false}}, SHIFT(23),
+  [75] = {.entry = {.count = 1, .reusable = true}}, SHIFT(35),
+  [77] = {.entry = {.count = 1, .reusable = false}}, SHIFT(35),
+  [79] = {.entry = {.count = 1, .reusable = true}}, SHIFT(36),
+  [81] = {.entry = {.count = 1, .reusable = false}}, SHIFT(36),
+  [83] = {.entry = {.count = 1, .reusable = true}}, SHIFT(26),
+  [85] = {.entry = {.count = 1, .reusable = false}}, SHIFT(26),
+  [87] = {.entry = {.count = 1, .reusable = true}}, SHIFT(94),
+  [89] = {.entry = {.count = 1, .reusable = true}}, SHIFT(92),
+  [91] = {.entry = {.count = 1, .reusable = true}}, SHIFT(89),
+  [93] = {.entry = {.count = 1, .reusable = false}}, SHIFT(89),
+  [95] = {.entry = {.count = 1, .reusable = true}}, SHIFT(88),
+  [97] = {.entry = {.count = 1, .reusable = true}}, SHIFT(17),
+  [99] = {.entry = {.count = 1, .reusable = false}}, SHIFT(17),
+  [101] = {.entry = {.count = 1, .reusable = true}}, SHIFT(18),
+  [103] = {.entry = {.count = 1, .reusable = false}}, SHIFT(18),
+  [105] = {.entry = {.count = 1, .reusable = true}}, SHIFT(20),
+  [107] = {.entry = {.count = 1, .reusable = false}}, SHIFT(20),
+  [109] = {.entry = {.count = 1, .reusable = true}}, SHIFT(21),
+  [111] = {.entry = {.count = 1, .reusable = false}}, SHIFT(21),
+  [113] = {.entry = {.count = 1, .reusable = true}}, SHIFT(24),
+  [115] = {.entry = {.count = 1, .reusable = false}}, SHIFT(24),
+  [117] = {.entry = {.count = 1, .reusable = true}}, SHIFT(25),
+  [119] = {.entry = {.count = 1, .reusable = false}}, SHIFT(25),
+  [121] = {.entry = {.count = 1, .reusable = true}}, SHIFT(77),
+  [123] = {.entry = {.count = 1, .reusable = false}}, SHIFT(77),
+  [125] = {.entry = {.count = 1, .reusable = true}}, SHIFT(72),
+  [127] = {.entry = {.count = 1, .reusable = false}}, SHIFT(72),
+  [129] = {.entry = {.count = 1, .reusable = true}}, SHIFT(68),
+  [131] = {.entry = {.count = 1, .reusable = false}}, SHIFT(68),
+  [133] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_s, 2),
+  [135] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_range, 1),
+  [137] = {.entry = {.count = 1, .reusable = true}}, SHIFT(49),
+  [139] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_range, 1),
+  [141] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_range, 3),
+  [143] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_range, 3),
+  [145] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_update, 1),
+  [147] = {.entry = {.count = 1, .reusable = true}}, SHIFT(11),
+  [149] = {.entry = {.count = 1, .reusable = true}}, SHIFT(45),
+  [151] = {.entry = {.count = 1, .reusable = true}}, SHIFT(58),
+  [153] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_write, 1),
+  [155] = {.entry = {.count = 1, .reusable = true}}, SHIFT(12),
+  [157] = {.entry = {.count = 1, .reusable = true}}, SHIFT(44),
+  [159] = {.entry = {.count = 1, .reusable = true}}, SHIFT(57),
+  [161] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_setlocal, 2),
+  [163] = {.entry = {.count = 1, .reusable = true}}, SHIFT(15),
+  [165] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_setglobal, 1),
+  [167] = {.entry = {.count = 1, .reusable = true}}, SHIFT(28),
+  [169] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_edit, 1),
+  [171] = {.entry = {.count = 1, .reusable = true}}, SHIFT(46),
+  [173] = {.entry = {.count = 1, .reusable = true}}, SHIFT(50),
+  [175] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_update, 2),
+  [177] = {.entry = {.count = 1, .reusable = true}}, SHIFT(33),
+  [179] = {.entry = {.count = 1, .reusable = true}}, SHIFT(95),
+  [181] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_write, 2),
+  [183] = {.entry = {.count = 1, .reusable = true}}, SHIFT(37),
+  [185] = {.entry = {.count = 1, .reusable = true}}, SHIFT(93),
+  [187] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_bdelete, 1),
+  [189] = {.entry = {.count = 1, .reusable = true}}, SHIFT(29),
+  [191] = {.entry = {.count = 1, .reusable = true}}, SHIFT(55),
+  [193] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_set, 2),
+  [195] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_set_repeat1, 2),
+  [197] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_set_repeat1, 2), SHIFT_REPEAT(15),
+  [200] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_setlocal, 1),
+  [202] = {.entry = {.count = 1, .reusable = true}}, SHIFT(8),
+  [204] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_nmap, 1),
+  [206] = {.entry = {.count = 1, .reusable = true}}, SHIFT(40),
+  [208] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_imap, 1),
+  [210] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_read, 1),
+  [212] = {.entry = {.count = 1, .reusable = true}}, SHIFT(62),
+  [214] = {.entry = {.count = 1, .reusable = true}}, SHIFT(63),
+  [216] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cmap, 1),
+  [218] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_noremap, 1),
+  [220] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_wq, 1),
+  [222] = {.entry = {.count = 1, .reusable = true}}, SHIFT(43),
+  [224] = {.entry = {.count = 1, .reusable = true}}, SHIFT(67),
+  [226] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_xit, 1),
+  [228] = {.entry = {.count = 1, .reusable = true}}, SHIFT(42),
+  [230] = {.entry = {.count = 1, .reusable = true}}, SHIFT(69),
+  [232] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_inoremap, 1),
+  [234] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cnoremap, 1),
+  [236] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_mksession, 1),
+  [238] = {.entry = {.count = 1, .reusable = true}}, SHIFT(41),
+  [240] = {.entry = {.count = 1, .reusable = true}}, SHIFT(73),
+  [242] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_set, 1),
+  [244] = {.entry = {.count = 1, .reusable = true}}, SHIFT(14),
+  [246] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_setglobal, 2),
+  [248] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_bdelete, 2),
+  [250] = {.entry = {.count = 1, .reusable = true}}, SHIFT(91),
+  [252] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_bprevious, 1),
+  [254] = {.entry = {.count = 1, .reusable = true}}, SHIFT(53),
+  [256] = {.entry = {.count = 1, .reusable = true}}, SHIFT(59),
+  [258] = {.entry = {.count = 1, .reusable = true}}, SHIFT(60),
+  [260] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_wall, 1),
+  [262] = {.entry = {.count = 1, .reusable = true}}, SHIFT(61),
+  [264] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_update, 3),
+  [266] = {.entry = {.count = 1, .reusable = true}}, SHIFT(103),
+  [268] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_quit, 1),
+  [270] = {.entry = {.count = 1, .reusable = true}}, SHIFT(47),
+  [272] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_qall, 1),
+  [274] = {.entry = {.count = 1, .reusable = true}}, SHIFT(70),
+  [276] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_wqall, 1),
+  [278] = {.entry = {.count = 1, .reusable = true}}, SHIFT(71),
+  [280] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_write, 3),
+  [282] = {.entry = {.count = 1, .reusable = true}}, SHIFT(102),
+  [284] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_buffer, 1),
+  [286] = {.entry = {.count = 1, .reusable = true}}, SHIFT(51),
+  [288] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_bnext, 1),
+  [290] = {.entry = {.count = 1, .reusable = true}}, SHIFT(52),
+  [292] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__map_args, 1),
+  [294] = {.entry = {.count = 1, .reusable = true}}, SHIFT(101),
+  [296] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_mksession, 2),
+  [298] = {.entry = {.count = 1, .reusable = true}}, SHIFT(100),
+  [300] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_xit, 2),
+  [302] = {.entry = {.count = 1, .reusable = true}}, SHIFT(99),
+  [304] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_wq, 2),
+  [306] = {.entry = {.count = 1, .reusable = true}}, SHIFT(98),
+  [308] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_edit, 2),
+  [310] = {.entry = {.count = 1, .reusable = true}}, SHIFT(90),
+  [312] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_quit, 2),
+  [314] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_rename, 2),
+  [316] = {.entry = {.count = 1, .reusable = true}}, SHIFT(5),
+  [318] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_buffer, 2),
+  [320] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_bnext, 2),
+  [322] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_bprevious, 2),
+  [324] = {.entry = {.count = 1, .reusable = true}},  ACCEPT_INPUT(),
+  [326] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_buffers, 1),
+  [328] = {.entry = {.count = 1, .reusable = true}}, SHIFT(96),
+  [330] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_saveas, 2),
+  [332] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_wall, 2),
+  [334] = {.entry = {.count = 1, .reusable = false}}, SHIFT_EXTRA(),
+  [336] = {.entry = {.count = 1, .reusable = true}}, SHIFT(97),
+  [338] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_read, 2),
+  [340] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_substitute, 2),
+  [342] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cmd, 1),
+  [344] = {.entry = {.count = 1, .reusable = true}}, SHIFT(64),
+  [346] = {.entry = {.count = 1, .reusable = true}}, SHIFT(86),
+  [348] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_qall, 2),
+  [350] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_wqall, 2),
+  [352] = {.entry = {.count = 1, .reusable = true}}, SHIFT(85),
+  [354] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_mkdir, 2),
+  [356] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_mkfile, 2),
+  [358] = {.entry = {.count = 1, .reusable = true}}, SHIFT(84),
+  [360] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_nmap, 2),
+  [362] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_imap, 2),
+  [364] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cmap, 2),
+  [366] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_noremap, 2),
+  [368] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_inoremap, 2),
+  [370] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cnoremap, 2),
+  [372] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_unmap, 2),
+  [374] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_iunmap, 2),
+  [376] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cunmap, 2),
+  [378] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_s, 3),
+  [380] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_remove, 1),
+  [382] = {.entry = {.count = 1, .reusable = true}}, SHIFT(48),
+  [384] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_edit, 3),
+  [386] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_bdelete, 3),
+  [388] = {.entry = {.count = 1, .reusable = true}}, SHIFT(75),
+  [390] = {.entry = {.count = 1, .reusable = true}}, SHIFT(74),
+  [392] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_saveas, 3),
+  [394] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_read, 3),
+  [396] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_wq, 3),
+  [398] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_xit, 3),
+  [400] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_mksession, 3),
+  [402] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__map_args, 2),
+  [404] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_write, 4),
+  [406] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_update, 4),
 };
 
 #ifdef __cplusplus
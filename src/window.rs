@@ -2,6 +2,8 @@ use crossterm;
 
 use std::{cmp, fmt, ops, ops::Add, result};
 
+pub use crate::window_finder::WindowFinder;
+pub use crate::window_hex::WindowHex;
 pub use crate::window_less::WindowLess;
 pub use crate::window_prompt::WindowPrompt;
 pub use crate::window_status::WindowStatus;
@@ -340,6 +342,12 @@ impl JumpList {
         }
     }
 
+    /// Return the jump, currently visited while moving around the
+    /// jump-list using [JumpList::older] and [JumpList::newer].
+    pub fn to_current(&self) -> Option<Jump> {
+        self.zero.clone()
+    }
+
     pub fn newer(&mut self, n: usize) {
         match self.inner.len() {
             0 => (),
@@ -450,6 +458,16 @@ impl Jump {
         }
     }
 
+    #[inline]
+    pub fn to_cursor(&self) -> usize {
+        self.cursor
+    }
+
+    #[inline]
+    pub fn to_buffer_id(&self) -> String {
+        self.buf_id.clone()
+    }
+
     fn update(mut self, edit: &event::Edit) -> Option<Self> {
         use crate::event::Edit;
 
@@ -468,7 +486,7 @@ impl Jump {
                     true => None,
                 }
             }
-            Edit::Chg { cursor, oldt, newt } => {
+            Edit::Chg { cursor, oldt, newt } if self.cursor >= *cursor => {
                 let n = text::width(oldt.chars());
                 let m = text::width(newt.chars());
                 match (*cursor..(*cursor + n)).contains(&self.cursor) {
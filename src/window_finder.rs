@@ -0,0 +1,327 @@
+#[allow(unused_imports)]
+use log::{debug, trace};
+
+use std::{cmp, fmt, iter::FromIterator, mem, path, result, sync::mpsc, thread, time};
+
+use crate::{
+    colors::{ColorScheme, Highlight},
+    event::Event,
+    fuzzy,
+    term::{Attribute, Span, Spanline, Style},
+    text,
+    window::{Coord, Cursor, WindowPrompt},
+    Error, Result,
+};
+
+/// Number of files sent by the indexer in one batch.
+const INDEX_BATCH: usize = 1024;
+/// Interval for collecting files from the indexer, while it is running.
+const INDEX_INTERVAL: time::Duration = time::Duration::from_millis(100);
+/// Prompt shown before the pattern.
+const PROMPT: &'static str = "> ";
+
+/// Floating window to pick a file by fuzzy matching its path. Files are
+/// indexed by a background thread, walking the directory recursively and
+/// skipping files ignored by `.gitignore` and hidden files, and matched
+/// as they are collected. First line of the window is the prompt line,
+/// rest of the lines list the matching files, best match first.
+pub struct WindowFinder {
+    coord: Coord,
+    scheme: ColorScheme,
+    dir: path::PathBuf,
+    prompt: WindowPrompt,
+    // indexer, None after all the files are collected.
+    rx: Option<mpsc::Receiver<Vec<String>>>,
+    files: Vec<String>,
+    pattern: String,
+    // matching files, (index into files, match), best match first.
+    matches: Vec<(usize, fuzzy::Match)>,
+    choice: usize,
+    top: usize,
+}
+
+impl fmt::Display for WindowFinder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "WindowFinder<{},{:?}>", self.coord, self.dir)
+    }
+}
+
+impl WindowFinder {
+    /// Create a finder window for files under `dir`, indexing starts
+    /// right away.
+    pub fn new(coord: Coord, dir: &path::Path, scheme: ColorScheme) -> Self {
+        let prompt = {
+            let coord = Coord::new(coord.col, coord.row, 1, coord.wth);
+            WindowPrompt::new(coord, vec![PROMPT.to_string()], scheme.clone())
+        };
+        WindowFinder {
+            coord,
+            scheme,
+            dir: dir.to_path_buf(),
+            prompt,
+            rx: Some(spawn_index(dir.to_path_buf())),
+            files: Vec::default(),
+            pattern: String::default(),
+            matches: Vec::default(),
+            choice: 0,
+            top: 0,
+        }
+    }
+}
+
+impl WindowFinder {
+    /// Return the selected file, if any.
+    pub fn to_choice(&self) -> Option<path::PathBuf> {
+        let (i, _) = self.matches.get(self.choice)?;
+        Some(self.dir.join(&self.files[*i]))
+    }
+
+    /// Return whether the indexer is still collecting files.
+    #[inline]
+    pub fn is_indexing(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    // collect files from indexer and match them with current pattern.
+    fn collect_files(&mut self) {
+        let rx = match self.rx.take() {
+            Some(rx) => rx,
+            None => return,
+        };
+        let start = self.files.len();
+        loop {
+            match rx.try_recv() {
+                Ok(files) => self.files.extend(files),
+                Err(mpsc::TryRecvError::Empty) => {
+                    self.rx = Some(rx);
+                    break;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    debug!(
+                        "finder indexed {} files in {:?}",
+                        self.files.len(),
+                        self.dir
+                    );
+                    break;
+                }
+            }
+        }
+        if self.files.len() > start {
+            let iter = fuzzy::rank(&self.pattern, &self.files[start..]).into_iter();
+            self.matches.extend(iter.map(|(i, m)| (i + start, m)));
+            self.sort_matches();
+        }
+    }
+
+    fn sort_matches(&mut self) {
+        let files = &self.files;
+        self.matches.sort_by(|(a, x), (b, y)| {
+            let (p, q) = (files[*a].len(), files[*b].len());
+            y.score.cmp(&x.score).then(p.cmp(&q)).then(a.cmp(b))
+        });
+    }
+
+    fn set_pattern(&mut self, pattern: String) {
+        // narrowing down the pattern can only drop matches.
+        self.matches = if pattern.starts_with(&self.pattern) {
+            let files = &self.files;
+            let iter = self.matches.drain(..);
+            iter.filter_map(|(i, _)| Some((i, fuzzy::fuzzy_match(&pattern, &files[i])?)))
+                .collect()
+        } else {
+            fuzzy::rank(&pattern, &self.files)
+        };
+        self.pattern = pattern;
+        self.sort_matches();
+        self.choice = 0;
+        self.top = 0;
+    }
+
+    fn move_choice(&mut self, up: bool) {
+        let n = self.matches.len();
+        self.choice = match (n, up) {
+            (0, _) => 0,
+            (n, true) => (self.choice + n - 1) % n,
+            (n, false) => (self.choice + 1) % n,
+        };
+        // keep the choice within the listed lines.
+        let hgt = self.coord.hgt.saturating_sub(1) as usize;
+        if self.choice < self.top {
+            self.top = self.choice
+        } else if self.choice >= self.top + hgt {
+            self.top = self.choice + 1 - hgt
+        }
+    }
+}
+
+impl WindowFinder {
+    #[inline]
+    pub fn to_name(&self) -> String {
+        "window-finder".to_string()
+    }
+
+    #[inline]
+    pub fn to_coord(&self) -> Coord {
+        self.coord
+    }
+
+    #[inline]
+    pub fn to_cursor(&self) -> Option<Cursor> {
+        let col = {
+            let n = text::width(PROMPT.chars()) + text::width(self.pattern.chars());
+            let n = cmp::min(n, self.coord.wth.saturating_sub(1) as usize) as u16;
+            curz!(self.coord.col) + n
+        };
+        Some(Cursor::new(col, curz!(self.coord.row)))
+    }
+
+    /// Poll the indexer while it is running.
+    pub fn to_timeout(&self) -> Option<time::Duration> {
+        if_else!(self.is_indexing(), Some(INDEX_INTERVAL), None)
+    }
+
+    /// `Enter` and `Esc` are returned back to the caller, to open the
+    /// [selected file](WindowFinder::to_choice) and to close the window.
+    pub fn on_event(&mut self, evnt: Event) -> Result<Event> {
+        let (empty, ctrl) = (evnt.to_modifiers().is_empty(), evnt.is_control());
+        match evnt {
+            Event::Timeout => self.collect_files(),
+            evnt @ Event::Esc => return Ok(evnt),
+            evnt @ Event::Enter(_) if empty => return Ok(evnt),
+            Event::Up(_) | Event::BackTab => self.move_choice(true),
+            Event::Char('p', _) if ctrl => self.move_choice(true),
+            Event::Down(_) | Event::Tab(_) => self.move_choice(false),
+            Event::Char('n', _) if ctrl => self.move_choice(false),
+            evnt => {
+                self.prompt.on_event(evnt)?;
+                let pattern = self.prompt.prompt_match().unwrap_or(String::default());
+                if pattern != self.pattern {
+                    self.set_pattern(pattern)
+                }
+            }
+        }
+        Ok(Event::Noop)
+    }
+
+    pub fn on_refresh(&mut self) -> Result<()> {
+        let (col, row) = self.coord.to_origin_cursor();
+        let wth = self.coord.wth as usize;
+        let canvas = self.scheme.to_style(Highlight::TabcLine);
+
+        // prompt line, with the number of matches on the right.
+        {
+            let status = {
+                let more = if_else!(self.is_indexing(), "+", "");
+                format!("{}/{}{} ", self.matches.len(), self.files.len(), more)
+            };
+            let mut line: Spanline = {
+                let s = format!("{:>width$}", status, width = wth);
+                let span: Span = String::from_iter(text::take_width(s.chars(), wth)).into();
+                span.using(canvas.clone()).into()
+            };
+            line.set_cursor(Cursor { col, row });
+            err_at!(Fatal, termqu!(line))?;
+            self.prompt.on_refresh()?;
+        }
+
+        let hgt = self.coord.hgt.saturating_sub(1);
+        let mut iter = self.matches.iter().enumerate().skip(self.top);
+        for r in (row + 1)..(row + 1 + hgt) {
+            let mut line = match iter.next() {
+                Some((i, (j, m))) => {
+                    let style = match i == self.choice {
+                        true => self.scheme.to_style(Highlight::TabcSelect),
+                        false => canvas.clone(),
+                    };
+                    self.to_span_line(&self.files[*j], &m.positions, style)
+                }
+                None => {
+                    let span: Span = format!("{:width$}", "", width = wth).into();
+                    span.using(canvas.clone()).into()
+                }
+            };
+            line.set_cursor(Cursor { col, row: r });
+            err_at!(Fatal, termqu!(line))?;
+        }
+
+        Ok(())
+    }
+
+    // render file path, matching characters are highlighted in bold and
+    // underlined, padded to the width of the window.
+    fn to_span_line(&self, file: &str, positions: &[usize], style: Style) -> Spanline {
+        let wth = self.coord.wth as usize;
+        let chars = text::take_width(format!(" {}", file).chars(), wth);
+
+        // group consecutive characters that are matching, or not.
+        let mut spans: Vec<Span> = vec![];
+        let mut positions = positions.iter().map(|p| p + 1).peekable();
+        let (mut s, mut matched) = (String::default(), false);
+        for (i, ch) in chars.enumerate() {
+            let ok = positions.peek() == Some(&i);
+            if ok {
+                positions.next();
+            }
+            if ok != matched && s.len() > 0 {
+                spans.push(Self::to_span(mem::take(&mut s), matched, &style));
+            }
+            matched = ok;
+            s.push(ch);
+        }
+        spans.push(Self::to_span(s, matched, &style));
+
+        let n = wth.saturating_sub(spans.iter().map(|span| span.to_width()).sum());
+        spans.push(Self::to_span(
+            format!("{:width$}", "", width = n),
+            false,
+            &style,
+        ));
+
+        Spanline::from_iter(spans.into_iter())
+    }
+
+    fn to_span(s: String, matched: bool, style: &Style) -> Span {
+        let span: Span = s.into();
+        let mut span = span.using(style.clone());
+        if matched {
+            span.attrs.push(Attribute::Bold);
+            span.attrs.push(Attribute::Underlined);
+        }
+        span
+    }
+}
+
+// walk `dir` recursively, in a separate thread, and send file paths,
+// relative to `dir`, in batches. Indexing stops when the receiver is
+// dropped, say when the finder window is closed.
+fn spawn_index(dir: path::PathBuf) -> mpsc::Receiver<Vec<String>> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut files = Vec::with_capacity(INDEX_BATCH);
+        for entry in ignore::WalkBuilder::new(&dir).build() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    debug!("finder skip {}", err);
+                    continue;
+                }
+            };
+            match entry.file_type() {
+                Some(ft) if ft.is_file() || ft.is_symlink() => (),
+                _ => continue,
+            }
+            let file = entry.path().strip_prefix(&dir).unwrap_or(entry.path());
+            files.push(file.to_string_lossy().to_string());
+            if files.len() >= INDEX_BATCH {
+                let batch = mem::replace(&mut files, Vec::with_capacity(INDEX_BATCH));
+                if tx.send(batch).is_err() {
+                    return;
+                }
+            }
+        }
+        tx.send(files).ok();
+    });
+
+    rx
+}
@@ -0,0 +1,455 @@
+//! Module `window_hex` implement the hex window, to view and edit binary
+//! content as an xxd style dump of offset, hex and ASCII columns. Bytes
+//! are written back as is, without any new-line or encoding conversion.
+
+#[allow(unused_imports)]
+use log::{debug, trace};
+
+use std::{cmp, fmt, result};
+
+use crate::{
+    colors::{ColorScheme, Highlight},
+    event::{Event, Mod, Mto, Scroll, DP},
+    keymap::KeyHex,
+    location::Location,
+    term::{Span, Spanline},
+    window::{Coord, Cursor},
+    Error, Result,
+};
+
+/// Number of bytes displayed in each row.
+const ROW_BYTES: usize = 16;
+
+/// Hex window, content is displayed as rows of [ROW_BYTES] bytes. Last
+/// line of the window is used as the prompt line.
+#[derive(Clone)]
+pub struct WindowHex {
+    coord: Coord,
+    loc: Location,
+    bytes: Vec<u8>,
+    scheme: ColorScheme,
+    keymap: KeyHex,
+    // cursor as nibble offset, and the first row displayed.
+    nibble: usize,
+    top: usize,
+    // editing state
+    replace: bool,
+    modified: bool,
+    written: bool,
+    // prompt state
+    prompt: Option<(usize, String)>,
+    pattern: Option<(Vec<u8>, DP)>,
+    message: Option<(String, Highlight)>,
+}
+
+impl Eq for WindowHex {}
+
+impl PartialEq for WindowHex {
+    fn eq(&self, other: &Self) -> bool {
+        let ok = self.coord == other.coord && self.bytes == other.bytes;
+        ok && self.nibble == other.nibble
+    }
+}
+
+impl fmt::Display for WindowHex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "WindowHex<{}>", self.coord)
+    }
+}
+
+impl WindowHex {
+    /// Create a hex window for `loc`, content is read as raw bytes.
+    pub fn new(loc: Location, coord: Coord, scheme: ColorScheme) -> Result<Self> {
+        let bytes = loc.read_bytes()?;
+        let w = WindowHex {
+            coord,
+            loc,
+            bytes,
+            scheme,
+            keymap: KeyHex::default(),
+            nibble: 0,
+            top: 0,
+            replace: false,
+            modified: false,
+            written: false,
+            prompt: None,
+            pattern: None,
+            message: None,
+        };
+        debug!("{} {} bytes", w, w.bytes.len());
+        Ok(w)
+    }
+}
+
+impl WindowHex {
+    #[inline]
+    pub fn to_location(&self) -> Location {
+        self.loc.clone()
+    }
+
+    /// Return whether bytes are edited since they were last written.
+    #[inline]
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Return whether bytes were written to the location, at least once.
+    #[inline]
+    pub fn is_written(&self) -> bool {
+        self.written
+    }
+
+    /// Return the terminal cursor, which is on the prompt line while
+    /// prompting, else on the nibble under the cursor.
+    pub fn to_cursor(&self) -> Option<Cursor> {
+        let (col, row) = self.coord.to_origin_cursor();
+        let (col, row) = match &self.prompt {
+            Some((_, text)) => {
+                let row = row + self.coord.hgt.saturating_sub(1);
+                (col + (text.chars().count() as u16), row)
+            }
+            None => {
+                let byte = self.nibble / 2;
+                let r = (byte / ROW_BYTES).saturating_sub(self.top);
+                let c = to_hex_col(byte % ROW_BYTES) + (self.nibble % 2);
+                (col + (c as u16), row + (r as u16))
+            }
+        };
+        Some(Cursor::new(cmp::min(col, self.coord.wth), row))
+    }
+
+    pub fn on_event(&mut self, evnt: Event) -> Result<Event> {
+        self.message = None;
+        if let Some((n, text)) = self.prompt.take() {
+            return self.on_prompt(n, text, evnt);
+        }
+
+        match self.keymap.fold(self.replace, evnt)? {
+            Event::Md(Mod::Replace(_, _)) => self.replace = true,
+            Event::Md(Mod::Esc) => self.replace = false,
+            Event::Char(':', _) => self.prompt = Some((1, ":".to_string())),
+            Event::Char(ch, _) => self.set_nibble(ch),
+            Event::Mt(Mto::Pattern(n, None, dp)) => {
+                let text = if_else!(dp == DP::Left, "?", "/").to_string();
+                self.prompt = Some((n, text));
+            }
+            Event::Mt(Mto::PatternR(n, dp)) => self.mto_pattern(n, dp),
+            Event::Mt(mto) => self.on_motion(mto),
+            _ => (),
+        }
+        self.align_top();
+
+        Ok(Event::Noop)
+    }
+
+    pub fn on_refresh(&mut self) -> Result<()> {
+        let coord = self.to_content_coord();
+        let (col, row) = coord.to_origin_cursor();
+        let style = self.scheme.to_style(Highlight::Canvas);
+        for r in 0..coord.hgt {
+            let offset = (self.top + (r as usize)) * ROW_BYTES;
+            let text = if offset < self.bytes.len() || offset == 0 {
+                let line = to_hex_line(&self.bytes, offset);
+                line.chars().take(coord.wth as usize).collect()
+            } else {
+                String::default()
+            };
+            let span: Span = text.into();
+            let mut line: Spanline = span.using(style.clone()).into();
+            let padding = coord.wth.saturating_sub(line.to_width() as u16);
+            line.set_cursor(Cursor::new(col, row + r))
+                .right_padding(padding);
+            err_at!(Fatal, termqu!(line))?;
+        }
+
+        self.render_prompt()
+    }
+}
+
+impl WindowHex {
+    // coordinate for the content, leaving out the prompt line.
+    fn to_content_coord(&self) -> Coord {
+        let mut coord = self.coord;
+        coord.hgt = coord.hgt.saturating_sub(1);
+        coord
+    }
+
+    #[inline]
+    fn to_last_nibble(&self) -> usize {
+        (self.bytes.len() * 2).saturating_sub(1)
+    }
+
+    fn on_prompt(&mut self, n: usize, mut text: String, evnt: Event) -> Result<Event> {
+        match evnt {
+            Event::Char(ch, _) if !ch.is_control() => {
+                text.push(ch);
+                self.prompt = Some((n, text));
+            }
+            Event::Backspace(_) => {
+                text.pop();
+                if !text.is_empty() {
+                    self.prompt = Some((n, text));
+                }
+            }
+            Event::Enter(_) if text.len() > 1 => {
+                let arg: String = text.chars().skip(1).collect();
+                match text.chars().next() {
+                    Some(':') => return Ok(self.on_command(arg.trim())),
+                    Some('?') => self.on_search(n, &arg, DP::Left),
+                    _ => self.on_search(n, &arg, DP::Right),
+                }
+                self.align_top();
+            }
+            Event::Enter(_) | Event::Esc => (),
+            _ => self.prompt = Some((n, text)),
+        }
+        Ok(Event::Noop)
+    }
+
+    // only writing and quitting commands are supported in hex window,
+    // quitting the window is notified as Event::Esc.
+    fn on_command(&mut self, cmd: &str) -> Event {
+        let res = match cmd {
+            "w" | "write" => self.write().map(|_| Event::Noop),
+            "wq" | "x" | "xit" => self.write().map(|_| Event::Esc),
+            "q" | "quit" if self.modified => {
+                err_at!(Invalid, msg: format!("no write since last change, add ! to override"))
+            }
+            "q" | "quit" | "q!" | "quit!" => Ok(Event::Esc),
+            cmd => err_at!(Invalid, msg: format!("not a hex window command {:?}", cmd)),
+        };
+        match res {
+            Ok(evnt) => evnt,
+            Err(err) => {
+                self.message = Some((err.to_string(), Highlight::Error));
+                Event::Noop
+            }
+        }
+    }
+
+    fn write(&mut self) -> Result<()> {
+        if self.loc.is_read_only() {
+            err_at!(Invalid, msg: format!("{} is read-only", self.loc))?
+        }
+        self.loc.save_bytes(&self.bytes)?;
+        self.modified = false;
+        self.written = true;
+
+        let msg = format!("{:?} {}B written", self.loc.to_string(), self.bytes.len());
+        self.message = Some((msg, Highlight::Canvas));
+        Ok(())
+    }
+
+    fn on_search(&mut self, n: usize, arg: &str, dp: DP) {
+        match parse_hex_pattern(arg) {
+            Ok(patt) => {
+                self.pattern = Some((patt, dp));
+                self.mto_pattern(n, DP::Right);
+            }
+            Err(err) => self.message = Some((err.to_string(), Highlight::Error)),
+        }
+    }
+
+    // search the last pattern `n` times, in the same direction as the
+    // pattern's search if `dp` is DP::Right, else in the opposite one.
+    fn mto_pattern(&mut self, n: usize, dp: DP) {
+        let (patt, dp) = match &self.pattern {
+            Some((patt, sdp)) if dp == DP::Right => (patt.clone(), *sdp),
+            Some((patt, DP::Left)) => (patt.clone(), DP::Right),
+            Some((patt, _)) => (patt.clone(), DP::Left),
+            None => {
+                let msg = "No previous pattern".to_string();
+                self.message = Some((msg, Highlight::Error));
+                return;
+            }
+        };
+
+        let mut byte = self.nibble / 2;
+        for _ in 0..n {
+            match find_bytes(&self.bytes, &patt, byte, dp) {
+                Some(off) => byte = off,
+                None => {
+                    let msg = "Pattern not found".to_string();
+                    self.message = Some((msg, Highlight::Error));
+                    return;
+                }
+            }
+        }
+        self.nibble = byte * 2;
+    }
+
+    fn on_motion(&mut self, mto: Mto) {
+        let row_nibbles = ROW_BYTES * 2;
+        let hgt = self.to_content_coord().hgt as usize;
+        let nibble = self.nibble;
+
+        self.nibble = match mto {
+            Mto::Left(n, _) => nibble.saturating_sub(n),
+            Mto::Right(n, _) => nibble.saturating_add(n),
+            Mto::Word(n, DP::Left, _) => (nibble / 2).saturating_sub(n) * 2,
+            Mto::Word(n, _, _) => (nibble / 2).saturating_add(n).saturating_mul(2),
+            Mto::Up(n, _) if n.saturating_mul(row_nibbles) <= nibble => nibble - n * row_nibbles,
+            Mto::Up(_, _) => nibble % row_nibbles,
+            Mto::Down(n, _) => nibble.saturating_add(n.saturating_mul(row_nibbles)),
+            Mto::LineHome(_) => nibble - (nibble % row_nibbles),
+            Mto::LineEnd(n, _) => {
+                let row = (nibble / row_nibbles).saturating_add(n.saturating_sub(1));
+                row.saturating_add(1).saturating_mul(row_nibbles) - 1
+            }
+            Mto::Row(n, _) => n.saturating_sub(1).saturating_mul(row_nibbles),
+            Mto::WinScroll(n, scrll, dp) => {
+                let rows = match scrll {
+                    Scroll::Lines if n == 1 => cmp::max(hgt / 2, 1),
+                    Scroll::Pages => hgt * n,
+                    _ => n,
+                };
+                let m = rows.saturating_mul(row_nibbles);
+                match dp {
+                    DP::Left if m <= nibble => nibble - m,
+                    DP::Left => nibble % row_nibbles,
+                    _ => nibble.saturating_add(m),
+                }
+            }
+            _ => nibble,
+        };
+        self.nibble = cmp::min(self.nibble, self.to_last_nibble());
+    }
+
+    fn set_nibble(&mut self, ch: char) {
+        let digit = match ch.to_digit(16) {
+            Some(digit) => digit as u8,
+            None => return,
+        };
+        if self.loc.is_read_only() {
+            let msg = format!("{} is read-only", self.loc);
+            self.message = Some((msg, Highlight::Error));
+            return;
+        }
+
+        match self.bytes.get_mut(self.nibble / 2) {
+            Some(b) if self.nibble % 2 == 0 => *b = (*b & 0x0F) | (digit << 4),
+            Some(b) => *b = (*b & 0xF0) | digit,
+            None => {
+                let msg = "No content to edit".to_string();
+                self.message = Some((msg, Highlight::Error));
+                return;
+            }
+        }
+        self.modified = true;
+        if self.replace {
+            self.nibble = cmp::min(self.nibble + 1, self.to_last_nibble());
+        }
+    }
+
+    // keep the cursor row within the window.
+    fn align_top(&mut self) {
+        let hgt = cmp::max(self.to_content_coord().hgt as usize, 1);
+        let row = self.nibble / 2 / ROW_BYTES;
+        if row < self.top {
+            self.top = row;
+        } else if row >= (self.top + hgt) {
+            self.top = row + 1 - hgt;
+        }
+    }
+
+    fn render_prompt(&self) -> Result<()> {
+        let (text, hl) = match (&self.prompt, &self.message) {
+            (Some((_, text)), _) => (text.clone(), Highlight::Canvas),
+            (None, Some((msg, hl))) => (msg.clone(), *hl),
+            (None, None) if self.replace => ("-- REPLACE --".to_string(), Highlight::Canvas),
+            (None, None) => {
+                let s = format!(
+                    "{}{} 0x{:x}/0x{:x}",
+                    self.loc,
+                    if_else!(self.modified, " [modified]", ""),
+                    self.nibble / 2,
+                    self.bytes.len()
+                );
+                (s, Highlight::Canvas)
+            }
+        };
+
+        let span: Span = text.into();
+        let mut line: Spanline = span.using(self.scheme.to_style(hl)).into();
+        let padding = self.coord.wth.saturating_sub(line.to_width() as u16);
+        let cursor = {
+            let (col, row) = self.coord.to_origin_cursor();
+            Cursor::new(col, row + self.coord.hgt.saturating_sub(1))
+        };
+        line.set_cursor(cursor).right_padding(padding);
+
+        err_at!(Fatal, termqu!(line))?;
+        Ok(())
+    }
+}
+
+// column of byte `i`, within a row, in the hex dump.
+fn to_hex_col(i: usize) -> usize {
+    10 + ((i / 2) * 5) + ((i % 2) * 2)
+}
+
+// xxd style dump of the row starting at byte `offset`.
+fn to_hex_line(bytes: &[u8], offset: usize) -> String {
+    let row = &bytes[offset..cmp::min(offset + ROW_BYTES, bytes.len())];
+
+    let mut line = format!("{:08x}: ", offset);
+    for i in 0..ROW_BYTES {
+        match row.get(i) {
+            Some(b) => line.push_str(&format!("{:02x}", b)),
+            None => line.push_str("  "),
+        }
+        if i % 2 == 1 {
+            line.push(' ');
+        }
+    }
+    line.push(' ');
+    for b in row.iter() {
+        line.push(if_else!(
+            b.is_ascii_graphic() || *b == b' ',
+            *b as char,
+            '.'
+        ));
+    }
+    line
+}
+
+// parse search pattern, a sequence of hex-digits optionally separated
+// by white-space, like `de ad beef`.
+fn parse_hex_pattern(patt: &str) -> Result<Vec<u8>> {
+    let mut digits = vec![];
+    for ch in patt.chars().filter(|ch| !ch.is_whitespace()) {
+        match ch.to_digit(16) {
+            Some(digit) => digits.push(digit as u8),
+            None => err_at!(Invalid, msg: format!("invalid hex pattern {:?}", patt))?,
+        }
+    }
+    if digits.len() == 0 || digits.len() % 2 != 0 {
+        err_at!(Invalid, msg: format!("invalid hex pattern {:?}", patt))?
+    }
+    Ok(digits.chunks(2).map(|ds| (ds[0] << 4) | ds[1]).collect())
+}
+
+// find `patt` in `bytes`, next to byte offset `from` in direction `dp`,
+// wrapping around the content.
+fn find_bytes(bytes: &[u8], patt: &[u8], from: usize, dp: DP) -> Option<usize> {
+    let n = (bytes.len() + 1).checked_sub(patt.len())?;
+    if patt.len() == 0 || n == 0 {
+        return None;
+    }
+
+    let is_match = |off: &usize| &bytes[*off..(*off + patt.len())] == patt;
+    match dp {
+        DP::Left => {
+            let iter = (0..cmp::min(from, n)).rev();
+            iter.chain((from..n).rev()).find(is_match)
+        }
+        _ => {
+            let iter = (from + 1)..n;
+            iter.chain(0..cmp::min(from + 1, n)).find(is_match)
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "window_hex_test.rs"]
+mod window_hex_test;
@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn test_hex_line() {
+    let bytes = "hello world\n\x00\x7f".as_bytes();
+    assert_eq!(
+        to_hex_line(bytes, 0),
+        "00000000: 6865 6c6c 6f20 776f 726c 640a 007f       hello world..."
+    );
+
+    let bytes: Vec<u8> = (0..20).collect();
+    assert_eq!(
+        to_hex_line(&bytes, 16),
+        "00000010: 1011 1213                                ...."
+    );
+    assert_eq!(to_hex_col(0), 10);
+    assert_eq!(to_hex_col(3), 17);
+    assert_eq!(to_hex_col(15), 47);
+}
+
+#[test]
+fn test_hex_pattern() {
+    assert_eq!(
+        parse_hex_pattern("de ad BEEF").unwrap(),
+        vec![0xde, 0xad, 0xbe, 0xef]
+    );
+    assert!(parse_hex_pattern("dea").is_err());
+    assert!(parse_hex_pattern("xy").is_err());
+    assert!(parse_hex_pattern("  ").is_err());
+
+    let bytes = vec![0xde, 0xad, 0x00, 0xde, 0xad, 0x01];
+    let patt = vec![0xde, 0xad];
+    assert_eq!(find_bytes(&bytes, &patt, 0, DP::Right), Some(3));
+    assert_eq!(find_bytes(&bytes, &patt, 3, DP::Right), Some(0));
+    assert_eq!(find_bytes(&bytes, &patt, 3, DP::Left), Some(0));
+    assert_eq!(find_bytes(&bytes, &patt, 0, DP::Left), Some(3));
+    assert_eq!(find_bytes(&bytes, &patt, 5, DP::Left), Some(3));
+    assert_eq!(find_bytes(&bytes, &[0xff], 0, DP::Right), None);
+    assert_eq!(find_bytes(&bytes[..1], &patt, 0, DP::Right), None);
+}
@@ -1,30 +1,53 @@
 #[allow(unused_imports)]
 use log::{debug, trace};
 
-use std::{convert::TryInto, fmt, mem, result};
+use std::{cmp, convert::TryInto, fmt, mem, result, time};
 
 use crate::{
-    buffer::Buffer,
-    colors::ColorScheme,
-    event::Event,
+    buffer::{self, Buffer},
+    colors::{ColorScheme, Highlight},
+    event::{self, Event, Mto, Scroll, DP},
     keymap::Keymap,
     location::Location,
-    syntax, view,
-    window::{Coord, Cursor},
-    Result,
+    state::Pipe,
+    syntax::{self, Syntax},
+    term::{Span, Spanline},
+    text, view,
+    window::{Coord, Cursor, Render, WinBuffer, Window},
+    Error, Result,
 };
 
+/// Interval for re-reading the content, when following a file.
+const FOLLOW_INTERVAL: time::Duration = time::Duration::from_millis(1000);
+
+/// Interval for reading the content, when paging through piped input.
+const PIPE_INTERVAL: time::Duration = time::Duration::from_millis(100);
+
+/// File encodings that can be decoded from any byte offset, when
+/// following a file.
+const TAIL_ENCODINGS: [&str; 4] = ["utf-8", "utf-8-bom", "latin1", "cp1252"];
+
+/// Pager window, to page through read-only content like the `less` command.
+/// Last line of the window is used as the prompt line.
 #[derive(Clone)]
 pub struct WindowLess {
     coord: Coord,
     cursor: Cursor,
+    obc_xy: buffer::Cursor,
     buffer: Buffer,
     syn: syntax::Syn,
     scheme: ColorScheme,
     keymap: Keymap,
     old_screen: Option<Vec<view::ScrLine>>,
+    // pager state
+    search: Option<(usize, String)>,
+    message: Option<String>,
+    follow: bool,
+    tail: Option<u64>,
+    pipe: Option<Pipe>,
     // configuration
     wrap: bool,
+    large_file: usize,
 }
 
 impl Eq for WindowLess {}
@@ -32,7 +55,7 @@ impl Eq for WindowLess {}
 impl PartialEq for WindowLess {
     fn eq(&self, other: &Self) -> bool {
         let mut ok = self.coord == other.coord;
-        ok = ok && self.buffer.to_string() == other.buffer.to_string();
+        ok = ok && self.buffer.to_rope() == other.buffer.to_rope();
         ok && self.wrap == other.wrap
     }
 }
@@ -50,74 +73,428 @@ impl WindowLess {
             let loc = Location::new_ted(&name, content.as_bytes(), read_only)?;
             Buffer::from_reader(loc)?
         };
+        let syn = (name, content, scheme.clone()).try_into()?;
+        Ok(Self::new_window(buf, syn, coord, scheme, std::usize::MAX))
+    }
+
+    /// Create a pager window for `buf`, syntax is detected from buffer's
+    /// location. Buffers larger than `large_file` bytes are not parsed.
+    pub fn from_buffer(
+        buf: Buffer,
+        coord: Coord,
+        scheme: ColorScheme,
+        large_file: usize,
+    ) -> Result<Self> {
+        let syn = syntax::detect(&buf, &scheme, large_file)?;
+        Ok(Self::new_window(buf, syn, coord, scheme, large_file))
+    }
+
+    fn new_window(
+        buf: Buffer,
+        syn: syntax::Syn,
+        coord: Coord,
+        scheme: ColorScheme,
+        large_file: usize,
+    ) -> Self {
         let mut w = WindowLess {
             coord,
             cursor: Cursor::default(),
+            obc_xy: buffer::Cursor::default(),
             buffer: buf,
-            syn: (name, content, scheme.clone()).try_into()?,
+            syn,
             scheme,
             keymap: Keymap::new_less(),
             old_screen: None,
+            search: None,
+            message: None,
+            follow: false,
+            tail: None,
+            pipe: None,
             wrap: false,
+            large_file,
         };
         debug!("{}", w);
         w.buffer.set_normal_mode();
-        Ok(w)
+        w
     }
 
     pub fn set_wrap(&mut self, wrap: bool) -> &mut Self {
         self.wrap = wrap;
         self
     }
+
+    /// Page through standard input `pipe`, content is appended to the
+    /// buffer as it arrives.
+    pub fn set_pipe(&mut self, pipe: Pipe) -> &mut Self {
+        self.pipe = Some(pipe);
+        self
+    }
 }
 
 impl WindowLess {
     #[inline]
-    pub fn to_name(&self) -> String {
-        "window-less".to_string()
+    pub fn to_location(&self) -> Location {
+        self.buffer.to_location()
     }
 
-    #[inline]
-    pub fn to_coord(&self) -> Coord {
-        self.coord
+    /// Return the terminal cursor, which is always on the prompt line.
+    pub fn to_cursor(&self) -> Option<Cursor> {
+        let (col, row) = self.coord.to_origin_cursor();
+        let row = row + self.coord.hgt.saturating_sub(1);
+        let col = match &self.search {
+            Some((_, text)) => col + (text.chars().count() as u16),
+            None => col + 1,
+        };
+        Some(Cursor::new(cmp::min(col, self.coord.wth), row))
     }
 
-    #[inline]
-    pub fn to_cursor(&self) -> Option<Cursor> {
-        None
+    /// Return the duration to wait for the next key, before sending a
+    /// [Event::Timeout]. Pager wakes up periodically only when following,
+    /// or when reading piped input.
+    pub fn to_timeout(&self) -> Option<time::Duration> {
+        match (&self.pipe, self.follow) {
+            (Some(_), _) => Some(PIPE_INTERVAL),
+            (None, true) => Some(FOLLOW_INTERVAL),
+            (None, false) => None,
+        }
     }
 
     pub fn on_event(&mut self, evnt: Event) -> Result<Event> {
         match evnt {
-            evnt @ Event::Esc => Ok(evnt),
-            evnt => {
-                let mut km = mem::replace(&mut self.keymap, Keymap::default());
-                let evnt = km.fold(&self.buffer, evnt)?;
-                let evnt = self.buffer.on_event(evnt)?;
-                self.keymap = km;
-                Ok(evnt)
+            Event::Timeout => {
+                match &self.pipe {
+                    Some(_) => self.pipe_tail()?,
+                    None if self.follow => self.follow_tail()?,
+                    None => (),
+                }
+                if self.follow {
+                    self.mto_end()?;
+                }
+                return Ok(Event::Noop);
             }
+            _ => self.follow = false,
+        }
+
+        self.message = None;
+        if let Some((n, text)) = self.search.take() {
+            return self.on_search(n, text, evnt);
         }
+
+        let mut km = mem::replace(&mut self.keymap, Keymap::default());
+        let evnt = km.fold(&self.buffer, evnt);
+        self.keymap = km;
+
+        match evnt? {
+            Event::Esc => return Ok(Event::Esc),
+            Event::Mt(Mto::Pattern(n, None, dp)) => {
+                let text = if_else!(dp == DP::Left, "?", "/").to_string();
+                self.search = Some((n, text));
+            }
+            Event::Mt(Mto::WinScroll(_, Scroll::Follow, _)) => {
+                self.follow = true;
+                self.mto_end()?;
+            }
+            Event::Mt(mto @ Mto::WinScroll(_, _, _)) => self.mto_win_scroll(mto)?,
+            Event::Mt(Mto::Row(std::usize::MAX, _)) => self.mto_end()?,
+            Event::Mt(mto @ Mto::Row(_, _)) => {
+                self.buffer.on_event(Event::Mt(mto))?;
+                self.align_top();
+            }
+            Event::Mt(mto @ Mto::Pattern(_, _, _)) | Event::Mt(mto @ Mto::PatternR(_, _)) => {
+                self.mto_pattern(mto)
+            }
+            _ => (),
+        };
+
+        Ok(Event::Noop)
     }
 
     pub fn on_refresh(&mut self) -> Result<()> {
-        //use crate::Error;
-        //use crossterm::queue;
-        //use std::io::{self, Write};
-
-        //let (col, row_iter) = {
-        //    let (col, _) = self.coord.to_origin_cursor();
-        //    let start = self.coord.hgt.saturating_sub(
-        //        self.span_lines.len() as u16
-        //    );
-        //    (col, start..self.coord.hgt)
-        //};
-        //for (row, line) in row_iter.zip(self.span_lines.iter_mut()) {
-        //    line.set_cursor(Cursor { col, row });
-        //    err_at!(Fatal, termqu!(line))?;
-        //}
-        //let span: Span = self.buffer.to_string().into();
-        //err_at!(Fatal, termqu!(span))?;
+        let (cursor, lines) = if self.wrap {
+            let mut v: view::Wrap = (&*self, self.obc_xy).try_into()?;
+            v.shift_cursor(&self.buffer)?;
+            let lines = v.to_edit_lines(&self.buffer);
+            let old_screen = self.old_screen.replace(lines.clone());
+            (v.render(&self.buffer, self, old_screen)?, lines)
+        } else {
+            let mut v: view::NoWrap = (&*self, self.obc_xy).try_into()?;
+            v.shift_cursor(&self.buffer)?;
+            let lines = v.to_edit_lines(&self.buffer);
+            let old_screen = self.old_screen.replace(lines.clone());
+            (v.render(&self.buffer, self, old_screen)?, lines)
+        };
+        self.cursor = cursor;
+        self.obc_xy = self.buffer.to_xy_cursor(None);
+
+        self.render_prompt(&lines)
+    }
+}
+
+impl WindowLess {
+    // coordinate for the content, leaving out the prompt line.
+    fn to_content_coord(&self) -> Coord {
+        let mut coord = self.coord;
+        coord.hgt = coord.hgt.saturating_sub(1);
+        coord
+    }
+
+    fn on_search(&mut self, n: usize, mut text: String, evnt: Event) -> Result<Event> {
+        match evnt {
+            Event::Char(ch, _) if !ch.is_control() => {
+                text.push(ch);
+                self.search = Some((n, text));
+            }
+            Event::Backspace(_) => {
+                text.pop();
+                if !text.is_empty() {
+                    self.search = Some((n, text));
+                }
+            }
+            Event::Enter(_) if text.len() > 1 => {
+                let dp = if_else!(text.starts_with('?'), DP::Left, DP::Right);
+                let patt: String = text.chars().skip(1).collect();
+                self.mto_pattern(Mto::Pattern(n, Some(patt), dp));
+            }
+            Event::Enter(_) | Event::Esc => (),
+            _ => self.search = Some((n, text)),
+        }
+        Ok(Event::Noop)
+    }
+
+    fn mto_pattern(&mut self, mto: Mto) {
+        let from = self.buffer.to_char_cursor();
+        match self.buffer.on_event(Event::Mt(mto)) {
+            Ok(_) if self.buffer.to_char_cursor() == from => {
+                self.message = Some("Pattern not found".to_string());
+            }
+            Ok(_) => self.align_top(),
+            Err(err) => self.message = Some(err.to_string()),
+        }
+    }
+
+    fn mto_win_scroll(&mut self, mto: Mto) -> Result<()> {
+        use crate::scroll::{scroll_down, scroll_up};
+
+        let name = format!("mto_win_scroll-{}", mto);
+        let (n, scrll, dp) = match mto {
+            Mto::WinScroll(n, scrll, dp) => (n, scrll, dp),
+            mto => err_at!(Fatal, msg: format!("{}", mto))?,
+        };
+
+        let hgt = self.to_content_coord().hgt as usize;
+        let n = match &scrll {
+            Scroll::Ones => n,
+            Scroll::Lines if n == 1 => cmp::max(hgt / 2, 1),
+            Scroll::Lines => n,
+            Scroll::Pages => hgt * n,
+            scrll => err_at!(Fatal, msg: format!("{} {}", scrll, dp))?,
+        };
+        let (cursor, nbc) = match dp {
+            DP::Left => scroll_up(&name, self, &self.buffer, n)?,
+            DP::Right => scroll_down(&name, self, &self.buffer, n)?,
+            dp => err_at!(Fatal, msg: format!("{} {}", scrll, dp))?,
+        };
+        self.obc_xy = self.buffer.to_xy_cursor(Some(nbc));
+        self.cursor = cursor;
+        self.buffer.set_cursor(nbc);
+
         Ok(())
     }
+
+    // move to the end of the content, with last line at the bottom.
+    fn mto_end(&mut self) -> Result<()> {
+        self.buffer
+            .on_event(Event::Mt(Mto::Row(std::usize::MAX, DP::None)))?;
+        let n = self.buffer.n_chars();
+        self.buffer.set_cursor(n.saturating_sub(1));
+
+        let hgt = self.to_content_coord().hgt;
+        let nbc_xy = self.buffer.to_xy_cursor(None);
+        self.cursor.row = cmp::min(hgt.saturating_sub(1) as usize, nbc_xy.row) as u16;
+        self.obc_xy = nbc_xy;
+        Ok(())
+    }
+
+    // align the cursor line at the top of the screen.
+    fn align_top(&mut self) {
+        self.cursor.row = 0;
+        self.obc_xy = self.buffer.to_xy_cursor(None);
+    }
+
+    // append the content written to file since it was last read, keeping
+    // the detected syntax. A multi-byte sequence cut short at the end is
+    // read again with the next tail. Content is re-read, if the file was
+    // truncated, compressed, or its encoding is not byte-oriented.
+    fn follow_tail(&mut self) -> Result<()> {
+        let loc = self.buffer.to_location();
+        let (enc, from) = match &loc {
+            Location::Disk { enc, stat, .. } if TAIL_ENCODINGS.contains(&enc.as_str()) => {
+                (enc.clone(), self.tail.or(stat.map(|(_, n)| n)))
+            }
+            Location::Disk { .. } => return self.reload(),
+            _ => return Ok(()),
+        };
+        let bytes = match from {
+            Some(from) => loc.read_tail(from)?,
+            None => None,
+        };
+        let (from, bytes) = match (from, bytes) {
+            (Some(from), Some(bytes)) => (from, bytes),
+            (_, _) => return self.reload(),
+        };
+
+        let n = self.append_tail(&bytes, &enc, false)?;
+        self.tail = Some(from + (n as u64));
+        Ok(())
+    }
+
+    // append the piped input that arrived since the last read, stop
+    // reading once standard input is closed and its content is consumed.
+    fn pipe_tail(&mut self) -> Result<()> {
+        let from = self.tail.unwrap_or(0);
+        let (enc, bytes, eof) = match &self.pipe {
+            Some(pipe) => pipe.to_tail(from as usize),
+            None => return Ok(()),
+        };
+        let n = match enc {
+            Some(enc) => self.append_tail(&bytes, &enc, eof)?,
+            None => 0,
+        };
+        self.tail = Some(from + (n as u64));
+        if eof && n == bytes.len() {
+            self.pipe = None;
+        }
+        Ok(())
+    }
+
+    // append `bytes` decoded using `enc` to the end of buffer, keeping the
+    // detected syntax. Return the number of bytes consumed. Unless `eof`,
+    // a multi-byte sequence cut short at the end is left for the next
+    // tail, and encodings that can't be decoded from any byte offset
+    // wait for the entire content.
+    fn append_tail(&mut self, bytes: &[u8], enc: &str, eof: bool) -> Result<usize> {
+        let n = match std::str::from_utf8(bytes) {
+            _ if eof => bytes.len(),
+            _ if !TAIL_ENCODINGS.contains(&enc) => 0,
+            Err(err) if enc.starts_with("utf-8") && err.error_len().is_none() => err.valid_up_to(),
+            _ => bytes.len(),
+        };
+        if n > 0 {
+            let txt: String = match text::Encoding::decode(&bytes[..n], enc) {
+                Ok(val) => val.into(),
+                Err(_) => String::from_utf8_lossy(&bytes[..n]).to_string(),
+            };
+            let cursor = self.buffer.n_chars();
+            self.buffer.cud_str(Some(cursor), &txt)?;
+            let evnt = Event::Edit(event::Edit::new_ins(cursor, txt));
+            self.syn.on_edit(&self.buffer, evnt)?;
+        }
+        Ok(n)
+    }
+
+    // re-read the content from disk, applicable only for file locations.
+    fn reload(&mut self) -> Result<()> {
+        self.tail = None;
+        let loc = self.buffer.to_location();
+        match &loc {
+            Location::Disk { .. } => (),
+            _ => return Ok(()),
+        }
+        let mut buf = Buffer::from_reader(loc)?;
+        if buf.to_rope() == self.buffer.to_rope() {
+            return Ok(());
+        }
+
+        buf.set_normal_mode();
+        self.syn = syntax::detect(&buf, &self.scheme, self.large_file)?;
+        self.buffer = buf;
+        Ok(())
+    }
+
+    fn render_prompt(&self, lines: &[view::ScrLine]) -> Result<()> {
+        let at_end = {
+            let n = self.buffer.n_chars();
+            n == 0
+                || lines
+                    .iter()
+                    .any(|l| l.n > 0 && (l.bc + (l.n as usize)) >= n)
+        };
+        let (text, hl) = match (&self.search, &self.message) {
+            (Some((_, text)), _) => (text.clone(), Highlight::Canvas),
+            (None, Some(msg)) => (msg.clone(), Highlight::Error),
+            (None, None) if self.follow => {
+                let s = "Waiting for data... (any key to stop)";
+                (s.to_string(), Highlight::Canvas)
+            }
+            (None, None) if at_end => ("(END)".to_string(), Highlight::Canvas),
+            (None, None) => (":".to_string(), Highlight::Canvas),
+        };
+
+        let span: Span = text.into();
+        let mut line: Spanline = span.using(self.scheme.to_style(hl)).into();
+        let padding = self.coord.wth.saturating_sub(line.to_width() as u16);
+        let cursor = {
+            let (col, row) = self.coord.to_origin_cursor();
+            Cursor::new(col, row + self.coord.hgt.saturating_sub(1))
+        };
+        line.set_cursor(cursor).right_padding(padding);
+
+        err_at!(Fatal, termqu!(line))?;
+        Ok(())
+    }
+}
+
+impl Window for WindowLess {
+    type App = ();
+
+    #[inline]
+    fn to_name(&self) -> String {
+        "window-less".to_string()
+    }
+
+    #[inline]
+    fn to_coord(&self) -> Coord {
+        self.to_content_coord()
+    }
+
+    #[inline]
+    fn to_cursor(&self) -> Option<Cursor> {
+        Some(self.cursor)
+    }
+
+    #[inline]
+    fn config_wrap(&self) -> bool {
+        self.wrap
+    }
+
+    #[inline]
+    fn config_line_number(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn config_scroll_offset(&self) -> u16 {
+        0
+    }
+
+    fn on_event(&mut self, _: &mut (), evnt: Event) -> Result<Event> {
+        WindowLess::on_event(self, evnt)
+    }
+
+    fn on_refresh(&mut self, _: &mut ()) -> Result<()> {
+        WindowLess::on_refresh(self)
+    }
+}
+
+impl Render for WindowLess {
+    type Buf = Buffer;
+
+    #[inline]
+    fn as_color_scheme(&self) -> &ColorScheme {
+        &self.scheme
+    }
+
+    fn to_span_line(&self, buf: &Self::Buf, a: usize, z: usize) -> Result<Spanline> {
+        self.syn.to_span_line(buf, a, z)
+    }
 }
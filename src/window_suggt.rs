@@ -139,6 +139,27 @@ impl WindowSuggest {
                 }
                 WindowSuggest::Empty { .. } => evnt,
             },
+            evnt @ Event::BackTab => match self {
+                WindowSuggest::Active {
+                    choice,
+                    suggestions,
+                    ..
+                } => {
+                    *choice = match suggestions.len() {
+                        0 => None,
+                        n => match choice.take() {
+                            Some(0) => None,
+                            Some(o) => Some(o - 1),
+                            None => Some(n - 1),
+                        },
+                    };
+                    match choice {
+                        Some(o) => Event::TabInsert(suggestions[*o].clone()),
+                        None => Event::TabClear,
+                    }
+                }
+                WindowSuggest::Empty { .. } => evnt,
+            },
             evnt => evnt,
         };
         Ok(evnt)